@@ -5,6 +5,10 @@ use tokio::fs::{File, OpenOptions};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
 use std::path::Path;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use rand::RngCore;
 use tokio::sync::Mutex;
 use auth::login_service_server::{LoginService, LoginServiceServer};
 use auth::create_service_server::{CreateService, CreateServiceServer};
@@ -27,6 +31,9 @@ struct UserRecord {
 }
 
 const USER_DATA_FILE: &str = "users.jsonl";
+// Key shared with the game servers, which check session tokens offline
+const SESSION_KEY_FILE: &str = "session.key";
+const SESSION_TTL_SECS: u64 = 3600;
 
 async fn load_users(path: &Path) -> Result<Vec<UserRecord>, std::io::Error> {
     if !path.exists() {
@@ -79,11 +86,38 @@ fn verify_password(hashed_password: &str, password: &str) -> Result<bool, argon2
     Ok(Argon2::default().verify_password(password.as_bytes(), &parsed_hash).is_ok())
 }
 
+async fn load_session_key(path: &Path) -> Result<Vec<u8>, std::io::Error> {
+    if !path.exists() {
+        let mut key = [0u8; 32];
+        OsRng.fill_bytes(&mut key);
+        // Only the owner may read the key, anyone holding it can forge sessions
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(path)
+            .await?;
+        file.write_all(hex::encode(key).as_bytes()).await?;
+        println!("Generated a new session key in {}", path.display());
+    }
+    let content = tokio::fs::read(path).await?;
+    Ok(content.trim_ascii().to_vec())
+}
+
+// Token format checked by the game server: `{username}:{expires_at}:{hex hmac-sha256}`
+fn sign_session(key: &[u8], username: &str, expires_at: u64) -> String {
+    let message = format!("{}:{}", username, expires_at);
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(message.as_bytes());
+    format!("{}:{}", message, hex::encode(mac.finalize().into_bytes()))
+}
+
 type SharedUsers = Arc<Mutex<Vec<UserRecord>>>;
 
 #[derive(Debug)]
 pub struct MyLoginService {
-    users: SharedUsers
+    users: SharedUsers,
+    session_key: Arc<Vec<u8>>,
 }
 
 #[tonic::async_trait]
@@ -101,10 +135,15 @@ impl LoginService for MyLoginService {
                 match verify_password(&user.password_hash, &req_data.password) {
                     Ok(true) => {
                         println!("Authentification successfull for user: {}", user.username);
+                        let now = SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .map(|elapsed| elapsed.as_secs())
+                            .unwrap_or(0);
                         let reply = AuthentificationResponse{
                             success: true,
                             user_id: user.username.clone(),
                             message: "Authentification successfull".to_string(),
+                            session_token: sign_session(&self.session_key, &user.username, now + SESSION_TTL_SECS),
                         };
                         Ok(Response::new(reply))
                     }
@@ -114,6 +153,7 @@ impl LoginService for MyLoginService {
                             success: false,
                             user_id: user.username.clone(),
                             message: "Authentification failed".to_string(),
+                            session_token: String::new(),
                         };
                         Ok(Response::new(reply))
                     }
@@ -129,6 +169,7 @@ impl LoginService for MyLoginService {
                     success: false,
                     user_id: req_data.username.clone(),
                     message: "Authentification failed: user not found".to_string(),
+                    session_token: String::new(),
                 };
                 Ok(Response::new(reply))
             }
@@ -203,11 +244,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
+    let session_key = match load_session_key(Path::new(SESSION_KEY_FILE)).await {
+        Ok(key) => Arc::new(key),
+        Err(e) => {
+            eprintln!("FATAL: could not load or create file {}: {}", SESSION_KEY_FILE, e);
+            return Err(Box::new(e))
+        }
+    };
+
     let shared_users = Arc::new(Mutex::new(initial_users));
     let user_file_path_arc = Arc::from(user_file);
 
     let login_service = MyLoginService{
         users: Arc::clone(&shared_users),
+        session_key,
     };
     let create_service = MyCreateService{
         users: Arc::clone(&shared_users),
@@ -1,6 +1,7 @@
 package communication
 
 import (
+	"encoding/binary"
 	"errors"
 	"log"
 	"strconv"
@@ -51,22 +52,35 @@ func DecodeRLE(rle []byte) ([21][51]int, error) {
 	return grid, nil
 }
 
-// DecodeDeltas converts a slice of 3-byte arrays into a slice of 3-integer arrays.
-// Each 3-byte array represents a delta with the following format:
-//   - delta[0]: X-coordinate (byte)
-//   - delta[1]: Y-coordinate (byte)
-//   - delta[2]: Value (byte)
+// DecodeBinaryRLE decodes the keyframe encoding of protocol version 2 into a view of size cells.
+//
+// Each run is the cell code as a byte followed by the run length as an unsigned LEB128 varint.
 //
 // Parameters:
-//   - deltas: A slice of 3-byte arrays, where each array represents a delta.
+//   - rle: A byte slice containing the RLE-encoded data.
+//   - size: The number of cells of the view, rows times columns.
 //
 // Returns:
-//   - A slice of 3-integer arrays, where each array contains the decoded X, Y, and Value.
-func DecodeDeltas(deltas [][3]byte) [][3]int {
-	var decodedDeltas [][3]int
-	for _, delta := range deltas {
-		decodedDelta := [3]int{int(delta[0]), int(delta[1]), int(delta[2])}
-		decodedDeltas = append(decodedDeltas, decodedDelta)
+//   - The cell codes of the view, row by row.
+//   - An error if the RLE data is malformed or does not hold exactly size cells.
+func DecodeBinaryRLE(rle []byte, size int) ([]byte, error) {
+	decoded := make([]byte, 0, size)
+	for i := 0; i < len(rle); {
+		value := rle[i]
+		count, n := binary.Uvarint(rle[i+1:])
+		if n <= 0 {
+			return nil, errors.New("Failed to decode RLE run length")
+		}
+		if count > uint64(size-len(decoded)) {
+			return nil, errors.New("RLE holds more cells than the view")
+		}
+		for range count {
+			decoded = append(decoded, value)
+		}
+		i += 1 + n
+	}
+	if len(decoded) != size {
+		return nil, errors.New("RLE holds fewer cells than the view")
 	}
-	return decodedDeltas
+	return decoded, nil
 }
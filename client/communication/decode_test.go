@@ -100,3 +100,25 @@ func checkGridsMatch(t *testing.T, grid1, grid2 [21][51]int) {
 	}
 }
 
+
+func TestDecodeBinaryRLE(t *testing.T) {
+	// 200 walls then 3 empty cells, 200 needs a two byte run length
+	rle := []byte{0, 0xc8, 0x01, 1, 3}
+	decoded, err := communication.DecodeBinaryRLE(rle, 203)
+	if err != nil {
+		t.Fatalf("Expected no error, got %v", err)
+	}
+	if decoded[199] != 0 || decoded[200] != 1 || decoded[202] != 1 {
+		t.Errorf("Unexpected cells around the second run: %v", decoded[198:])
+	}
+
+	if _, err := communication.DecodeBinaryRLE(rle, 202); err == nil {
+		t.Error("Expected an error when the RLE holds more cells than the view")
+	}
+	if _, err := communication.DecodeBinaryRLE(rle, 204); err == nil {
+		t.Error("Expected an error when the RLE holds fewer cells than the view")
+	}
+	if _, err := communication.DecodeBinaryRLE([]byte{0, 0x80}, 203); err == nil {
+		t.Error("Expected an error for a truncated run length")
+	}
+}
@@ -0,0 +1,79 @@
+package communication
+
+import (
+	"errors"
+	"fmt"
+	"slices"
+
+	"github.com/GrGLeo/ctf/shared"
+)
+
+// The game server falls back to a keyframe once the acknowledged frame is older than this
+const frameHistory = 64
+
+// FrameDecoder rebuilds the board from the frames of a protocol version 2 connection.
+// Deltas are computed against the last acknowledged frame, so every frame from the
+// last ack onward is kept.
+type FrameDecoder struct {
+	rows, cols int
+	frames     map[uint32][]byte
+}
+
+func NewFrameDecoder() *FrameDecoder {
+	return &FrameDecoder{
+		rows:   21,
+		cols:   51,
+		frames: make(map[uint32][]byte),
+	}
+}
+
+// SetViewport changes the size of the view, the server follows a resize with a keyframe
+func (d *FrameDecoder) SetViewport(rows, cols int) {
+	d.rows = rows
+	d.cols = cols
+	clear(d.frames)
+}
+
+// Apply decodes a frame and returns the board it holds
+func (d *FrameDecoder) Apply(frame *shared.FramePacket) ([][]int, error) {
+	var cells []byte
+	if frame.BaseTick == 0 {
+		decoded, err := DecodeBinaryRLE(frame.Data, d.rows*d.cols)
+		if err != nil {
+			return nil, err
+		}
+		cells = decoded
+	} else {
+		base, ok := d.frames[frame.BaseTick]
+		if !ok {
+			return nil, fmt.Errorf("missing base frame %d", frame.BaseTick)
+		}
+		if len(frame.Data)%3 != 0 {
+			return nil, errors.New("delta is not made of (col, row, value) triples")
+		}
+		cells = slices.Clone(base)
+		for i := 0; i < len(frame.Data); i += 3 {
+			col, row, value := int(frame.Data[i]), int(frame.Data[i+1]), frame.Data[i+2]
+			if row >= d.rows || col >= d.cols {
+				return nil, fmt.Errorf("delta cell (%d, %d) is outside the view", row, col)
+			}
+			cells[row*d.cols+col] = value
+		}
+	}
+
+	d.frames[frame.Tick] = cells
+	for tick := range d.frames {
+		if tick < frame.BaseTick || tick+frameHistory < frame.Tick {
+			delete(d.frames, tick)
+		}
+	}
+
+	board := make([][]int, d.rows)
+	for row := range board {
+		board[row] = make([]int, d.cols)
+		for col := range board[row] {
+			board[row][col] = int(cells[row*d.cols+col])
+		}
+	}
+	return board, nil
+}
@@ -0,0 +1,90 @@
+package communication_test
+
+import (
+	"testing"
+
+	"github.com/GrGLeo/ctf/client/communication"
+	"github.com/GrGLeo/ctf/shared"
+)
+
+func frame(tick, baseTick uint32, data []byte) *shared.FramePacket {
+	return shared.NewFramePacket(tick, baseTick, 400, 500, 80, 100, 2, 40, 75, 0, data)
+}
+
+func TestFrameDecoderAppliesDeltas(t *testing.T) {
+	decoder := communication.NewFrameDecoder()
+	decoder.SetViewport(11, 21)
+
+	board, err := decoder.Apply(frame(1, 0, []byte{1, 231, 0x01}))
+	if err != nil {
+		t.Fatalf("Expected the keyframe to apply, got %v", err)
+	}
+	if len(board) != 11 || len(board[0]) != 21 || board[10][20] != 1 {
+		t.Fatalf("Unexpected keyframe board %v", board)
+	}
+
+	// (col, row, value) triples
+	board, err = decoder.Apply(frame(2, 1, []byte{3, 2, 4, 20, 10, 0}))
+	if err != nil {
+		t.Fatalf("Expected the delta to apply, got %v", err)
+	}
+	if board[2][3] != 4 || board[10][20] != 0 || board[0][0] != 1 {
+		t.Errorf("Delta was not applied on top of frame 1: %v", board)
+	}
+
+	// Still against frame 1 while frame 2 is not acknowledged
+	board, err = decoder.Apply(frame(3, 1, []byte{0, 0, 5}))
+	if err != nil {
+		t.Fatalf("Expected the delta to apply, got %v", err)
+	}
+	if board[0][0] != 5 || board[2][3] != 1 {
+		t.Errorf("Delta was not applied on top of frame 1: %v", board)
+	}
+
+	// Frame 1 is dropped once a delta is based on a later frame
+	if _, err := decoder.Apply(frame(4, 3, nil)); err != nil {
+		t.Fatalf("Expected the delta to apply, got %v", err)
+	}
+	if _, err := decoder.Apply(frame(5, 1, nil)); err == nil {
+		t.Error("Expected an error for a pruned base frame")
+	}
+}
+
+func TestFrameDecoderRejectsBadFrames(t *testing.T) {
+	decoder := communication.NewFrameDecoder()
+	if _, err := decoder.Apply(frame(1, 0, []byte{1, 100})); err == nil {
+		t.Error("Expected an error for a keyframe smaller than the 21x51 view")
+	}
+	if _, err := decoder.Apply(frame(2, 0, []byte{1, 0xaf, 0x08})); err != nil {
+		t.Fatalf("Expected the keyframe to apply, got %v", err)
+	}
+	if _, err := decoder.Apply(frame(3, 2, []byte{51, 0, 1})); err == nil {
+		t.Error("Expected an error for a cell outside the view")
+	}
+	if _, err := decoder.Apply(frame(4, 2, []byte{0, 0})); err == nil {
+		t.Error("Expected an error for an incomplete triple")
+	}
+}
+
+func TestFramedPacketRoundTrip(t *testing.T) {
+	sent := frame(7, 5, []byte{1, 2, 3})
+	data := shared.Frame(sent)
+	if data[0] != shared.FramedVersion || data[1] != 10 || int(data[2])<<8|int(data[3]) != len(data)-4 {
+		t.Fatalf("Unexpected framed header % x", data[:4])
+	}
+
+	// A trailing unknown packet is skipped as a whole
+	data = append(data, shared.FramedVersion, 99, 0, 2, 0xff, 0xff)
+	packet, consumed, err := shared.DeSerialize(data)
+	if err != nil {
+		t.Fatalf("Expected no error, got %v", err)
+	}
+	received, ok := packet.(*shared.FramePacket)
+	if !ok || received.Tick != 7 || received.BaseTick != 5 || received.XpNeeded != 75 || string(received.Data) != string(sent.Data) {
+		t.Fatalf("Unexpected packet %+v", packet)
+	}
+	packet, rest, err := shared.DeSerialize(data[consumed:])
+	if err != nil || rest != 6 || packet.Code() != 99 {
+		t.Errorf("Expected the unknown packet to be skipped, got %+v, %d, %v", packet, rest, err)
+	}
+}
@@ -22,7 +22,8 @@ code 0: login succes
 code 1: invalid credential
 */
 type ResponseMsg struct {
-	Code bool
+	Code         bool
+	SessionToken string
 }
 
 /*
@@ -55,6 +56,8 @@ type GoToShopMsg struct {
 	Armor         int
 	Gold          int
 	Inventory     []int
+	HealthRegen   int
+	ManaRegen     int
 }
 
 // UpdatePlayerStatsMsg is sent when the player buys an item
@@ -65,6 +68,8 @@ type UpdatePlayerStatsMsg struct {
 	Armor         int
 	Gold          int
 	Inventory     []int
+	HealthRegen   int
+	ManaRegen     int
 }
 
 // BackToGame is sent when the player press 'p' while in Shop
@@ -75,20 +80,38 @@ type EndGameMsg struct {
 	Win bool
 }
 
-// BoardMsg is used to transfer the board to game model
-type BoardMsg struct {
-	Points [2]int
-	Health [2]int
-	Mana   [2]int
-	Level  int
-	Xp     [2]int
-	Board  [21][51]int
+// ReconnectTokenMsg carries the token needed to come back after a disconnect
+type ReconnectTokenMsg struct {
+	PlayerID int
+	Token    uint64
+}
+
+// PhaseMsg is sent when the match phase, its countdown or the resume votes change
+type PhaseMsg struct {
+	Phase       int
+	SecondsLeft int
+	ResumeVotes int
+	Players     int
 }
 
-type DeltaMsg struct {
-	Points [2]int
-	Deltas [][3]int
-	TickID uint32
+// KillMsg is sent when a champion dies, Killer is 0 when no champion gets the kill
+type KillMsg struct {
+	Killer  int
+	Victim  int
+	Bounty  int
+	Assists []int
+}
+
+// BoardMsg is used to transfer the board to game model, Tick is 0 for a version 1 board
+type BoardMsg struct {
+	Tick      uint32
+	Points    [2]int
+	Health    [2]int
+	Mana      [2]int
+	Level     int
+	Xp        [2]int
+	RespawnIn int
+	Board     [][]int
 }
 
 // ConnectionMsg to pass the connection to meta model
@@ -67,42 +67,56 @@ func SendRoomCreatePacket(conn *net.TCPConn, roomType int) error {
 	return err
 }
 
+// The game server keeps the version of the first packet for the whole connection,
+// everything sent to it is framed so it answers with delta frames.
+func sendToGame(conn *net.TCPConn, packet shared.Packet) error {
+	_, err := conn.Write(shared.Frame(packet))
+	return err
+}
+
 func SendAction(conn *net.TCPConn, action int) error {
 	log.Println("Sent action")
-	actionPacket := shared.NewActionPacket(action)
-	data := actionPacket.Serialize()
-	_, err := conn.Write(data)
-	return err
+	return sendToGame(conn, shared.NewActionPacket(action))
 }
 
 func SendShopRequest(conn *net.TCPConn) error {
 	log.Println("Sent shop request")
-	shopReqPacket := shared.NewShopRequestPacket()
-	data := shopReqPacket.Serialize()
-	_, err := conn.Write(data)
-	return err
+	return sendToGame(conn, shared.NewShopRequestPacket())
 }
 
 func SendPurchaseItemPacket(conn *net.TCPConn, itemID int) error {
 	log.Printf("Sending purchase item request for item ID: %d", itemID)
-	purchasePacket := shared.NewPurchaseItemPacket(itemID)
-	data := purchasePacket.Serialize()
-	log.Println(data)
-	_, err := conn.Write(data)
-	return err
+	return sendToGame(conn, shared.NewPurchaseItemPacket(itemID))
 }
 
-func SendSpellSelectionPacket(conn *net.TCPConn, spell1, spell2 int) error {
-	log.Printf("Sending spell selection: %d, %d", spell1, spell2)
-	spellPacket := shared.NewSpellSelectionPacket(spell1, spell2)
-	data := spellPacket.Serialize()
-	_, err := conn.Write(data)
-	return err
+func SendJoinPacket(conn *net.TCPConn, spell1, spell2 int, sessionToken string) error {
+	log.Printf("Sending join with spells: %d, %d", spell1, spell2)
+	return sendToGame(conn, shared.NewJoinPacket(spell1, spell2, sessionToken))
+}
+
+func SendSpectatePacket(conn *net.TCPConn, vision int) error {
+	log.Printf("Sending spectate with vision: %d", vision)
+	return sendToGame(conn, shared.NewSpectatePacket(vision))
+}
+
+func SendCameraFollow(conn *net.TCPConn, playerID int) error {
+	log.Printf("Following player %d", playerID)
+	return sendToGame(conn, shared.NewCameraFollowPacket(playerID))
+}
+
+func SendViewportRequest(conn *net.TCPConn, rows, cols int) error {
+	log.Printf("Requesting a %dx%d view", rows, cols)
+	return sendToGame(conn, shared.NewViewportRequestPacket(rows, cols))
+}
+
+func SendFrameAck(conn *net.TCPConn, tick uint32) error {
+	return sendToGame(conn, shared.NewFrameAckPacket(tick))
 }
 
 func ListenForPackets(conn *net.TCPConn, msgs chan<- tea.Msg) {
 	var data []byte
 	buf := make([]byte, 4096)
+	frames := NewFrameDecoder()
 
 	for {
 		n, err := conn.Read(buf)
@@ -120,6 +134,11 @@ func ListenForPackets(conn *net.TCPConn, msgs chan<- tea.Msg) {
 				if err.Error() == "incomplete packet" {
 					// Not enough data, wait for more
 					break
+				} else if bytesConsumed > 0 {
+					// A malformed framed packet, its length is still known
+					log.Printf("Skipping malformed packet: %v", err)
+					data = data[bytesConsumed:]
+					continue
 				} else {
 					log.Printf("Error deserializing packet: %v", err)
 					// Discard the buffer to prevent getting stuck on a bad packet
@@ -134,7 +153,7 @@ func ListenForPackets(conn *net.TCPConn, msgs chan<- tea.Msg) {
 			switch msg := packet.(type) {
 			case *shared.RespPacket:
 				log.Printf("Sending RespMsg: %+v", msg)
-				msgs <- ResponseMsg{Code: msg.Success}
+				msgs <- ResponseMsg{Code: msg.Success, SessionToken: msg.SessionToken}
 			case *shared.LookRoomPacket:
 				log.Printf("Sending LookRoomMsg: %+v", msg)
 				msgs <- LookRoomMsg{Code: msg.Success, RoomID: msg.RoomID, RoomIP: msg.RoomIP}
@@ -147,24 +166,52 @@ func ListenForPackets(conn *net.TCPConn, msgs chan<- tea.Msg) {
 				msgs <- GameCloseMsg{Code: msg.Success}
 			case *shared.ShopResponsePacket:
 				log.Println("Sending GoToShopMsg")
-				msgs <- GoToShopMsg{Health: msg.Health, Mana: msg.Mana, Attack_damage: msg.Attack_damage, Armor: msg.Armor, Gold: msg.Gold, Inventory: msg.Inventory}
+				msgs <- GoToShopMsg{Health: msg.Health, Mana: msg.Mana, Attack_damage: msg.Attack_damage, Armor: msg.Armor, Gold: msg.Gold, Inventory: msg.Inventory, HealthRegen: msg.HealthRegen, ManaRegen: msg.ManaRegen}
 			case *shared.BoardPacket:
-				board, err := DecodeRLE(msg.EncodedBoard)
+				decoded, err := DecodeRLE(msg.EncodedBoard)
 				if err != nil {
 					log.Print(err.Error())
 				}
+				board := make([][]int, len(decoded))
+				for i := range decoded {
+					board[i] = decoded[i][:]
+				}
 				health := [2]int{msg.Health, msg.MaxHealth}
 				mana := [2]int{msg.Mana, msg.MaxMana}
 				xp := [2]int{msg.Xp, msg.XpNeeded}
 				log.Printf("Sending BoardMsg: Health=%v, Level=%d, Xp=%v", health, msg.Level, xp)
 				msgs <- BoardMsg{Points: msg.Points, Health: health, Mana: mana, Level: msg.Level, Xp: xp, Board: board}
-			case *shared.DeltaPacket:
-				deltas := DecodeDeltas(msg.Deltas)
-				log.Printf("Sending DeltaMsg: TickID=%d, Deltas=%v", msg.TickID, deltas)
-				msgs <- DeltaMsg{Points: msg.Points, Deltas: deltas, TickID: msg.TickID}
+			case *shared.FramePacket:
+				board, err := frames.Apply(msg)
+				if err != nil {
+					// Not acknowledged, the next delta is against an older frame or a keyframe
+					log.Printf("Failed to apply frame %d: %v", msg.Tick, err)
+					continue
+				}
+				if err := SendFrameAck(conn, msg.Tick); err != nil {
+					log.Printf("Failed to acknowledge frame %d: %v", msg.Tick, err)
+				}
+				health := [2]int{msg.Health, msg.MaxHealth}
+				mana := [2]int{msg.Mana, msg.MaxMana}
+				xp := [2]int{msg.Xp, msg.XpNeeded}
+				msgs <- BoardMsg{Tick: msg.Tick, Health: health, Mana: mana, Level: msg.Level, Xp: xp, RespawnIn: msg.RespawnIn, Board: board}
+			case *shared.ViewportPacket:
+				log.Printf("Server view is now %dx%d", msg.Rows, msg.Cols)
+				frames.SetViewport(msg.Rows, msg.Cols)
+			case *shared.UnknownPacket:
+				log.Printf("Skipping unknown packet code %d", msg.Code())
 			case *shared.EndGamePacket:
 				log.Printf("Sending EndGameMsg: Win=%t", msg.Win)
 				msgs <- EndGameMsg{Win: msg.Win}
+			case *shared.ReconnectTokenPacket:
+				log.Printf("Sending ReconnectTokenMsg for player %d", msg.PlayerID)
+				msgs <- ReconnectTokenMsg{PlayerID: msg.PlayerID, Token: msg.Token}
+			case *shared.PhasePacket:
+				log.Printf("Sending PhaseMsg: %+v", msg)
+				msgs <- PhaseMsg{Phase: msg.Phase, SecondsLeft: msg.SecondsLeft, ResumeVotes: msg.ResumeVotes, Players: msg.Players}
+			case *shared.KillPacket:
+				log.Printf("Sending KillMsg: %+v", msg)
+				msgs <- KillMsg{Killer: msg.Killer, Victim: msg.Victim, Bounty: msg.Bounty, Assists: msg.Assists}
 			default:
 				log.Printf("Unknown type: %T, raw: %x", packet, data)
 				msgs <- GamePacketMsg{Packet: data}
@@ -3,9 +3,11 @@ package main
 import (
 	"log"
 	"net"
+	"os"
 
 	"github.com/GrGLeo/ctf/client/communication"
 	"github.com/GrGLeo/ctf/client/model"
+	"github.com/GrGLeo/ctf/shared"
 	tea "github.com/charmbracelet/bubbletea"
 )
 
@@ -30,6 +32,7 @@ type MetaModel struct {
 	GameOverModel  model.GameOverModel
 	state          string
 	Username       string
+	SessionToken   string
 	Connection     *net.TCPConn
 	GameConnection *net.TCPConn
 	msgs           chan tea.Msg
@@ -56,6 +59,8 @@ func (m MetaModel) Init() tea.Cmd {
 		return m.AnimationModel.Init()
 	case Login:
 		return m.AuthModel.Init()
+	case Game:
+		return m.GameModel.Init()
 	}
 	return communication.AttemptReconnect()
 }
@@ -118,6 +123,7 @@ func (m MetaModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 				log.Println("Failed to log in")
 			} else {
 				log.Println("Manage to log in")
+				m.SessionToken = msg.SessionToken
 				m.state = Lobby
 				m.LobbyModel = model.NewLobbyModel(m.Connection)
 				m.LobbyModel.SetDimension(m.height, m.width)
@@ -136,8 +142,8 @@ func (m MetaModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 				conn, err := communication.MakeConnection(msg.RoomIP)
 				if err == nil {
 					m.GameConnection = conn
-					// Send spell selection after successful game connection
-					communication.SendSpellSelectionPacket(m.GameConnection, m.LobbyModel.SelectedSpells[0], m.LobbyModel.SelectedSpells[1])
+					// Send spells and session token after successful game connection
+					communication.SendJoinPacket(m.GameConnection, m.LobbyModel.SelectedSpells[0], m.LobbyModel.SelectedSpells[1], m.SessionToken)
 					break
 				}
 			}
@@ -146,6 +152,7 @@ func (m MetaModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 			m.state = Game
 			m.GameModel = model.NewGameModel(m.GameConnection)
 			m.GameModel.SetDimension(m.height, m.width)
+			m.GameModel.RequestViewport()
 			return m, m.GameModel.Init()
 		default:
 			return m, cmd
@@ -155,6 +162,10 @@ func (m MetaModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		newmodel, cmd = m.GameModel.Update(msg)
 		m.GameModel = newmodel.(model.GameModel)
 		switch msg := msg.(type) {
+		case tea.WindowSizeMsg:
+			m.width = msg.Width
+			m.height = msg.Height
+			return m, cmd
 		case communication.GoToShopMsg:
 			m.state = Shop
 			m.ShopModel = model.NewShopModel(
@@ -165,6 +176,8 @@ func (m MetaModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
         msg.Armor,
         msg.Gold,
         msg.Inventory,
+        msg.HealthRegen,
+        msg.ManaRegen,
         m.GameConnection,
       )
 			m.ShopModel.SetDimension(m.height, m.width)
@@ -236,6 +249,30 @@ func (m MetaModel) View() string {
 	return ""
 }
 
+// spectate connects straight to a game server from SPECTATE_PORT, watching with
+// SPECTATE_VISION: "blue" (default), "red" or "full"
+func (m *MetaModel) spectate(port string) error {
+	conn, err := communication.MakeConnection(port)
+	if err != nil {
+		return err
+	}
+	vision := shared.VisionBlue
+	switch os.Getenv("SPECTATE_VISION") {
+	case "red":
+		vision = shared.VisionRed
+	case "full":
+		vision = shared.VisionFull
+	}
+	if err := communication.SendSpectatePacket(conn, vision); err != nil {
+		return err
+	}
+	m.GameConnection = conn
+	m.GameModel = model.NewSpectatorModel(conn)
+	m.state = Game
+	go communication.ListenForPackets(conn, m.msgs)
+	return nil
+}
+
 func main() {
 	model := NewMetaModel()
 
@@ -244,6 +281,11 @@ func main() {
 		log.Fatal(err)
 	}
 	defer f.Close()
+	if port := os.Getenv("SPECTATE_PORT"); port != "" {
+		if err := model.spectate(port); err != nil {
+			log.Fatal(err)
+		}
+	}
 	p := tea.NewProgram(model, tea.WithAltScreen())
 	// Serve as a bridge to pass message from ListenForPackets to models
 	go func() {
@@ -15,8 +15,11 @@ import (
 	"github.com/charmbracelet/lipgloss"
 )
 
+// Lines of the game view that are not board: hud, bars, respawn timer, kill feed, dash and border
+const hudRows = 10
+
 type GameModel struct {
-	currentBoard   [21][51]int
+	currentBoard   [][]int
 	conn           *net.TCPConn
 	gameClock      time.Duration
 	height, width  int
@@ -27,6 +30,8 @@ type GameModel struct {
 	health         [2]int
 	mana           [2]int
 	level          int
+	respawnIn      int
+	killFeed       []string
 	xp             [2]int
 	points         [2]int
 	attackMode     bool
@@ -34,6 +39,7 @@ type GameModel struct {
 	dashcooldown   time.Duration
 	dashStart      time.Time
 	percent        float64
+	spectator      bool
 }
 
 func NewGameModel(conn *net.TCPConn) GameModel {
@@ -54,6 +60,13 @@ func NewGameModel(conn *net.TCPConn) GameModel {
 	}
 }
 
+// NewSpectatorModel watches a match, digits follow a champion instead of sending actions
+func NewSpectatorModel(conn *net.TCPConn) GameModel {
+	m := NewGameModel(conn)
+	m.spectator = true
+	return m
+}
+
 func (m GameModel) Init() tea.Cmd {
 	return nil
 }
@@ -68,23 +81,43 @@ func (m *GameModel) SetConnection(conn *net.TCPConn) {
 	m.conn = conn
 }
 
+// RequestViewport asks the game server for the biggest view that fits the terminal,
+// the server clamps it to the sizes it supports
+func (m GameModel) RequestViewport() {
+	if m.conn == nil || m.height <= hudRows || m.width <= 2 {
+		return
+	}
+	communication.SendViewportRequest(m.conn, m.height-hudRows, m.width-2)
+}
+
 func (m GameModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 	switch msg := msg.(type) {
+	case tea.WindowSizeMsg:
+		m.SetDimension(msg.Height, msg.Width)
+		m.RequestViewport()
+		return m, nil
 	case communication.BoardMsg:
+		if msg.Tick > 0 {
+			m.gameClock = time.Duration(50*int(msg.Tick)) * time.Millisecond
+		}
 		points := msg.Points
 		m.points = points
 		m.health = msg.Health
 		m.mana = msg.Mana
 		m.level = msg.Level
 		m.xp = msg.Xp
+		m.respawnIn = msg.RespawnIn
 		m.currentBoard = msg.Board
-	case communication.DeltaMsg:
-		m.gameClock = time.Duration(50*int(msg.TickID)) * time.Millisecond
-		points := msg.Points
-		m.points = points
-		ApplyDeltas(msg.Deltas, &m.currentBoard)
+	case communication.KillMsg:
+		m.killFeed = append(m.killFeed, killLine(msg))
+		if len(m.killFeed) > 3 {
+			m.killFeed = m.killFeed[1:]
+		}
 		return m, nil
 	case tea.KeyMsg:
+		if m.spectator {
+			return m.spectatorKey(msg)
+		}
 		switch msg.String() {
 		case "w":
 			communication.SendAction(m.conn, 1)
@@ -278,6 +311,13 @@ func (m GameModel) View() string {
 	builder.WriteString(xpHUD)
 	builder.WriteString("\n")
 
+	if m.respawnIn > 0 {
+		builder.WriteString(fmt.Sprintf("Respawning in %ds\n", m.respawnIn))
+	}
+	for _, line := range m.killFeed {
+		builder.WriteString(line + "\n")
+	}
+
 	var progressBar string
 	if m.percent != 0.0 {
 		progressBar = m.progress.ViewAs(m.percent)
@@ -294,17 +334,30 @@ func (m GameModel) View() string {
 	)
 }
 
+func (m GameModel) spectatorKey(msg tea.KeyMsg) (tea.Model, tea.Cmd) {
+	switch key := msg.String(); key {
+	case "1", "2", "3", "4", "5", "6", "7", "8", "9":
+		playerID, _ := strconv.Atoi(key)
+		communication.SendCameraFollow(m.conn, playerID)
+	case "ctrl+c":
+		return m, tea.Quit
+	}
+	return m, nil
+}
+
+func killLine(msg communication.KillMsg) string {
+	if msg.Killer == 0 {
+		return fmt.Sprintf("Player %d was executed", msg.Victim)
+	}
+	line := fmt.Sprintf("Player %d killed Player %d (+%d gold)", msg.Killer, msg.Victim, msg.Bounty)
+	if len(msg.Assists) > 0 {
+		line += fmt.Sprintf(", assists %v", msg.Assists)
+	}
+	return line
+}
+
 func doTick() tea.Cmd {
 	return tea.Tick(50*time.Millisecond, func(time.Time) tea.Msg {
 		return communication.CooldownTickMsg{}
 	})
 }
-
-func ApplyDeltas(deltas [][3]int, currentBoard *[21][51]int) {
-	for _, delta := range deltas {
-		x := delta[0]
-		y := delta[1]
-		value := delta[2]
-		currentBoard[y][x] = value
-	}
-}
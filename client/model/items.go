@@ -11,6 +11,7 @@ type Item struct {
 	Damage      int
 	Armor       int
 	Health      int
+	ManaRegen   int
 }
 
 func (i Item) String() string {
@@ -34,6 +35,11 @@ func (i Item) String() string {
 		s += fmt.Sprintf("\nHealth: %d", i.Health)
 	} else {
     s += fmt.Sprintf("\n")
+  }
+	if i.ManaRegen > 0 {
+		s += fmt.Sprintf("\nMana Regen: %d/s", i.ManaRegen)
+	} else {
+    s += fmt.Sprintf("\n")
   }
 	return s
 }
@@ -61,4 +67,11 @@ var availableItems = []Item{
 		Armor:       5,
 		Health:      50,
 	},
+	{
+		ID:          4,
+		Name:        "Crystal of Focus",
+		Description: "Restores mana faster.             ",
+		Cost:        250,
+		ManaRegen:   4,
+	},
 }
@@ -27,6 +27,8 @@ type ShopModel struct {
 	health, mana         int
 	attack_damage, armor int
 	gold                 int
+	healthRegen          int
+	manaRegen            int
 	conn                 *net.TCPConn
 	inventory            []int
 }
@@ -36,7 +38,7 @@ func (m *ShopModel) SetDimension(height, width int) {
 	m.width = width
 }
 
-func NewShopModel(styles *Styles, health, mana, attack_damage, armor, gold int, inventory []int, conn *net.TCPConn) ShopModel {
+func NewShopModel(styles *Styles, health, mana, attack_damage, armor, gold int, inventory []int, healthRegen, manaRegen int, conn *net.TCPConn) ShopModel {
 	return ShopModel{
 		styles:        styles,
 		Items:         availableItems,
@@ -46,6 +48,8 @@ func NewShopModel(styles *Styles, health, mana, attack_damage, armor, gold int,
 		attack_damage: attack_damage,
 		armor:         armor,
 		gold:          gold,
+		healthRegen:   healthRegen,
+		manaRegen:     manaRegen,
 		conn:          conn,
 		inventory:     inventory,
 	}
@@ -68,6 +72,8 @@ func (m ShopModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
     m.armor = msg.Armor
     m.gold = msg.Gold
     m.inventory = msg.Inventory
+    m.healthRegen = msg.HealthRegen
+    m.manaRegen = msg.ManaRegen
 	case tea.KeyMsg:
 		switch {
 		case key.Matches(msg, shopUpKey):
@@ -96,6 +102,8 @@ func (m ShopModel) Update(msg tea.Msg) (tea.Model, tea.Cmd) {
 		m.armor = msg.Armor
 		m.gold = msg.Gold
 		m.inventory = msg.Inventory
+		m.healthRegen = msg.HealthRegen
+		m.manaRegen = msg.ManaRegen
 	}
 	return m, nil
 }
@@ -128,6 +136,8 @@ func (m ShopModel) View() string {
 	rightPanel.WriteString(fmt.Sprintf("  Mana: %d\n", m.mana))
 	rightPanel.WriteString(fmt.Sprintf("  Attack Damage: %d\n", m.attack_damage))
 	rightPanel.WriteString(fmt.Sprintf("  Armor: %d\n", m.armor))
+	rightPanel.WriteString(fmt.Sprintf("  Health Regen: %d/s\n", m.healthRegen))
+	rightPanel.WriteString(fmt.Sprintf("  Mana Regen: %d/s\n", m.manaRegen))
 	rightPanel.WriteString(fmt.Sprintf("  Gold: %d\n", m.gold))
 	rightPanel.WriteString("  Inventory:\n")
 	for i := range 6 {
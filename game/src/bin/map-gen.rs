@@ -0,0 +1,67 @@
+use std::fs;
+
+use clap::Parser;
+use game::game::map_generator::{GeneratorParams, generate};
+
+// Cli Parser
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Generate a mirror-symmetric 3-lane map", long_about = None)]
+struct CliArgs {
+    #[arg(long = "seed", value_name = "SEED", default_value_t = 0)]
+    seed: u64,
+
+    #[arg(long = "size", value_name = "CELLS", default_value_t = 200)]
+    size: u16,
+
+    #[arg(long = "lane-width", value_name = "CELLS", default_value_t = 5)]
+    lane_width: u16,
+
+    /// Chance for a jungle floor cell to become a bush, between 0 and 1
+    #[arg(long = "bush-density", value_name = "RATIO", default_value_t = 0.15)]
+    bush_density: f64,
+
+    /// How much the jungle is broken up by walls, between 0 and 1
+    #[arg(long = "wall-complexity", value_name = "RATIO", default_value_t = 0.4)]
+    wall_complexity: f64,
+
+    /// Camp as `blue_monster:red_monster`, using monster ids from stats.toml
+    #[arg(long = "camp", value_name = "BLUE:RED", default_values_t = ["wolf_blue:wolf_red".to_string(), "golem_blue:golem_red".to_string()])]
+    camps: Vec<String>,
+
+    #[arg(long = "output", value_name = "PATH")]
+    output: String,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = CliArgs::parse();
+
+    let mut camps = Vec::new();
+    for camp in &args.camps {
+        let Some((blue, red)) = camp.split_once(':') else {
+            return Err(format!("Invalid camp {:?}, expected BLUE:RED", camp).into());
+        };
+        camps.push((blue.to_string(), red.to_string()));
+    }
+
+    let params = GeneratorParams {
+        seed: args.seed,
+        size: args.size,
+        lane_width: args.lane_width,
+        bush_density: args.bush_density,
+        wall_complexity: args.wall_complexity,
+        camps,
+    };
+    let layout = generate(&params)?;
+    fs::write(&args.output, serde_json::to_string(&layout)?)?;
+
+    println!(
+        "Map {}x{} written to {} ({} structures, {} lanes, {} camps)",
+        layout.rows,
+        layout.cols,
+        args.output,
+        layout.map_layout.structures.len(),
+        layout.map_layout.lanes.len(),
+        layout.map_layout.camps.len()
+    );
+    Ok(())
+}
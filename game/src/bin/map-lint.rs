@@ -0,0 +1,46 @@
+use std::process;
+
+use clap::Parser;
+use game::game::board::Board;
+use game::game::map_lint::{lint, render_preview};
+
+// Cli Parser
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Check a map file and preview it in the terminal", long_about = None)]
+struct CliArgs {
+    #[arg(value_name = "MAP")]
+    map_file: String,
+
+    /// Only print the issues
+    #[arg(long = "no-preview")]
+    no_preview: bool,
+
+    /// Draw the preview with characters instead of terminal colors
+    #[arg(long = "plain")]
+    plain: bool,
+}
+
+fn main() {
+    let args = CliArgs::parse();
+
+    let mut board = match Board::from_json(&args.map_file) {
+        Ok(board) => board,
+        Err(e) => {
+            eprintln!("Failed to load map {}: {}", args.map_file, e);
+            process::exit(2);
+        }
+    };
+    let issues = lint(&mut board);
+
+    if !args.no_preview {
+        print!("{}", render_preview(&board, !args.plain));
+    }
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    if !issues.is_empty() {
+        eprintln!("{} issue(s) found in {}", issues.len(), args.map_file);
+        process::exit(1);
+    }
+    println!("{} is clean", args.map_file);
+}
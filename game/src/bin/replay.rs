@@ -0,0 +1,218 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process;
+
+use bytes::BytesMut;
+use clap::Parser;
+use game::errors::ProtocolError;
+use game::game::replay::{Playback, Replay};
+use game::game::spectator::{Camera, SpectatorVision};
+use game::game::{GameManager, PlayerId};
+use game::packet::codec::{
+    ClientPacket, FRAMED_VERSION, LEGACY_VERSION, PacketCodec, PacketReader, ServerPacket,
+};
+use game::packet::start_packet::StartPacket;
+use game::packet::viewport_packet::ViewportPacket;
+use tokio::io::{AsyncWriteExt, WriteHalf, split};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{Duration, interval};
+use tokio_util::codec::Encoder;
+
+// Cli Parser
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Play back a match recorded with --record", long_about = None)]
+struct CliArgs {
+    #[arg(value_name = "REPLAY")]
+    replay_file: String,
+
+    /// Wait for one spectator client on this port and stream the match to it
+    #[arg(long = "port", value_name = "PORT", conflicts_with = "dump_path")]
+    port: Option<u16>,
+
+    /// Playback speed when streaming, 2 plays twice as fast
+    #[arg(
+        long = "speed",
+        value_name = "FACTOR",
+        default_value_t = 1.0,
+        requires = "port"
+    )]
+    speed: f64,
+
+    /// Write the packets a full-vision spectator receives to this file, as a legacy client reads them
+    #[arg(long = "dump", value_name = "PATH")]
+    dump_path: Option<String>,
+
+    /// Center the dumped frames on this player's champion instead of the board center
+    #[arg(long = "follow", value_name = "PLAYER_ID", requires = "dump_path")]
+    follow: Option<PlayerId>,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = CliArgs::parse();
+
+    let replay = match Replay::load(&args.replay_file) {
+        Ok(replay) => replay,
+        Err(e) => {
+            eprintln!("Failed to load replay {}: {}", args.replay_file, e);
+            process::exit(1);
+        }
+    };
+    let mut manager = match replay.start_match() {
+        Ok(manager) => manager,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    println!(
+        "Replaying {} ticks from {}",
+        replay.last_tick(),
+        args.replay_file
+    );
+    let mut playback = Playback::new(replay);
+
+    let result = match (&args.dump_path, args.port) {
+        (Some(path), _) => dump(&mut playback, &mut manager, path, args.follow),
+        (None, Some(port)) => serve(&mut playback, &mut manager, port, args.speed).await,
+        (None, None) => {
+            while playback.step(&mut manager).is_some() {}
+            Ok(())
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("Replay stopped at tick {}: {}", playback.tick(), e);
+        process::exit(1);
+    }
+
+    println!("Played {} ticks", playback.tick());
+    match manager.match_result() {
+        Some(result) => match serde_json::to_string(result) {
+            Ok(json) => println!("Match result: {}", json),
+            Err(e) => eprintln!("Failed to serialize match result: {}", e),
+        },
+        None => println!("The recording ends before the match does"),
+    }
+}
+
+/// Runs the whole replay, writing every packet of a full-vision spectator to `path`.
+fn dump(
+    playback: &mut Playback,
+    manager: &mut GameManager,
+    path: &str,
+    follow: Option<PlayerId>,
+) -> Result<(), ProtocolError> {
+    manager.allow_spectators(1, true);
+    let spectator_id = manager
+        .add_spectator(SpectatorVision::Full)
+        .expect("a spectator slot was just opened");
+    if let Some(player_id) = follow {
+        manager.set_camera(spectator_id, Camera::Follow(player_id));
+    }
+
+    let mut output = BufWriter::new(File::create(path)?);
+    let mut codec = PacketCodec::with_version(LEGACY_VERSION);
+    let mut buffer = BytesMut::new();
+    codec.encode(ServerPacket::Start(StartPacket::new(0)), &mut buffer)?;
+    while let Some(mut updates) = playback.step(manager) {
+        for packet in updates.remove(&spectator_id).unwrap_or_default() {
+            codec.encode(packet, &mut buffer)?;
+        }
+        output.write_all(&buffer)?;
+        buffer.clear();
+    }
+    output.write_all(&buffer)?;
+    output.flush()?;
+    println!("Frames written to {}", path);
+    Ok(())
+}
+
+/// Streams the replay at the recorded tick rate to the first spectator that connects.
+async fn serve(
+    playback: &mut Playback,
+    manager: &mut GameManager,
+    port: u16,
+    speed: f64,
+) -> Result<(), ProtocolError> {
+    let listener = TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
+    println!("Waiting for a spectator on port {}", port);
+    let (stream, addr) = listener.accept().await?;
+    let (reader, mut writer) = split(stream);
+    let mut packets = PacketReader::new(reader);
+
+    let vision = match packets.next().await? {
+        Some(ClientPacket::Spectate(packet)) => packet.vision,
+        packet => {
+            eprintln!(
+                "Rejecting {:?}, expected a spectate packet: {:?}",
+                addr, packet
+            );
+            writer
+                .write_all(b"This server only plays back a replay.\n")
+                .await?;
+            writer.shutdown().await?;
+            return Ok(());
+        }
+    };
+    manager.allow_spectators(1, true);
+    let spectator_id = manager
+        .add_spectator(vision)
+        .expect("a spectator slot was just opened");
+    let version = packets.version().unwrap_or(LEGACY_VERSION);
+    manager.track_frames(spectator_id, version >= FRAMED_VERSION);
+    println!("Spectator {:?} is watching", addr);
+
+    let mut codec = PacketCodec::with_version(version);
+    let start = ServerPacket::Start(StartPacket::new(0));
+    send(&mut writer, &mut codec, vec![start]).await?;
+
+    let speed = if speed > 0.0 { speed } else { 1.0 };
+    let mut ticks = interval(Duration::from_secs_f64(
+        manager.tick_duration().as_secs_f64() / speed,
+    ));
+    loop {
+        tokio::select! {
+            packet = packets.next() => match packet {
+                Ok(Some(ClientPacket::Camera(request))) => {
+                    manager.set_camera(spectator_id, request.camera);
+                }
+                Ok(Some(ClientPacket::Viewport(request))) => {
+                    let viewport = manager.set_viewport(spectator_id, request.rows, request.cols);
+                    let reply = ServerPacket::Viewport(ViewportPacket::new(viewport));
+                    send(&mut writer, &mut codec, vec![reply]).await?;
+                }
+                Ok(Some(ClientPacket::FrameAck(tick))) => manager.ack_frame(spectator_id, tick),
+                Ok(Some(packet)) => eprintln!("Ignoring packet from spectator: {:?}", packet),
+                Ok(None) => {
+                    println!("Spectator {:?} left", addr);
+                    return Ok(());
+                }
+                Err(e @ ProtocolError::Malformed { .. }) => {
+                    eprintln!("Ignoring packet from {:?}: {}", addr, e);
+                }
+                Err(e) => return Err(e),
+            },
+            _ = ticks.tick() => {
+                let Some(mut updates) = playback.step(manager) else {
+                    writer.shutdown().await?;
+                    return Ok(());
+                };
+                let packets = updates.remove(&spectator_id).unwrap_or_default();
+                send(&mut writer, &mut codec, packets).await?;
+            }
+        }
+    }
+}
+
+async fn send(
+    writer: &mut WriteHalf<TcpStream>,
+    codec: &mut PacketCodec,
+    packets: Vec<ServerPacket>,
+) -> Result<(), ProtocolError> {
+    let mut buffer = BytesMut::new();
+    for packet in packets {
+        codec.encode(packet, &mut buffer)?;
+    }
+    writer.write_all(&buffer).await?;
+    Ok(())
+}
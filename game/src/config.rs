@@ -1,9 +1,16 @@
 use std::collections::HashMap;
 use std::fs;
+use std::time::Duration;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use crate::errors::ConfigIssue;
+use crate::game::cell::{PlayerId, Team};
 use crate::game::entities::item::Item;
+use crate::game::map_layout::StructureKind;
+use crate::game::spell;
+use crate::game::{BaseTerrain, Board};
+use strum::IntoEnumIterator;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ItemFile {
@@ -14,6 +21,18 @@ pub struct ItemFile {
 pub struct BaseStats {
     pub health: u16,
     pub armor: u16,
+    // Reach of the fountain around the base footprint, in cells
+    pub fountain_radius: u16,
+    pub fountain_pulse_ms: u64,
+    // Share of max health and mana given back to allies on each pulse
+    pub fountain_heal_percent: u16,
+    pub fountain_damage: u16,
+}
+
+impl BaseStats {
+    pub fn fountain_pulse(&self) -> Duration {
+        Duration::from_millis(self.fountain_pulse_ms)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -27,6 +46,11 @@ pub struct ChampionStats {
     pub level_up_health_increase: u16,
     pub level_up_attack_damage_increase: u16,
     pub level_up_armor_increase: u16,
+    // Health and mana given back every second
+    pub health_regen: u16,
+    pub mana_regen: u16,
+    pub level_up_health_regen_increase: u16,
+    pub level_up_mana_regen_increase: u16,
     pub attack_range_row: u16,
     pub attack_range_col: u16,
 }
@@ -93,6 +117,130 @@ pub struct SpellFile {
     spell: Vec<SpellStats>,
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TimingRules {
+    pub game_tick_ms: u64,
+    pub minion_wave_interval_secs: u64,
+    pub countdown_secs: u64,
+    pub pause_timeout_secs: u64,
+    pub reconnect_grace_secs: u64,
+    // Longest run of delta frames before a full board is sent again
+    pub keyframe_interval_ticks: u32,
+}
+
+impl TimingRules {
+    pub fn game_tick(&self) -> Duration {
+        Duration::from_millis(self.game_tick_ms)
+    }
+
+    pub fn minion_wave_interval(&self) -> Duration {
+        Duration::from_secs(self.minion_wave_interval_secs)
+    }
+
+    pub fn countdown(&self) -> Duration {
+        Duration::from_secs(self.countdown_secs)
+    }
+
+    pub fn pause_timeout(&self) -> Duration {
+        Duration::from_secs(self.pause_timeout_secs)
+    }
+
+    pub fn reconnect_grace(&self) -> Duration {
+        Duration::from_secs(self.reconnect_grace_secs)
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct MinionRules {
+    pub minions_per_wave: u8,
+    // Gold for the champion landing the last hit on an enemy minion
+    pub minion_kill_gold: u16,
+    // XP split between the enemy champions around a dying minion
+    pub minion_kill_xp: u32,
+    pub xp_share_radius: u16,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct ChampionRules {
+    pub champion_respawn_base_time: u64,
+    pub champion_respawn_time_per_level: u64,
+    pub champion_kill_gold: u16,
+    pub champion_kill_xp: u32,
+    // Extra gold on a champion for each kill since its last death, up to the max streak
+    pub bounty_per_streak: u16,
+    pub max_bounty_streak: u16,
+    pub assist_gold: u16,
+    pub assist_xp: u32,
+    // How long damage dealt to a champion counts toward its kill
+    pub assist_window_secs: u64,
+}
+
+impl ChampionRules {
+    /// How long a champion of this level stays dead, the base time applies at level 1.
+    pub fn respawn_time(&self, level: u8) -> Duration {
+        let extra_levels = level.saturating_sub(1) as u64;
+        Duration::from_secs(
+            self.champion_respawn_base_time + self.champion_respawn_time_per_level * extra_levels,
+        )
+    }
+
+    /// Gold paid for killing a champion on a `streak` of kills.
+    pub fn bounty(&self, streak: u16) -> u16 {
+        let streak = streak.min(self.max_bounty_streak);
+        self.champion_kill_gold
+            .saturating_add(self.bounty_per_streak.saturating_mul(streak))
+    }
+
+    pub fn assist_window(&self) -> Duration {
+        Duration::from_secs(self.assist_window_secs)
+    }
+}
+
+// Sight radii in cells, walls block the line of sight
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct VisionRules {
+    pub champion_sight_radius: u16,
+    pub minion_sight_radius: u16,
+    pub tower_sight_radius: u16,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RulesConfig {
+    pub timing_rules: TimingRules,
+    pub minion_rules: MinionRules,
+    pub champion_rules: ChampionRules,
+    pub vision_rules: VisionRules,
+}
+
+/// Paths the configuration was loaded from, used when reporting problems.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigSources {
+    pub stats: String,
+    pub spells: String,
+    pub items: String,
+    pub rules: String,
+}
+
+/// Contents of the configuration files, kept so a replay can rebuild the same configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigFiles {
+    pub stats: String,
+    pub spells: String,
+    pub items: String,
+    pub rules: String,
+}
+
+impl ConfigFiles {
+    pub fn read(sources: &ConfigSources) -> std::io::Result<Self> {
+        Ok(ConfigFiles {
+            stats: fs::read_to_string(&sources.stats)?,
+            spells: fs::read_to_string(&sources.spells)?,
+            items: fs::read_to_string(&sources.items)?,
+            rules: fs::read_to_string(&sources.rules)?,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct GameConfig {
     pub base: BaseStats,
@@ -104,6 +252,12 @@ pub struct GameConfig {
     pub spells: HashMap<u8, SpellStats>,
     #[serde(skip)]
     pub items: HashMap<u32, Item>,
+    #[serde(skip)]
+    pub rules: RulesConfig,
+    #[serde(skip)]
+    pub sources: ConfigSources,
+    #[serde(skip)]
+    pub files: ConfigFiles,
 }
 
 impl GameConfig {
@@ -111,28 +265,525 @@ impl GameConfig {
         config_path: &str,
         spell_path: &str,
         items_path: &str,
+        rules_path: &str,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(config_path)?;
-        let mut config: GameConfig = toml::from_str(&content)?;
+        let sources = ConfigSources {
+            stats: config_path.to_string(),
+            spells: spell_path.to_string(),
+            items: items_path.to_string(),
+            rules: rules_path.to_string(),
+        };
+        let files = ConfigFiles::read(&sources)?;
+        Self::from_files(files, sources)
+    }
 
-        let spell_content = fs::read_to_string(spell_path)?;
-        let spells_file: SpellFile = toml::from_str(&spell_content)?;
+    /// Parses configuration files already read, `sources` names them in reports.
+    pub fn from_files(
+        files: ConfigFiles,
+        sources: ConfigSources,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config: GameConfig = toml::from_str(&files.stats)?;
 
+        let spells_file: SpellFile = toml::from_str(&files.spells)?;
         config.spells = spells_file
             .spell
             .into_iter()
             .map(|spell_conf| (spell_conf.id, spell_conf))
             .collect();
 
-        let items_content = fs::read_to_string(items_path)?;
-        let items_file: ItemFile = toml::from_str(&items_content)?;
-
+        let items_file: ItemFile = toml::from_str(&files.items)?;
         config.items = items_file
             .items
             .into_iter()
             .map(|item| (item.id, item))
             .collect();
 
+        config.rules = toml::from_str(&files.rules)?;
+        config.sources = sources;
+        config.files = files;
+
         Ok(config)
     }
+
+    /// Checks the loaded data against itself and the board, collecting every problem found.
+    pub fn validate(&self, board: &Board) -> Result<(), Vec<ConfigIssue>> {
+        let mut issues = Vec::new();
+        let mut report = |file: &str, section: String, field: &str, message: String| {
+            issues.push(ConfigIssue {
+                file: file.to_string(),
+                section,
+                field: field.to_string(),
+                message,
+            });
+        };
+
+        // stats.toml
+        let stats = &self.sources.stats;
+        if self.champion.xp_per_level.is_empty() {
+            report(
+                stats,
+                "champion".to_string(),
+                "xp_per_level",
+                "must define at least one level".to_string(),
+            );
+        }
+        if self.base.fountain_pulse_ms == 0 {
+            report(
+                stats,
+                "base".to_string(),
+                "fountain_pulse_ms",
+                "must be greater than 0".to_string(),
+            );
+        }
+        let map = &board.map_layout.source;
+        for (i, monster) in self.neutral_monsters.iter().enumerate() {
+            let section = format!("neutral_monsters.{} ({})", i, monster.id);
+            // A camp on the map overrides the spawn written in stats.toml
+            if board.map_layout.camp_of(&monster.id).is_none() {
+                for (field, message) in position_problems(
+                    board,
+                    &[(monster.spawn_row, monster.spawn_col)],
+                    ("spawn_row", "spawn_col"),
+                ) {
+                    report(stats, section.clone(), &field, message);
+                }
+            }
+            if self.neutral_monsters[..i]
+                .iter()
+                .any(|other| other.id == monster.id)
+            {
+                report(
+                    stats,
+                    section,
+                    "id",
+                    format!("duplicate monster id {:?}", monster.id),
+                );
+            }
+        }
+
+        // map file
+        for (i, camp) in board.map_layout.camps.iter().enumerate() {
+            let section = format!("camps.{} ({})", i, camp.monster);
+            if !self
+                .neutral_monsters
+                .iter()
+                .any(|monster| monster.id == camp.monster)
+            {
+                report(
+                    map,
+                    section.clone(),
+                    "monster",
+                    format!("unknown monster id {:?}", camp.monster),
+                );
+            }
+            for (field, message) in
+                position_problems(board, &[(camp.row, camp.col)], ("row", "col"))
+            {
+                report(map, section.clone(), &field, message);
+            }
+        }
+        for (i, structure) in board.map_layout.structures.iter().enumerate() {
+            let section = format!("structures.{}", i);
+            for (field, message) in position_problems(board, &structure.footprint(), ("row", "col"))
+            {
+                report(map, section.clone(), &field, message);
+            }
+        }
+        for team in Team::iter() {
+            let bases = board
+                .map_layout
+                .structures_of(StructureKind::Base)
+                .filter(|structure| structure.team == team)
+                .count();
+            if bases != 1 {
+                report(
+                    map,
+                    "structures".to_string(),
+                    "kind",
+                    format!("{:?} team needs exactly one base, found {}", team, bases),
+                );
+            }
+            if board.map_layout.spawns_of(team).is_empty() {
+                report(
+                    map,
+                    "spawns".to_string(),
+                    "team",
+                    format!("{:?} team has no spawn point", team),
+                );
+            }
+        }
+        for (i, spawn) in board.map_layout.spawns.iter().enumerate() {
+            for (field, message) in
+                position_problems(board, &[(spawn.row, spawn.col)], ("row", "col"))
+            {
+                report(map, format!("spawns.{}", i), &field, message);
+            }
+        }
+
+        // spells.toml
+        let mut spells: Vec<_> = self.spells.iter().collect();
+        spells.sort_by_key(|(id, _)| **id);
+        for (&id, stats) in spells {
+            if spell::create_spell_from_id(id, stats.clone()).is_none() {
+                report(
+                    &self.sources.spells,
+                    format!("spell.{}", id),
+                    "id",
+                    format!("unknown spell id {}", id),
+                );
+            }
+        }
+
+        // rules.toml
+        let rules = &self.sources.rules;
+        if self.rules.timing_rules.game_tick_ms == 0 {
+            report(
+                rules,
+                "timing_rules".to_string(),
+                "game_tick_ms",
+                "must be greater than 0".to_string(),
+            );
+        }
+        if self.rules.timing_rules.pause_timeout_secs == 0 {
+            report(
+                rules,
+                "timing_rules".to_string(),
+                "pause_timeout_secs",
+                "must be greater than 0".to_string(),
+            );
+        }
+        if self.rules.timing_rules.keyframe_interval_ticks == 0 {
+            report(
+                rules,
+                "timing_rules".to_string(),
+                "keyframe_interval_ticks",
+                "must be greater than 0".to_string(),
+            );
+        }
+        let vision = &self.rules.vision_rules;
+        for (field, radius) in [
+            ("champion_sight_radius", vision.champion_sight_radius),
+            ("minion_sight_radius", vision.minion_sight_radius),
+            ("tower_sight_radius", vision.tower_sight_radius),
+        ] {
+            if radius == 0 {
+                report(
+                    rules,
+                    "vision_rules".to_string(),
+                    field,
+                    "must be greater than 0".to_string(),
+                );
+            }
+        }
+        if self.rules.minion_rules.minions_per_wave == 0 {
+            report(
+                rules,
+                "minion_rules".to_string(),
+                "minions_per_wave",
+                "must be greater than 0".to_string(),
+            );
+        }
+        if self.rules.minion_rules.xp_share_radius == 0 {
+            report(
+                rules,
+                "minion_rules".to_string(),
+                "xp_share_radius",
+                "must be greater than 0".to_string(),
+            );
+        }
+        if self.rules.champion_rules.assist_window_secs == 0 {
+            report(
+                rules,
+                "champion_rules".to_string(),
+                "assist_window_secs",
+                "must be greater than 0".to_string(),
+            );
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+}
+
+/// Checks that each team has its own spawn point for every player joining without a roster.
+pub fn validate_spawn_count(board: &Board, max_players: u8) -> Result<(), Vec<ConfigIssue>> {
+    let issues: Vec<_> = Team::iter()
+        .filter_map(|team| {
+            let players = (1..=max_players as PlayerId)
+                .filter(|player_id| Team::of_player(*player_id) == team)
+                .count();
+            let spawns = board.map_layout.spawns_of(team).len();
+            (players > spawns).then(|| ConfigIssue {
+                file: board.map_layout.source.clone(),
+                section: "spawns".to_string(),
+                field: "team".to_string(),
+                message: format!(
+                    "{:?} team has {} spawn(s) for {} players",
+                    team, spawns, players
+                ),
+            })
+        })
+        .collect();
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(issues)
+    }
+}
+
+/// Problems with placing something on `cells`, keyed by the offending field of `(row, col)`.
+fn position_problems(
+    board: &Board,
+    cells: &[(u16, u16)],
+    (row_field, col_field): (&str, &str),
+) -> Vec<(String, String)> {
+    let mut problems = Vec::new();
+    let mut report = |field: String, message: String| {
+        if !problems.iter().any(|(f, m)| *f == field && *m == message) {
+            problems.push((field, message));
+        }
+    };
+    for &(row, col) in cells {
+        if row as usize >= board.rows {
+            report(
+                row_field.to_string(),
+                format!("{} is outside the board ({} rows)", row, board.rows),
+            );
+        }
+        if col as usize >= board.cols {
+            report(
+                col_field.to_string(),
+                format!("{} is outside the board ({} cols)", col, board.cols),
+            );
+        }
+        if let Some(cell) = board.get_cell(row as usize, col as usize)
+            && matches!(cell.base, BaseTerrain::Wall | BaseTerrain::TowerDestroyed)
+        {
+            report(
+                format!("{}, {}", row_field, col_field),
+                format!("({}, {}) is not walkable terrain", row, col),
+            );
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map_layout::Camp;
+
+    #[test]
+    fn test_parse_rules() {
+        let content = r#"
+            [timing_rules]
+            game_tick_ms = 40
+            minion_wave_interval_secs = 30
+            countdown_secs = 5
+            pause_timeout_secs = 60
+            reconnect_grace_secs = 90
+            keyframe_interval_ticks = 25
+
+            [minion_rules]
+            minions_per_wave = 6
+            minion_kill_gold = 15
+            minion_kill_xp = 5
+            xp_share_radius = 6
+
+            [champion_rules]
+            champion_respawn_base_time = 10
+            champion_respawn_time_per_level = 2
+            champion_kill_gold = 300
+            champion_kill_xp = 40
+            bounty_per_streak = 50
+            max_bounty_streak = 5
+            assist_gold = 100
+            assist_xp = 20
+            assist_window_secs = 10
+
+            [vision_rules]
+            champion_sight_radius = 8
+            minion_sight_radius = 5
+            tower_sight_radius = 7
+        "#;
+        let rules: RulesConfig = toml::from_str(content).unwrap();
+
+        assert_eq!(rules.timing_rules.game_tick(), Duration::from_millis(40));
+        assert_eq!(
+            rules.timing_rules.minion_wave_interval(),
+            Duration::from_secs(30)
+        );
+        assert_eq!(rules.timing_rules.countdown(), Duration::from_secs(5));
+        assert_eq!(rules.timing_rules.pause_timeout(), Duration::from_secs(60));
+        assert_eq!(
+            rules.timing_rules.reconnect_grace(),
+            Duration::from_secs(90)
+        );
+        assert_eq!(rules.minion_rules.minions_per_wave, 6);
+        assert_eq!(rules.minion_rules.minion_kill_gold, 15);
+        assert_eq!(rules.minion_rules.xp_share_radius, 6);
+        assert_eq!(
+            rules.champion_rules.respawn_time(1),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            rules.champion_rules.respawn_time(4),
+            Duration::from_secs(16)
+        );
+        assert_eq!(rules.champion_rules.bounty(0), 300);
+        assert_eq!(rules.champion_rules.bounty(2), 400);
+        assert_eq!(rules.champion_rules.bounty(9), 550);
+        assert_eq!(rules.vision_rules.champion_sight_radius, 8);
+    }
+
+    fn create_board() -> Board {
+        let mut board = Board::new(200, 200);
+        board.map_layout = serde_json::from_str(
+            r#"{
+                "structures": [
+                    {"kind": "base", "team": "blue", "row": 190, "col": 10},
+                    {"kind": "base", "team": "red", "row": 10, "col": 190}
+                ],
+                "spawns": [
+                    {"team": "blue", "row": 199, "col": 0},
+                    {"team": "red", "row": 0, "col": 199}
+                ]
+            }"#,
+        )
+        .unwrap();
+        board.map_layout.source = "map.json".to_string();
+        board
+    }
+
+    fn load_repo_config() -> GameConfig {
+        GameConfig::load("stats.toml", "spells.toml", "items.toml", "rules.toml").unwrap()
+    }
+
+    #[test]
+    fn test_repo_config_is_valid() {
+        let config = load_repo_config();
+        let board = create_board();
+        assert_eq!(config.validate(&board), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_reports_every_issue() {
+        let mut config = load_repo_config();
+        config.champion.xp_per_level.clear();
+        config.neutral_monsters[0].spawn_row = 500;
+        let mut unknown_spell = config.spells[&0].clone();
+        unknown_spell.id = 9;
+        config.spells.insert(9, unknown_spell);
+        config.rules.timing_rules.game_tick_ms = 0;
+
+        let board = create_board();
+        let issues = config.validate(&board).unwrap_err();
+        let located: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.file.as_str(), issue.field.as_str()))
+            .collect();
+
+        assert_eq!(issues.len(), 4);
+        assert!(located.contains(&("stats.toml", "xp_per_level")));
+        assert!(located.contains(&("stats.toml", "spawn_row")));
+        assert!(located.contains(&("spells.toml", "id")));
+        assert!(located.contains(&("rules.toml", "game_tick_ms")));
+        assert!(issues.iter().any(|issue| issue.section == "spell.9"));
+    }
+
+    #[test]
+    fn test_validate_rejects_monster_on_wall() {
+        let config = load_repo_config();
+        let monster = &config.neutral_monsters[0];
+        let mut board = create_board();
+        board.change_base(
+            BaseTerrain::Wall,
+            monster.spawn_row as usize,
+            monster.spawn_col as usize,
+        );
+
+        let issues = config.validate(&board).unwrap_err();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "spawn_row, spawn_col");
+        assert!(issues[0].message.contains("not walkable"));
+    }
+
+    #[test]
+    fn test_validate_checks_the_whole_footprint() {
+        let config = load_repo_config();
+        let mut board = create_board();
+        // Bottom-right cell of the blue base, anchored on (190, 10)
+        board.change_base(BaseTerrain::Wall, 192, 12);
+        board.map_layout.structures[1].row = 198;
+
+        let issues = config.validate(&board).unwrap_err();
+        let located: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.section.as_str(), issue.field.as_str()))
+            .collect();
+
+        assert_eq!(issues.len(), 2);
+        assert!(located.contains(&("structures.0", "row, col")));
+        assert!(located.contains(&("structures.1", "row")));
+    }
+
+    #[test]
+    fn test_validate_spawn_count() {
+        let board = create_board();
+        assert_eq!(validate_spawn_count(&board, 2), Ok(()));
+
+        let issues = validate_spawn_count(&board, 3).unwrap_err();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            (issues[0].section.as_str(), issues[0].field.as_str()),
+            ("spawns", "team")
+        );
+        assert!(
+            issues[0]
+                .message
+                .starts_with("Blue team has 1 spawn(s) for 2 players")
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_spell_id() {
+        let mut config = load_repo_config();
+        let mut stats = config.spells[&1].clone();
+        stats.id = 9;
+        config.spells.insert(9, stats);
+
+        let issues = config.validate(&create_board()).unwrap_err();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            (issues[0].section.as_str(), issues[0].field.as_str()),
+            ("spell.9", "id")
+        );
+    }
+
+    #[test]
+    fn test_validate_checks_map_sections() {
+        let config = load_repo_config();
+        let mut board = create_board();
+        board.map_layout.structures.pop();
+        board.map_layout.camps.push(Camp {
+            monster: "dragon".to_string(),
+            row: 50,
+            col: 250,
+        });
+
+        let issues = config.validate(&board).unwrap_err();
+        let located: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.section.as_str(), issue.field.as_str()))
+            .collect();
+
+        assert!(issues.iter().all(|issue| issue.file == "map.json"));
+        assert_eq!(issues.len(), 3);
+        assert!(located.contains(&("structures", "kind")));
+        assert!(located.contains(&("camps.0 (dragon)", "monster")));
+        assert!(located.contains(&("camps.0 (dragon)", "col")));
+    }
 }
@@ -14,12 +14,59 @@ pub enum GameError {
     PlayerIsDead,
     #[error("Invalid Animation was called")]
     InvalidAnimation,
-    #[error("Error generating ID")]
-    GenerateIdError,
     #[error("Entity is stunned")]
     IsStunned,
     #[error("Inventory is full")]
     InventoryFull,
     #[error("Not enough gold")]
     NotEnoughGold,
+    #[error("Invalid session token: {0}")]
+    InvalidSession(&'static str),
+}
+
+/// A single problem found while validating the game configuration files.
+#[derive(Debug, Error, PartialEq, Eq, Clone)]
+#[error("{file} [{section}] {field}: {message}")]
+pub struct ConfigIssue {
+    pub file: String,
+    pub section: String,
+    pub field: String,
+    pub message: String,
+}
+
+/// Why a client byte stream could not be decoded.
+#[derive(Debug, Error)]
+pub enum ProtocolError {
+    #[error("Connection error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Unsupported protocol version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Expected protocol version {expected}, got {found}")]
+    VersionMismatch { expected: u8, found: u8 },
+    #[error("Unknown packet code {0}, cannot be skipped in protocol version 1")]
+    UnknownLegacyPacket(u8),
+    #[error("Packet {code} announces {len} bytes of payload, more than allowed")]
+    FrameTooLong { code: u8, len: usize },
+    #[error("Malformed packet {code}: {reason}")]
+    Malformed { code: u8, reason: &'static str },
+}
+
+/// Why a replay file could not be recorded or played back.
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Replay file error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Replay line {line}: {source}")]
+    Parse {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("Replay file is empty")]
+    MissingHeader,
+    #[error("Unsupported replay version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Replay tick {tick} comes after tick {previous}")]
+    OutOfOrder { tick: u64, previous: u64 },
+    #[error("Cannot rebuild the recorded match: {0}")]
+    Setup(String),
 }
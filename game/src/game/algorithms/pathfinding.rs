@@ -10,10 +10,10 @@ pub fn get_valid_neighbors(board: &Board, row: u16, col: u16) -> Vec<(u16, u16)>
             }
             let neighbors_row = row as isize + i;
             let neighbors_col = col as isize + j;
-            if let Some(cell) = board.get_cell(neighbors_row as usize, neighbors_col as usize) {
-                if cell.is_passable() {
-                    valid_neighbors.push((neighbors_row as u16, neighbors_col as u16))
-                }
+            if let Some(cell) = board.get_cell(neighbors_row as usize, neighbors_col as usize)
+                && cell.is_passable()
+            {
+                valid_neighbors.push((neighbors_row as u16, neighbors_col as u16))
             }
         }
     }
@@ -138,7 +138,6 @@ pub fn find_path_on_board(
                             f_cost: neighbor_f_cost,
                         };
                         open_set.push(neighbor_node);
-                    } else {
                     }
                 }
                 None => {
@@ -164,6 +163,23 @@ pub fn find_path_on_board(
     None
 }
 
+/// Whether a unit at `start` can walk next to `goal`, chaining the bounded searches
+/// of `find_path_on_board` the same way moving units re-plan along the way.
+pub fn is_reachable(board: &Board, start: (u16, u16), goal: (u16, u16)) -> bool {
+    let mut current = start;
+    let mut visited = HashSet::new();
+    while current != goal && !is_adjacent_to_goal(current, goal) {
+        if !visited.insert(current) {
+            return false;
+        }
+        match find_path_on_board(board, current, goal).and_then(|path| path.back().copied()) {
+            Some(next) if next != current => current = next,
+            _ => return false,
+        }
+    }
+    true
+}
+
 #[cfg(test)]
 mod pathfinding_tests {
     // You might want to name this module appropriately
@@ -516,7 +532,7 @@ mod pathfinding_tests {
             path1
                 .back()
                 .copied()
-                .map_or(false, |pos| is_adjacent_to_goal(pos, goal)),
+                .is_some_and(|pos| is_adjacent_to_goal(pos, goal)),
             "Minion 1 path should end at a cell adjacent to the goal."
         );
 
@@ -537,7 +553,7 @@ mod pathfinding_tests {
             path2
                 .back()
                 .copied()
-                .map_or(false, |pos| is_adjacent_to_goal(pos, goal)),
+                .is_some_and(|pos| is_adjacent_to_goal(pos, goal)),
             "Minion 2 path should end at a cell adjacent to the goal."
         );
 
@@ -651,7 +667,7 @@ mod pathfinding_tests {
                         // Check if the next step is passable *before* attempting the move
                         if board
                             .get_cell(next_step.0 as usize, next_step.1 as usize)
-                            .map_or(false, |cell| cell.is_passable())
+                            .is_some_and(|cell| cell.is_passable())
                         {
                             // Attempt to move the minion: Clear old cell, update position, place on new cell
                             board
@@ -787,4 +803,19 @@ mod pathfinding_tests {
             );
         }
     }
+
+    #[test]
+    fn test_is_reachable_beyond_search_depth() {
+        let board = Board::new(20, 200);
+        assert!(is_reachable(&board, (10, 0), (10, 199)));
+    }
+
+    #[test]
+    fn test_is_reachable_blocked_by_wall() {
+        let mut board = Board::new(20, 60);
+        for row in 0..20 {
+            board.change_base(BaseTerrain::Wall, row, 30);
+        }
+        assert!(!is_reachable(&board, (10, 0), (10, 59)));
+    }
 }
@@ -29,10 +29,10 @@ impl TowerHitAnimation {
             return None;
         }
         // We return the new position
-        return Some((
+        Some((
             self.last_drawn_row.saturating_add_signed(row_step),
             self.last_drawn_col.saturating_add_signed(col_step),
-        ));
+        ))
     }
 }
 
@@ -50,7 +50,7 @@ impl AnimationTrait for TowerHitAnimation {
     }
 
     fn get_last_drawn_pos(&self) -> Option<(u16, u16)> {
-        return Some((self.last_drawn_row, self.last_drawn_col));
+        Some((self.last_drawn_row, self.last_drawn_col))
     }
 
     fn next_frame(&mut self, row: u16, col: u16) -> AnimationCommand {
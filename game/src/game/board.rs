@@ -1,17 +1,47 @@
 use crate::game::cell::Team;
+use crate::game::map_layout::MapLayout;
 use crate::game::minion_manager::MinionManager;
+use crate::game::vision::TeamVision;
+use crate::packet::rle;
 
 use super::cell::{BaseTerrain, Cell, CellAnimation, CellContent, EncodedCellValue};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::Read;
-use std::usize;
 
-#[derive(Deserialize)]
-struct BoardLayout {
-    rows: usize,
-    cols: usize,
-    layout: Vec<Vec<String>>,
+/// Size in cells of the board area sent to a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub rows: u16,
+    pub cols: u16,
+}
+
+impl Viewport {
+    pub const DEFAULT: Viewport = Viewport { rows: 21, cols: 51 };
+    pub const MIN: Viewport = Viewport { rows: 11, cols: 21 };
+    // Delta frames address cells with a byte
+    pub const MAX: Viewport = Viewport {
+        rows: 61,
+        cols: 161,
+    };
+
+    pub fn len(&self) -> usize {
+        self.rows as usize * self.cols as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// On-disk map format: terrain names row by row, plus the gameplay placements.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardLayout {
+    pub rows: usize,
+    pub cols: usize,
+    pub layout: Vec<Vec<String>>,
+    #[serde(flatten)]
+    pub map_layout: MapLayout,
 }
 
 #[derive(Debug)]
@@ -19,6 +49,7 @@ pub struct Board {
     grid: Vec<Vec<Cell>>,
     pub rows: usize,
     pub cols: usize,
+    pub map_layout: MapLayout,
 }
 
 impl Board {
@@ -26,8 +57,16 @@ impl Board {
         let mut file = File::open(file_path)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
+        Board::from_json_str(&contents, file_path)
+    }
+
+    /// Builds the board from the contents of a map file, `source` names it in reports.
+    pub fn from_json_str(contents: &str, source: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let board_layout: BoardLayout = serde_json::from_str(contents)?;
+        Ok(Board::from_layout(board_layout, source))
+    }
 
-        let board_layout: BoardLayout = serde_json::from_str(&contents)?;
+    pub fn from_layout(board_layout: BoardLayout, source: &str) -> Self {
         let mut grid = Vec::with_capacity(board_layout.rows);
         for (i, row) in board_layout.layout.iter().enumerate() {
             let mut grid_row = Vec::with_capacity(board_layout.cols);
@@ -42,13 +81,14 @@ impl Board {
             }
             grid.push(grid_row);
         }
-        let board = Board {
+        let mut map_layout = board_layout.map_layout;
+        map_layout.source = source.to_string();
+        Board {
             grid,
             rows: board_layout.rows,
             cols: board_layout.cols,
-        };
-
-        Ok(board)
+            map_layout,
+        }
     }
 
     pub fn new(rows: usize, cols: usize) -> Self {
@@ -60,7 +100,12 @@ impl Board {
             }
             grid.push(row)
         }
-        Board { grid, rows, cols }
+        Board {
+            grid,
+            rows,
+            cols,
+            map_layout: MapLayout::default(),
+        }
     }
 
     pub fn get_cell(&self, row: usize, col: usize) -> Option<&Cell> {
@@ -84,10 +129,10 @@ impl Board {
     }
 
     pub fn place_cell(&mut self, content: CellContent, champ_row: usize, champ_col: usize) {
-        if let Some(row) = self.grid.get_mut(champ_row) {
-            if let Some(cell) = row.get_mut(champ_col) {
-                cell.content = Some(content);
-            }
+        if let Some(row) = self.grid.get_mut(champ_row)
+            && let Some(cell) = row.get_mut(champ_col)
+        {
+            cell.content = Some(content);
         }
     }
 
@@ -101,10 +146,10 @@ impl Board {
         animation_row: usize,
         animation_col: usize,
     ) {
-        if let Some(row) = self.grid.get_mut(animation_row) {
-            if let Some(cell) = row.get_mut(animation_col) {
-                cell.animation = Some(animation);
-            }
+        if let Some(row) = self.grid.get_mut(animation_row)
+            && let Some(cell) = row.get_mut(animation_col)
+        {
+            cell.animation = Some(animation);
         }
     }
 
@@ -120,7 +165,7 @@ impl Board {
         view_width: u16,
     ) -> Vec<Vec<&Cell>> {
         let grid_height = self.grid.len() as u16;
-        let grid_width = self.grid.get(0).map_or(0, |r| r.len() as u16);
+        let grid_width = self.grid.first().map_or(0, |r| r.len() as u16);
 
         let half_height = view_height / 2;
         let half_width = view_width / 2;
@@ -135,7 +180,7 @@ impl Board {
         }
         // Adjust if view hit the bottom
         if max_row == grid_height - 1 {
-            min_row = (grid_height - view_height).max(0);
+            min_row = grid_height - view_height;
         }
 
         // Calculate potential min and max col
@@ -147,7 +192,7 @@ impl Board {
         }
         // Adjust if view hit the right
         if max_col == grid_width - 1 {
-            min_col = (grid_width - view_width).max(0);
+            min_col = grid_width - view_width;
         }
 
         self.grid[min_row as usize..=max_row as usize]
@@ -157,44 +202,59 @@ impl Board {
             .collect()
     }
 
-    pub fn run_length_encode(
+    /// Closest viewport to the requested size within the server limits and the board. Sizes
+    /// are odd so the view centers on the player.
+    pub fn clamp_viewport(&self, rows: u16, cols: u16) -> Viewport {
+        let fit = |requested: u16, min: u16, max: u16, board: usize| {
+            let size = requested.clamp(min, max).min(board as u16);
+            if size.is_multiple_of(2) {
+                size - 1
+            } else {
+                size
+            }
+        };
+        Viewport {
+            rows: fit(rows, Viewport::MIN.rows, Viewport::MAX.rows, self.rows),
+            cols: fit(cols, Viewport::MIN.cols, Viewport::MAX.cols, self.cols),
+        }
+    }
+
+    /// Encoded value of every cell in the player's view, row by row. With a team vision,
+    /// cells the team cannot see only show their terrain.
+    pub fn encoded_view(
         &self,
         player_row: u16,
         player_col: u16,
+        viewport: Viewport,
         minion_manager: &MinionManager,
+        vision: Option<&TeamVision>,
     ) -> Vec<u8> {
-        let flattened_grid: Vec<&Cell> = self
-            .center_view(player_row, player_col, 21, 51)
+        self.center_view(player_row, player_col, viewport.rows, viewport.cols)
             .into_iter()
             .flat_map(|row| row.into_iter())
-            .collect();
-        let mut rle: Vec<String> = Vec::new();
-
-        if flattened_grid.is_empty() {
-            return Vec::new();
-        }
-
-        let mut current_cell_value: EncodedCellValue;
-        if let Some(first_cell) = flattened_grid.get(0) {
-            current_cell_value = get_encoded_cell_value(first_cell, minion_manager);
-        } else {
-            return Vec::new(); // Should not happen if flattened_grid is not empty
-        }
-        let mut count = 1;
+            .map(|cell| match vision {
+                Some(vision) if !vision.reveals(cell) => encoded_terrain(cell.base) as u8,
+                _ => get_encoded_cell_value(cell, minion_manager) as u8,
+            })
+            .collect()
+    }
 
-        for i in 1..flattened_grid.len() {
-            let encoded_value = get_encoded_cell_value(flattened_grid[i], minion_manager);
-            if encoded_value == current_cell_value {
-                count += 1;
-            } else {
-                rle.push(format!("{}:{}", current_cell_value as u8, count));
-                current_cell_value = encoded_value;
-                count = 1;
-            }
-        }
-        rle.push(format!("{}:{}", current_cell_value as u8, count));
-        println!("Encoded board: {}", rle.join("|"));
-        rle.join("|").into_bytes()
+    /// Text run-length encoding of the player's view, the format of the `BoardPacket`.
+    pub fn run_length_encode(
+        &self,
+        player_row: u16,
+        player_col: u16,
+        viewport: Viewport,
+        minion_manager: &MinionManager,
+        vision: Option<&TeamVision>,
+    ) -> Vec<u8> {
+        rle::encode_text(&self.encoded_view(
+            player_row,
+            player_col,
+            viewport,
+            minion_manager,
+            vision,
+        ))
     }
 }
 
@@ -215,7 +275,7 @@ fn get_encoded_cell_value(cell: &Cell, minion_manager: &MinionManager) -> Encode
                 if let Some(minion) = minion_manager.minions.get(minion_id) {
                     let health_percentage =
                         (minion.stats.health as f32 / minion.stats.max_health as f32) * 100.0;
-                    let health_level = ((health_percentage / 12.5).ceil() as u8).max(1).min(8); // Map to 1-8
+                    let health_level = ((health_percentage / 12.5).ceil() as u8).clamp(1, 8); // Map to 1-8
                     EncodedCellValue::from_health_level(health_level, *team)
                 } else {
                     EncodedCellValue::MinionPlaceholder
@@ -230,12 +290,16 @@ fn get_encoded_cell_value(cell: &Cell, minion_manager: &MinionManager) -> Encode
             CellContent::Monster(_) => EncodedCellValue::Monster,
         }
     } else {
-        match cell.base {
-            BaseTerrain::Wall => EncodedCellValue::Wall,
-            BaseTerrain::Floor => EncodedCellValue::Floor,
-            BaseTerrain::Bush => EncodedCellValue::Bush,
-            BaseTerrain::TowerDestroyed => EncodedCellValue::TowerDestroyed,
-        }
+        encoded_terrain(cell.base)
+    }
+}
+
+fn encoded_terrain(base: BaseTerrain) -> EncodedCellValue {
+    match base {
+        BaseTerrain::Wall => EncodedCellValue::Wall,
+        BaseTerrain::Floor => EncodedCellValue::Floor,
+        BaseTerrain::Bush => EncodedCellValue::Bush,
+        BaseTerrain::TowerDestroyed => EncodedCellValue::TowerDestroyed,
     }
 }
 
@@ -315,7 +379,7 @@ mod tests {
 
     #[test]
     fn test_get_cell() {
-        let mut board = Board::new(5, 5);
+        let board = Board::new(5, 5);
 
         // Test valid coordinates
         let cell = board.get_cell(2, 2);
@@ -517,8 +581,8 @@ mod tests {
             let mut current_cell_value: EncodedCellValue =
                 EncodedCellValue::from(flattened_grid[0]);
             let mut count = 1;
-            for i in 1..flattened_grid.len() {
-                let encoded_value = EncodedCellValue::from(flattened_grid[i]);
+            for cell in flattened_grid.iter().skip(1) {
+                let encoded_value = EncodedCellValue::from(*cell);
                 if encoded_value == current_cell_value {
                     count += 1;
                 } else {
@@ -540,4 +604,27 @@ mod tests {
             "Run-length encoding did not match expected output"
         );
     }
+
+    #[test]
+    fn test_clamp_viewport() {
+        let board = Board::new(40, 300);
+        assert_eq!(
+            board.clamp_viewport(31, 100),
+            Viewport { rows: 31, cols: 99 }
+        );
+        assert_eq!(board.clamp_viewport(1, 1), Viewport::MIN);
+        // Limited by the server first, then by the board height
+        assert_eq!(
+            board.clamp_viewport(500, 500),
+            Viewport {
+                rows: 39,
+                cols: Viewport::MAX.cols
+            }
+        );
+
+        let viewport = board.clamp_viewport(15, 41);
+        let view = board.center_view(20, 150, viewport.rows, viewport.cols);
+        assert_eq!(view.len(), 15);
+        assert!(view.iter().all(|row| row.len() == 41));
+    }
 }
@@ -1,15 +1,18 @@
 pub mod stun_buff;
-use std::{fmt::Debug, time::Duration};
+use std::fmt::Debug;
+
+use super::clock::GameInstant;
 
 pub trait HasBuff {
-    fn is_stunned(&self) -> bool;
-    fn set_stunned(&mut self, stunned: bool, duration: Option<Duration>);
+    fn is_stunned(&self, now: GameInstant) -> bool;
+    /// Stuns the entity until the given instant, or clears the stun with `None`.
+    fn set_stunned(&mut self, until: Option<GameInstant>);
 }
 
 pub trait Buff: Send + Sync + Debug {
     fn clone_box(&self) -> Box<dyn Buff>;
     fn id(&self) -> &str;
-    fn on_apply(&mut self, target: &mut dyn HasBuff);
-    fn on_tick(&mut self, target: &mut dyn HasBuff) -> bool;
+    fn on_apply(&mut self, target: &mut dyn HasBuff, now: GameInstant);
+    fn on_tick(&mut self, target: &mut dyn HasBuff, now: GameInstant) -> bool;
     fn on_remove(&mut self, target: &mut dyn HasBuff);
 }
@@ -1,18 +1,19 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use super::Buff;
+use crate::game::clock::GameInstant;
 
 #[derive(Debug, Clone)]
 pub struct StunBuff {
     pub duration_remaining: Duration,
-    pub applied_at: Instant,
+    pub applied_at: GameInstant,
 }
 
 impl StunBuff {
     pub fn new(duration: u64) -> StunBuff {
         StunBuff {
             duration_remaining: Duration::from_secs(duration),
-            applied_at: Instant::now(),
+            applied_at: GameInstant::ZERO,
         }
     }
 }
@@ -22,16 +23,17 @@ impl Buff for StunBuff {
         "Stun"
     }
 
-    fn on_apply(&mut self, target: &mut dyn super::HasBuff) {
-        target.set_stunned(true, Some(self.duration_remaining));
+    fn on_apply(&mut self, target: &mut dyn super::HasBuff, now: GameInstant) {
+        self.applied_at = now;
+        target.set_stunned(Some(now + self.duration_remaining));
     }
 
-    fn on_tick(&mut self, _target: &mut dyn super::HasBuff) -> bool {
-        self.applied_at.elapsed() > self.duration_remaining
+    fn on_tick(&mut self, _target: &mut dyn super::HasBuff, now: GameInstant) -> bool {
+        now.duration_since(self.applied_at) > self.duration_remaining
     }
 
     fn on_remove(&mut self, target: &mut dyn super::HasBuff) {
-        target.set_stunned(false, None);
+        target.set_stunned(None);
     }
 
     fn clone_box(&self) -> Box<dyn Buff> {
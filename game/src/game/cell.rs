@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
 pub type PlayerId = usize;
@@ -6,12 +7,24 @@ pub type MonsterId = usize;
 pub type FlagId = usize;
 pub type TowerId = usize;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Team {
     Blue,
     Red,
 }
 
+impl Team {
+    /// Team of a player joining without a roster: odd ids play Blue, even ids play Red.
+    pub fn of_player(player_id: PlayerId) -> Self {
+        if player_id.is_multiple_of(2) {
+            Team::Red
+        } else {
+            Team::Blue
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BaseTerrain {
     Wall,
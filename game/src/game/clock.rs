@@ -0,0 +1,104 @@
+use std::ops::{Add, Sub};
+use std::time::Duration;
+
+/// A point in simulated match time, measured from the first game tick.
+///
+/// Every cooldown and timer in the game logic is expressed with this type so
+/// that a match only depends on its inputs and on the number of ticks played.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GameInstant(Duration);
+
+impl GameInstant {
+    pub const ZERO: GameInstant = GameInstant(Duration::ZERO);
+
+    pub fn from_millis(millis: u64) -> Self {
+        GameInstant(Duration::from_millis(millis))
+    }
+
+    pub fn as_millis(&self) -> u64 {
+        self.0.as_millis() as u64
+    }
+
+    /// Time elapsed since `earlier`, or zero if `earlier` is in the future.
+    pub fn duration_since(&self, earlier: GameInstant) -> Duration {
+        self.0.saturating_sub(earlier.0)
+    }
+}
+
+impl Add<Duration> for GameInstant {
+    type Output = GameInstant;
+
+    fn add(self, rhs: Duration) -> GameInstant {
+        GameInstant(self.0 + rhs)
+    }
+}
+
+impl Sub<Duration> for GameInstant {
+    type Output = GameInstant;
+
+    fn sub(self, rhs: Duration) -> GameInstant {
+        GameInstant(self.0.saturating_sub(rhs))
+    }
+}
+
+/// Fixed-timestep clock driven by `GameManager::game_tick`.
+#[derive(Debug, Clone)]
+pub struct SimClock {
+    tick: u64,
+    tick_duration: Duration,
+}
+
+impl SimClock {
+    pub fn new(tick_duration: Duration) -> Self {
+        SimClock {
+            tick: 0,
+            tick_duration,
+        }
+    }
+
+    pub fn advance(&mut self) {
+        self.tick = self.tick.saturating_add(1);
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn tick_duration(&self) -> Duration {
+        self.tick_duration
+    }
+
+    pub fn now(&self) -> GameInstant {
+        let nanos = (self.tick_duration.as_nanos() as u64).saturating_mul(self.tick);
+        GameInstant(Duration::from_nanos(nanos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_advances_by_tick_duration() {
+        let mut clock = SimClock::new(Duration::from_millis(40));
+        assert_eq!(clock.tick(), 0);
+        assert_eq!(clock.now(), GameInstant::ZERO);
+
+        for _ in 0..25 {
+            clock.advance();
+        }
+        assert_eq!(clock.tick(), 25);
+        assert_eq!(clock.now(), GameInstant::from_millis(1000));
+    }
+
+    #[test]
+    fn test_game_instant_arithmetic() {
+        let start = GameInstant::from_millis(500);
+        let later = start + Duration::from_secs(2);
+        assert_eq!(later.as_millis(), 2500);
+        assert_eq!(later.duration_since(start), Duration::from_secs(2));
+        // Going backward saturates instead of panicking
+        assert_eq!(start.duration_since(later), Duration::ZERO);
+        assert_eq!(start - Duration::from_secs(10), GameInstant::ZERO);
+    }
+}
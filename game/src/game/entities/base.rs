@@ -1,7 +1,10 @@
+use std::time::Duration;
+
 use crate::config::BaseStats;
 use crate::game::Board;
 use crate::game::Cell;
 use crate::game::cell::Team;
+use crate::game::clock::GameInstant;
 use crate::game::entities::{Fighter, Stats};
 
 use super::AttackAction;
@@ -11,13 +14,18 @@ pub struct Base {
     pub team: Team,
     pub stats: Stats,
     pub position: (i32, i32),
+    pub fountain_radius: u16,
+    pub fountain_heal_percent: u16,
+    pub fountain_damage: u16,
+    fountain_pulse: Duration,
+    next_pulse: GameInstant,
 }
 
 impl Base {
     pub fn new(team: Team, position: (i32, i32), base_stats: BaseStats) -> Self {
         let stats = Stats {
             attack_damage: 0,
-            attack_speed: std::time::Duration::from_secs(999),
+            attack_speed: Duration::from_secs(999),
             health: base_stats.health,
             max_health: base_stats.health,
             mana: 0,
@@ -29,24 +37,76 @@ impl Base {
             team,
             stats,
             position,
+            fountain_radius: base_stats.fountain_radius,
+            fountain_heal_percent: base_stats.fountain_heal_percent,
+            fountain_damage: base_stats.fountain_damage,
+            fountain_pulse: base_stats.fountain_pulse(),
+            next_pulse: GameInstant::ZERO,
+        }
+    }
+
+    /// Distance in cells from `(row, col)` to the 3x3 footprint of the base, 0 on it.
+    fn distance_to(&self, row: u16, col: u16) -> u16 {
+        let gap = |value: u16, start: i32| {
+            let value = value as i32;
+            (start - value).max(value - (start + 2)).max(0) as u16
+        };
+        gap(row, self.position.0).max(gap(col, self.position.1))
+    }
+
+    pub fn in_fountain(&self, row: u16, col: u16) -> bool {
+        self.distance_to(row, col) <= self.fountain_radius
+    }
+
+    /// Whether the fountain acts this tick, it pulses once every `fountain_pulse_ms`.
+    pub fn fountain_ready(&mut self, now: GameInstant) -> bool {
+        if now < self.next_pulse {
+            return false;
+        }
+        self.next_pulse = now + self.fountain_pulse;
+        true
+    }
+
+    /// Where a champion of this team comes back: `preferred` when it is free, otherwise the
+    /// closest free cell around the base, scanned ring by ring in row order.
+    pub fn spawn_cell(&self, board: &Board, preferred: (u16, u16)) -> Option<(u16, u16)> {
+        let is_free = |(row, col): (i32, i32)| {
+            row >= 0
+                && col >= 0
+                && board
+                    .get_cell(row as usize, col as usize)
+                    .is_some_and(|cell| cell.is_passable())
+        };
+        if is_free((preferred.0 as i32, preferred.1 as i32)) {
+            return Some(preferred);
         }
+        let (top, left) = self.position;
+        (1..=self.fountain_radius.max(1) as i32).find_map(|ring| {
+            (top - ring..=top + 2 + ring)
+                .flat_map(|row| (left - ring..=left + 2 + ring).map(move |col| (row, col)))
+                .filter(|&(row, col)| {
+                    row == top - ring
+                        || row == top + 2 + ring
+                        || col == left - ring
+                        || col == left + 2 + ring
+                })
+                .find(|&cell| is_free(cell))
+                .map(|(row, col)| (row as u16, col as u16))
+        })
     }
 }
 
 impl Fighter for Base {
-    fn take_effect(&mut self, effects: Vec<GameplayEffect>) {
+    fn take_effect(&mut self, effects: Vec<GameplayEffect>, _now: GameInstant) {
         for effect in effects.into_iter() {
-            match effect {
-                GameplayEffect::Damage(damage) => {
-                    self.stats.health = self.stats.health.saturating_sub(damage as u16);
-                }
-                // Tower cannot be affected by buff or debuff
-                _ => {}
+            // Base cannot be affected by buff or debuff
+            if let GameplayEffect::Damage(damage) = effect {
+                self.stats.health = self.stats.health.saturating_sub(damage);
             }
         }
     }
 
-    fn can_attack(&mut self) -> Option<AttackAction> {
+    fn can_attack(&mut self, _now: GameInstant) -> Option<AttackAction> {
         // Base can't attack
         None
     }
@@ -61,13 +121,17 @@ impl Fighter for Base {
 mod tests {
     use super::*;
     use crate::config::BaseStats;
-    use crate::game::cell::Team;
+    use crate::game::cell::{CellContent, Team};
     use crate::game::entities::projectile::GameplayEffect;
 
     fn create_default_base_stats() -> BaseStats {
         BaseStats {
             health: 5000,
             armor: 10,
+            fountain_radius: 2,
+            fountain_pulse_ms: 1000,
+            fountain_heal_percent: 10,
+            fountain_damage: 60,
         }
     }
 
@@ -84,13 +148,45 @@ mod tests {
     fn test_take_damage() {
         let base_stats = create_default_base_stats();
         let mut base = Base::new(Team::Red, (10, 10), base_stats);
-        base.take_effect(vec![GameplayEffect::Damage(100)]);
+        base.take_effect(vec![GameplayEffect::Damage(100)], GameInstant::ZERO);
         assert_eq!(base.stats.health, 4900);
 
-        base.take_effect(vec![GameplayEffect::Damage(5000)]);
+        base.take_effect(vec![GameplayEffect::Damage(5000)], GameInstant::ZERO);
         assert_eq!(base.stats.health, 0);
 
-        base.take_effect(vec![GameplayEffect::Damage(100)]);
+        base.take_effect(vec![GameplayEffect::Damage(100)], GameInstant::ZERO);
         assert_eq!(base.stats.health, 0);
     }
+
+    #[test]
+    fn test_fountain_zone_and_pulse() {
+        let mut base = Base::new(Team::Blue, (10, 10), create_default_base_stats());
+        // The footprint covers rows and cols 10 to 12, the fountain reaches 2 more cells
+        assert!(base.in_fountain(11, 11));
+        assert!(base.in_fountain(8, 14));
+        assert!(!base.in_fountain(7, 11));
+        assert!(!base.in_fountain(11, 15));
+
+        assert!(base.fountain_ready(GameInstant::ZERO));
+        assert!(!base.fountain_ready(GameInstant::from_millis(999)));
+        assert!(base.fountain_ready(GameInstant::from_millis(1000)));
+    }
+
+    #[test]
+    fn test_spawn_cell_falls_back_around_the_base() {
+        let mut board = Board::new(20, 20);
+        let base = Base::new(Team::Blue, (10, 10), create_default_base_stats());
+        assert_eq!(base.spawn_cell(&board, (15, 11)), Some((15, 11)));
+
+        board.place_cell(CellContent::Minion(1, Team::Red), 15, 11);
+        assert_eq!(base.spawn_cell(&board, (15, 11)), Some((9, 9)));
+
+        // Every cell in reach is taken
+        for row in 8..15 {
+            for col in 8..15 {
+                board.place_cell(CellContent::Minion(1, Team::Red), row, col);
+            }
+        }
+        assert_eq!(base.spawn_cell(&board, (15, 11)), None);
+    }
 }
@@ -13,4 +13,8 @@ pub struct ItemStats {
     pub attack_damage: Option<u32>,
     pub health: Option<u32>,
     pub armor: Option<u32>,
+    #[serde(default)]
+    pub health_regen: Option<u32>,
+    #[serde(default)]
+    pub mana_regen: Option<u32>,
 }
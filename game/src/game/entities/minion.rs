@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::{HashMap, VecDeque},
-    time::{Duration, Instant},
+    collections::{BTreeMap, VecDeque},
+    time::Duration,
 };
 use strum_macros::EnumIter;
 
@@ -12,6 +13,8 @@ use crate::{
         animation::{AnimationTrait, melee::MeleeAnimation},
         buffs::{Buff, HasBuff},
         cell::Team,
+        clock::GameInstant,
+        map_layout::LanePath,
     },
 };
 
@@ -20,7 +23,8 @@ use crate::config::MinionStats;
 
 type MinionPath = (u16, u16);
 
-#[derive(Debug, PartialEq, Eq, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Lane {
     Top,
     Mid,
@@ -31,16 +35,15 @@ pub enum Lane {
 pub struct Minion {
     pub minion_id: MinionId,
     pub team_id: Team,
-    lane: Lane,
     path: Option<VecDeque<(u16, u16)>>,
     pub stats: Stats,
     minion_stats: MinionStats,
     current_path: MinionPath,
     minion_path: Vec<MinionPath>,
     checkpoint: usize,
-    last_attacked: Instant,
-    stun_timer: Option<Instant>,
-    pub active_buffs: HashMap<String, Box<dyn Buff>>,
+    last_attacked: GameInstant,
+    stun_timer: Option<GameInstant>,
+    pub active_buffs: BTreeMap<String, Box<dyn Buff>>,
     pub row: u16,
     pub col: u16,
 }
@@ -54,7 +57,18 @@ impl Minion {
         self.stats.max_health
     }
 
-    pub fn new(minion_id: MinionId, team_id: Team, lane: Lane, minion_stats: MinionStats) -> Self {
+    /// Applies reloaded balance values to this live minion.
+    pub fn rescale(&mut self, minion_stats: MinionStats) {
+        self.stats.rescale(
+            minion_stats.health,
+            minion_stats.attack_damage,
+            Duration::from_millis(minion_stats.attack_speed_ms),
+            minion_stats.armor,
+        );
+        self.minion_stats = minion_stats;
+    }
+
+    pub fn new(minion_id: MinionId, lane_path: &LanePath, minion_stats: MinionStats) -> Self {
         let stats = Stats {
             attack_damage: minion_stats.attack_damage,
             attack_speed: Duration::from_millis(minion_stats.attack_speed_ms),
@@ -65,57 +79,22 @@ impl Minion {
             armor: minion_stats.armor,
         };
 
-        let (row, col, paths) = match team_id {
-            Team::Blue => match lane {
-                Lane::Top => (
-                    184,
-                    10,
-                    vec![(120, 8), (39, 7), (7, 39), (8, 120), (10, 184)],
-                ),
-                Lane::Mid => (
-                    184,
-                    17,
-                    vec![(148, 67), (115, 82), (82, 115), (67, 148), (17, 184)],
-                ),
-                Lane::Bottom => (
-                    191,
-                    17,
-                    vec![(191, 79), (196, 150), (150, 196), (79, 191), (17, 191)],
-                ),
-            },
-            Team::Red => match lane {
-                Lane::Top => (
-                    10,
-                    184,
-                    vec![(8, 120), (7, 39), (39, 7), (120, 8), (184, 10)],
-                ),
-                Lane::Mid => (
-                    17,
-                    184,
-                    vec![(67, 148), (82, 115), (115, 82), (148, 67), (184, 17)],
-                ),
-                Lane::Bottom => (
-                    17,
-                    191,
-                    vec![(79, 191), (150, 196), (196, 150), (191, 79), (191, 17)],
-                ),
-            },
-        };
+        let (row, col) = lane_path.start;
+        let paths = lane_path.waypoints.clone();
         let path = paths[0];
 
         Self {
             minion_id,
-            team_id,
-            lane,
+            team_id: lane_path.team,
             path: None,
             stats,
             minion_stats,
             current_path: path,
             minion_path: paths,
             checkpoint: 0,
-            last_attacked: Instant::now(),
+            last_attacked: GameInstant::ZERO,
             stun_timer: None,
-            active_buffs: HashMap::new(),
+            active_buffs: BTreeMap::new(),
             row,
             col,
         }
@@ -124,16 +103,16 @@ impl Minion {
     fn change_goal(&mut self) {
         if self.checkpoint < self.minion_path.len() {
             self.checkpoint += 1;
-            self.current_path = self.minion_path[self.checkpoint as usize];
+            self.current_path = self.minion_path[self.checkpoint];
         }
     }
 
     pub fn is_dead(&self) -> bool {
-        if self.stats.health <= 0 { true } else { false }
+        self.stats.health == 0
     }
 
-    pub fn movement_phase(&mut self, board: &mut Board) -> Result<(), GameError> {
-        if self.is_stunned() {
+    pub fn movement_phase(&mut self, board: &mut Board, now: GameInstant) -> Result<(), GameError> {
+        if self.is_stunned(now) {
             return Ok(());
         }
         if is_adjacent_to_goal((self.row, self.col), self.current_path) {
@@ -171,15 +150,15 @@ impl Minion {
         let row_step = (target_pos.0 as i16 - self.row as i16).signum() as isize;
         let col_step = (target_pos.1 as i16 - self.col as i16).signum() as isize;
         match self.move_minion(board, row_step, col_step) {
-            Ok(_) => return Ok(()),
+            Ok(_) => Ok(()),
             Err(_) => {
                 if let Some(calculated_path) =
                     find_path_on_board(board, (self.row, self.col), target_pos)
                 {
                     self.path = Some(calculated_path);
-                    return Ok(());
+                    Ok(())
                 } else {
-                    return Err(GameError::CannotMoveHere(self.minion_id));
+                    Err(GameError::CannotMoveHere(self.minion_id))
                 }
             }
         }
@@ -190,61 +169,52 @@ impl Minion {
         board: &mut Board,
         new_animations: &mut Vec<Box<dyn AnimationTrait>>,
         pending_effects: &mut Vec<(Option<super::super::PlayerId>, Target, Vec<GameplayEffect>)>,
+        now: GameInstant,
     ) {
-        if self.is_stunned() {
+        if self.is_stunned(now) {
             return;
         }
-        if let Some(enemy) = self.get_potential_target(board) {
-            match &enemy.content {
-                Some(content) => match content {
-                    CellContent::Tower(id, _) => {
-                        if let Some(attack) = self.can_attack() {
-                            match attack {
-                                AttackAction::Melee { damage, animation } => {
-                                    new_animations.push(animation);
-                                    pending_effects.push((
-                                        None,
-                                        Target::Tower(*id),
-                                        vec![GameplayEffect::Damage(damage)],
-                                    ))
-                                }
-                                _ => {}
-                            }
-                        }
+        if let Some(enemy) = self.get_potential_target(board)
+            && let Some(content) = &enemy.content
+        {
+            match content {
+                CellContent::Tower(id, _) => {
+                    if let Some(attack) = self.can_attack(now)
+                        && let AttackAction::Melee { damage, animation } = attack
+                    {
+                        new_animations.push(animation);
+                        pending_effects.push((
+                            None,
+                            Target::Tower(*id),
+                            vec![GameplayEffect::Damage(damage)],
+                        ))
                     }
-                    CellContent::Minion(id, _) => {
-                        if let Some(attack) = self.can_attack() {
-                            match attack {
-                                AttackAction::Melee { damage, animation } => {
-                                    new_animations.push(animation);
-                                    pending_effects.push((
-                                        None,
-                                        Target::Minion(*id),
-                                        vec![GameplayEffect::Damage(damage)],
-                                    ))
-                                }
-                                _ => {}
-                            }
-                        }
+                }
+                CellContent::Minion(id, _) => {
+                    if let Some(attack) = self.can_attack(now)
+                        && let AttackAction::Melee { damage, animation } = attack
+                    {
+                        new_animations.push(animation);
+                        pending_effects.push((
+                            None,
+                            Target::Minion(*id),
+                            vec![GameplayEffect::Damage(damage)],
+                        ))
                     }
-                    CellContent::Champion(id, _) => {
-                        if let Some(attack) = self.can_attack() {
-                            match attack {
-                                AttackAction::Melee { damage, animation } => {
-                                    new_animations.push(animation);
-                                    pending_effects.push((
-                                        None,
-                                        Target::Champion(*id),
-                                        vec![GameplayEffect::Damage(damage)],
-                                    ))
-                                }
-                                _ => {}
-                            }
-                        }
+                }
+                CellContent::Champion(id, _) => {
+                    if let Some(attack) = self.can_attack(now)
+                        && let AttackAction::Melee { damage, animation } = attack
+                    {
+                        new_animations.push(animation);
+                        pending_effects.push((
+                            None,
+                            Target::Champion(*id),
+                            vec![GameplayEffect::Damage(damage)],
+                        ))
                     }
-                    _ => return,
-                },
-                None => return,
+                }
+                _ => (),
             }
         }
     }
@@ -283,37 +253,37 @@ impl Minion {
                 self.col = new_col;
                 Ok(())
             } else {
-                return Err(GameError::CannotMoveHere(self.minion_id));
+                Err(GameError::CannotMoveHere(self.minion_id))
             }
         } else {
-            return Err(GameError::NotFoundCell);
+            Err(GameError::NotFoundCell)
         }
     }
 }
 
 impl Fighter for Minion {
-    fn take_effect(&mut self, effects: Vec<GameplayEffect>) {
+    fn take_effect(&mut self, effects: Vec<GameplayEffect>, now: GameInstant) {
         for effect in effects.into_iter() {
             match effect {
                 GameplayEffect::Damage(damage) => {
                     let reduced_damage = reduced_damage(damage, self.stats.armor);
-                    self.stats.health = self.stats.health.saturating_sub(reduced_damage as u16);
+                    self.stats.health = self.stats.health.saturating_sub(reduced_damage);
                 }
                 GameplayEffect::Heal(heal_amount) => {
                     self.stats.health =
                         (self.stats.health + heal_amount).min(self.stats.max_health);
                 }
                 GameplayEffect::Buff(mut buff) => {
-                    buff.on_apply(self);
+                    buff.on_apply(self, now);
                     self.active_buffs.insert(buff.id().to_string(), buff);
                 }
             }
         }
     }
 
-    fn can_attack(&mut self) -> Option<AttackAction> {
-        if self.last_attacked + self.stats.attack_speed < Instant::now() {
-            self.last_attacked = Instant::now();
+    fn can_attack(&mut self, now: GameInstant) -> Option<AttackAction> {
+        if self.last_attacked + self.stats.attack_speed < now {
+            self.last_attacked = now;
             let animation = MeleeAnimation::new(self.minion_id);
             Some(AttackAction::Melee {
                 damage: self.stats.attack_damage,
@@ -369,21 +339,12 @@ impl Fighter for Minion {
 }
 
 impl HasBuff for Minion {
-    fn is_stunned(&self) -> bool {
-        self.stun_timer
-            .map_or(false, |timer_end| Instant::now() < timer_end)
+    fn is_stunned(&self, now: GameInstant) -> bool {
+        self.stun_timer.is_some_and(|timer_end| now < timer_end)
     }
 
-    fn set_stunned(&mut self, stunned: bool, duration: Option<Duration>) {
-        if stunned {
-            if let Some(dur) = duration {
-                self.stun_timer = Some(Instant::now() + dur);
-            } else {
-                self.stun_timer = Some(Instant::now() + Duration::from_secs(1));
-            }
-        } else {
-            self.stun_timer = None;
-        }
+    fn set_stunned(&mut self, until: Option<GameInstant>) {
+        self.stun_timer = until;
     }
 }
 
@@ -420,6 +381,46 @@ mod tests {
         (next_row, next_col)
     }
 
+    // Lane paths of the classic map
+    fn lane_path(team: Team, lane: Lane) -> LanePath {
+        let (start, waypoints) = match team {
+            Team::Blue => match lane {
+                Lane::Top => (
+                    (184, 10),
+                    vec![(120, 8), (39, 7), (7, 39), (8, 120), (10, 184)],
+                ),
+                Lane::Mid => (
+                    (184, 17),
+                    vec![(148, 67), (115, 82), (82, 115), (67, 148), (17, 184)],
+                ),
+                Lane::Bottom => (
+                    (191, 17),
+                    vec![(191, 79), (196, 150), (150, 196), (79, 191), (17, 191)],
+                ),
+            },
+            Team::Red => match lane {
+                Lane::Top => (
+                    (10, 184),
+                    vec![(8, 120), (7, 39), (39, 7), (120, 8), (184, 10)],
+                ),
+                Lane::Mid => (
+                    (17, 184),
+                    vec![(67, 148), (82, 115), (115, 82), (148, 67), (184, 17)],
+                ),
+                Lane::Bottom => (
+                    (17, 191),
+                    vec![(79, 191), (150, 196), (196, 150), (191, 79), (191, 17)],
+                ),
+            },
+        };
+        LanePath {
+            team,
+            lane,
+            start,
+            waypoints,
+        }
+    }
+
     fn create_default_minion_stats() -> MinionStats {
         MinionStats {
             attack_damage: 6,
@@ -436,7 +437,7 @@ mod tests {
     #[test]
     fn test_minion_stun_application() {
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(1, Team::Blue, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(1, &lane_path(Team::Blue, Lane::Mid), minion_stats);
         let mut board = create_dummy_board(200, 200);
         let mut new_animations = Vec::new();
         let mut pending_effects = Vec::new();
@@ -444,11 +445,11 @@ mod tests {
         // Apply a stun buff
         let stun_duration_secs = 5;
         let stun_effect = GameplayEffect::Buff(Box::new(StunBuff::new(stun_duration_secs)));
-        minion.take_effect(vec![stun_effect]);
+        minion.take_effect(vec![stun_effect], GameInstant::ZERO);
 
         // Assert minion is stunned
         assert!(
-            minion.is_stunned(),
+            minion.is_stunned(GameInstant::ZERO),
             "Minion should be stunned after applying stun buff"
         );
 
@@ -456,7 +457,7 @@ mod tests {
         let initial_col = minion.col;
 
         // Assert stunned minion cannot move
-        let move_result = minion.movement_phase(&mut board);
+        let move_result = minion.movement_phase(&mut board, GameInstant::ZERO);
         assert!(
             move_result.is_ok(),
             "Stunned minion should not be able to move"
@@ -472,11 +473,16 @@ mod tests {
 
         // Assert stunned minion cannot attack
         assert!(
-            minion.can_attack().is_none(),
+            minion.can_attack(GameInstant::ZERO).is_none(),
             "Stunned minion should not be able to attack"
         );
         // attack_phase should also do nothing
-        minion.attack_phase(&mut board, &mut new_animations, &mut pending_effects);
+        minion.attack_phase(
+            &mut board,
+            &mut new_animations,
+            &mut pending_effects,
+            GameInstant::ZERO,
+        );
         assert!(
             new_animations.is_empty(),
             "Stunned minion attack_phase should not create animations"
@@ -490,20 +496,20 @@ mod tests {
     #[test]
     fn test_minion_stun_expiration() {
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(1, Team::Blue, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(1, &lane_path(Team::Blue, Lane::Mid), minion_stats);
         let mut board = create_dummy_board(200, 200);
         minion.row = 180;
         minion.col = 10;
 
         // Apply a very short stun buff
         let stun_effect = GameplayEffect::Buff(Box::new(StunBuff::new(0))); // Duration 0 for immediate expiration
-        minion.take_effect(vec![stun_effect]);
+        minion.take_effect(vec![stun_effect], GameInstant::ZERO);
 
         // Manually process buffs to trigger expiration
         let current_buffs = std::mem::take(&mut minion.active_buffs);
-        let mut kept_buffs = HashMap::new();
+        let mut kept_buffs = BTreeMap::new();
         for (id, mut buff) in current_buffs.into_iter() {
-            if buff.on_tick(&mut minion) {
+            if buff.on_tick(&mut minion, GameInstant::from_millis(1)) {
                 buff.on_remove(&mut minion);
             } else {
                 kept_buffs.insert(id, buff);
@@ -513,7 +519,7 @@ mod tests {
 
         // Assert minion is no longer stunned
         assert!(
-            !minion.is_stunned(),
+            !minion.is_stunned(GameInstant::ZERO),
             "Minion should not be stunned after buff expiration"
         );
 
@@ -527,7 +533,7 @@ mod tests {
             minion.row as usize,
             minion.col as usize,
         );
-        let move_result = minion.movement_phase(&mut board);
+        let move_result = minion.movement_phase(&mut board, GameInstant::ZERO);
         assert!(
             move_result.is_ok(),
             "Unstunned minion should be able to move"
@@ -539,32 +545,39 @@ mod tests {
         );
 
         // Assert minion can now attack
-        minion.last_attacked = Instant::now() - minion.stats.attack_speed - Duration::from_secs(1);
+        let now = GameInstant::ZERO + minion.stats.attack_speed + Duration::from_secs(1);
         assert!(
-            minion.can_attack().is_some(),
+            minion.can_attack(now).is_some(),
             "Unstunned minion should be able to attack"
         );
     }
 
+    #[test]
     fn test_new_minion() {
         let minion_id = 1;
         let minion_stats = create_default_minion_stats();
 
         // Test Blue Team Minions
-        let blue_top_minion = Minion::new(minion_id, Team::Blue, Lane::Top, minion_stats.clone());
+        let blue_top_minion = Minion::new(
+            minion_id,
+            &lane_path(Team::Blue, Lane::Top),
+            minion_stats.clone(),
+        );
         assert_eq!(blue_top_minion.minion_id, minion_id);
         assert_eq!(blue_top_minion.team_id, Team::Blue);
-        assert_eq!(blue_top_minion.lane, Lane::Top);
         assert_eq!(blue_top_minion.stats.health, minion_stats.health); // Check a few stats fields
         assert_eq!(blue_top_minion.row, 184);
         assert_eq!(blue_top_minion.col, 10);
         assert_eq!(blue_top_minion.current_path, (120, 8));
 
         // Test Red Team Minions
-        let red_top_minion = Minion::new(minion_id, Team::Red, Lane::Top, minion_stats.clone());
+        let red_top_minion = Minion::new(
+            minion_id,
+            &lane_path(Team::Red, Lane::Top),
+            minion_stats.clone(),
+        );
         assert_eq!(red_top_minion.minion_id, minion_id);
         assert_eq!(red_top_minion.team_id, Team::Red);
-        assert_eq!(red_top_minion.lane, Lane::Top);
         assert_eq!(red_top_minion.stats.health, minion_stats.health);
         assert_eq!(red_top_minion.row, 10);
         assert_eq!(red_top_minion.col, 184);
@@ -581,7 +594,7 @@ mod tests {
 
         // Create a minion and place it on the board
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(minion_id, &lane_path(team_id, Lane::Mid), minion_stats);
         minion.row = initial_row; // Set initial position manually for testing
         minion.col = initial_col;
         let minion_content = CellContent::Minion(minion_id, team_id);
@@ -739,7 +752,7 @@ mod tests {
         let initial_col = 179;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Bottom, minion_stats);
+        let mut minion = Minion::new(minion_id, &lane_path(team_id, Lane::Bottom), minion_stats);
         minion.row = initial_row;
         minion.col = initial_col;
         minion.change_goal();
@@ -758,7 +771,7 @@ mod tests {
         let initial_col = 7;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Top, minion_stats);
+        let mut minion = Minion::new(minion_id, &lane_path(team_id, Lane::Top), minion_stats);
         minion.checkpoint = 2;
         minion.row = initial_row;
         minion.col = initial_col;
@@ -779,7 +792,7 @@ mod tests {
         let initial_col = 0;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(minion_id, &lane_path(team_id, Lane::Mid), minion_stats);
         minion.row = initial_row;
         minion.col = initial_col;
         let minion_content = CellContent::Minion(minion_id, team_id);
@@ -857,7 +870,11 @@ mod tests {
         // Add tests for moving out of bounds from other edges/corners similarly...
         // Test moving down from row 9
         let minion_stats_bottom = create_default_minion_stats();
-        let mut minion_bottom = Minion::new(minion_id + 1, team_id, Lane::Mid, minion_stats_bottom);
+        let mut minion_bottom = Minion::new(
+            minion_id + 1,
+            &lane_path(team_id, Lane::Mid),
+            minion_stats_bottom,
+        );
         let initial_row_bottom = 9;
         let initial_col_bottom = 5;
         minion_bottom.row = initial_row_bottom;
@@ -901,7 +918,7 @@ mod tests {
         let initial_col = 5;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(minion_id, &lane_path(team_id, Lane::Mid), minion_stats);
         minion.row = initial_row;
         minion.col = initial_col;
         let minion_content = CellContent::Minion(minion_id, team_id);
@@ -1020,7 +1037,7 @@ mod tests {
         let goal_col1 = 20;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion1 = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion1 = Minion::new(minion_id, &lane_path(team_id, Lane::Mid), minion_stats);
         minion1.row = initial_row1;
         minion1.col = initial_col1;
         minion1.current_path = (goal_row1, goal_col1); // Set the goal
@@ -1043,7 +1060,7 @@ mod tests {
         );
 
         // Call minion_turn (assuming updated signature fn minion_turn(&mut self, board: &mut Board))
-        minion1.movement_phase(&mut board1);
+        let _ = minion1.movement_phase(&mut board1, GameInstant::ZERO);
 
         // Assert the minion's position
         assert_eq!(
@@ -1080,7 +1097,7 @@ mod tests {
         let goal_col2 = 40;
 
         let minion_stats = create_default_minion_stats();
-        let mut minion2 = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion2 = Minion::new(minion_id, &lane_path(team_id, Lane::Mid), minion_stats);
         minion2.row = initial_row2;
         minion2.col = initial_col2;
         minion2.current_path = (goal_row2, goal_col2); // Set the goal
@@ -1103,7 +1120,7 @@ mod tests {
         );
 
         // Call minion_turn
-        minion2.movement_phase(&mut board2);
+        let _ = minion2.movement_phase(&mut board2, GameInstant::ZERO);
 
         // Assert the minion's position
         assert_eq!(
@@ -1140,7 +1157,7 @@ mod tests {
         let goal_col3 = 100; // Same column
 
         let minion_stats = create_default_minion_stats();
-        let mut minion3 = Minion::new(minion_id, team_id, Lane::Mid, minion_stats);
+        let mut minion3 = Minion::new(minion_id, &lane_path(team_id, Lane::Mid), minion_stats);
         minion3.row = initial_row3;
         minion3.col = initial_col3;
         minion3.current_path = (goal_row3, goal_col3); // Set the goal
@@ -1163,7 +1180,7 @@ mod tests {
         );
 
         // Call minion_turn
-        minion3.movement_phase(&mut board3);
+        let _ = minion3.movement_phase(&mut board3, GameInstant::ZERO);
 
         // Assert the minion's position
         assert_eq!(
@@ -1201,7 +1218,7 @@ mod tests {
         let minion_row = 25; // Center minion on a large board
         let minion_col = 25;
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, minion_team, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(minion_id, &lane_path(minion_team, Lane::Mid), minion_stats);
         minion.row = minion_row;
         minion.col = minion_col;
 
@@ -1249,7 +1266,7 @@ mod tests {
         let minion_row = 25; // Center minion
         let minion_col = 25;
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, minion_team, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(minion_id, &lane_path(minion_team, Lane::Mid), minion_stats);
         minion.row = minion_row;
         minion.col = minion_col;
 
@@ -1348,7 +1365,7 @@ mod tests {
         let minion_row = 25; // Center minion
         let minion_col = 25;
         let minion_stats = create_default_minion_stats();
-        let mut minion = Minion::new(minion_id, minion_team, Lane::Mid, minion_stats);
+        let mut minion = Minion::new(minion_id, &lane_path(minion_team, Lane::Mid), minion_stats);
         minion.row = minion_row;
         minion.col = minion_col;
 
@@ -1449,7 +1466,11 @@ mod tests {
         let minion_row = 25; // Center minion
         let minion_col = 25;
         let minion_stats = create_default_minion_stats(); // aggro_range_row: 10, aggro_range_col: 10
-        let mut minion = Minion::new(minion_id, minion_team, Lane::Mid, minion_stats.clone()); // Use clone to avoid moving minion_stats
+        let mut minion = Minion::new(
+            minion_id,
+            &lane_path(minion_team, Lane::Mid),
+            minion_stats.clone(),
+        ); // Use clone to avoid moving minion_stats
 
         minion.row = minion_row;
         minion.col = minion_col;
@@ -1488,4 +1509,23 @@ mod tests {
             "Should return None when enemies are outside the specified range (col)"
         );
     }
+
+    #[test]
+    fn test_rescale_keeps_health_ratio() {
+        let mut minion = Minion::new(
+            1,
+            &lane_path(Team::Blue, Lane::Top),
+            create_default_minion_stats(),
+        );
+        minion.stats.health = 10;
+
+        let mut reloaded = create_default_minion_stats();
+        reloaded.health = 80;
+        reloaded.attack_damage = 12;
+        minion.rescale(reloaded);
+
+        assert_eq!(minion.get_max_health(), 80);
+        assert_eq!(minion.get_health(), 20);
+        assert_eq!(minion.stats.attack_damage, 12);
+    }
 }
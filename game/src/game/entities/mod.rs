@@ -4,6 +4,7 @@ use projectile::GameplayEffect;
 
 use super::{
     Board, Cell, MinionId, PlayerId, TowerId, animation::AnimationTrait, cell::CellAnimation,
+    clock::GameInstant,
 };
 use crate::game::cell::Team;
 
@@ -47,9 +48,28 @@ pub struct Stats {
     armor: u16,
 }
 
+impl Stats {
+    /// Swaps in new base values, keeping the current health ratio.
+    pub fn rescale(
+        &mut self,
+        max_health: u16,
+        attack_damage: u16,
+        attack_speed: Duration,
+        armor: u16,
+    ) {
+        if self.max_health > 0 {
+            self.health = (self.health as u32 * max_health as u32 / self.max_health as u32) as u16;
+        }
+        self.max_health = max_health;
+        self.attack_damage = attack_damage;
+        self.attack_speed = attack_speed;
+        self.armor = armor;
+    }
+}
+
 pub trait Fighter {
-    fn take_effect(&mut self, effects: Vec<GameplayEffect>);
-    fn can_attack(&mut self) -> Option<AttackAction>;
+    fn take_effect(&mut self, effects: Vec<GameplayEffect>, now: GameInstant);
+    fn can_attack(&mut self, now: GameInstant) -> Option<AttackAction>;
     fn get_potential_target<'a>(&self, board: &'a Board) -> Option<&'a Cell>;
 }
 
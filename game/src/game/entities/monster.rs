@@ -1,13 +1,14 @@
 use std::{
-    collections::{HashMap, VecDeque},
-    time::{Duration, Instant},
+    collections::{BTreeMap, VecDeque},
+    time::Duration,
 };
 
 use crate::{
     config::MonsterStats,
     game::{
         Board, PlayerId, algorithms::pathfinding::find_path_on_board,
-        animation::melee::MeleeAnimation, buffs::Buff, cell::MonsterId, entities::AttackAction,
+        animation::melee::MeleeAnimation, buffs::Buff, cell::MonsterId, clock::GameInstant,
+        entities::AttackAction,
     },
 };
 
@@ -29,11 +30,10 @@ pub struct Monster {
     pub target_champion_id: Option<PlayerId>,
     pub path: Option<VecDeque<(u16, u16)>>,
     pub stats: Stats,
-    pub last_attacked: Instant,
-    stun_timer: Option<Instant>,
-    pub active_buffs: HashMap<String, Box<dyn Buff>>,
+    pub last_attacked: GameInstant,
+    pub active_buffs: BTreeMap<String, Box<dyn Buff>>,
     pub respawn_timer: Duration,
-    pub death_time: Option<Instant>,
+    pub death_time: Option<GameInstant>,
     pub row: u16,
     pub col: u16,
     pub spawn_row: u16,
@@ -60,9 +60,8 @@ impl Monster {
             target_champion_id: None,
             path: None,
             stats,
-            last_attacked: Instant::now(),
-            stun_timer: None,
-            active_buffs: HashMap::new(),
+            last_attacked: GameInstant::ZERO,
+            active_buffs: BTreeMap::new(),
             respawn_timer: Duration::from_secs(monster_stats.respawn_timer_secs as u64),
             death_time: None,
             row: monster_stats.spawn_row,
@@ -73,6 +72,18 @@ impl Monster {
         }
     }
 
+    /// Applies reloaded balance values to this live monster.
+    pub fn rescale(&mut self, monster_stats: &MonsterStats) {
+        self.stats.rescale(
+            monster_stats.health,
+            monster_stats.attack_damage,
+            Duration::from_millis(monster_stats.attack_speed_ms),
+            monster_stats.armor,
+        );
+        self.respawn_timer = Duration::from_secs(monster_stats.respawn_timer_secs as u64);
+        self.leash_range = monster_stats.leash_range;
+    }
+
     pub fn attach_target(&mut self, player_id: PlayerId) {
         // A dead monster cannot aggro or acquire a target
         if self.state == MonsterState::Dead {
@@ -105,31 +116,27 @@ impl Monster {
         self.path = None;
     }
 
-    pub fn can_respawn(&self) -> bool {
+    pub fn can_respawn(&self, now: GameInstant) -> bool {
         if let Some(death_timer) = self.death_time {
-            if death_timer.elapsed() > self.respawn_timer {
-                return true;
-            } else {
-                return false;
-            }
+            now.duration_since(death_timer) > self.respawn_timer
         } else {
             // TODO: We need to return an error here, can timer should always be set.
-            return false;
+            false
         }
     }
 }
 
 impl Fighter for Monster {
-    fn take_effect(&mut self, effects: Vec<GameplayEffect>) {
+    fn take_effect(&mut self, effects: Vec<GameplayEffect>, now: GameInstant) {
         for effect in effects.into_iter() {
             match effect {
                 GameplayEffect::Damage(damage) => {
                     let reduced_damage = reduced_damage(damage, self.stats.armor);
-                    self.stats.health = self.stats.health.saturating_sub(reduced_damage as u16);
+                    self.stats.health = self.stats.health.saturating_sub(reduced_damage);
                     if self.stats.health == 0 {
                         self.state = MonsterState::Dead;
                         self.target_champion_id = None;
-                        self.death_time = Some(Instant::now());
+                        self.death_time = Some(now);
                     }
                 }
                 GameplayEffect::Heal(..) => {}
@@ -138,9 +145,9 @@ impl Fighter for Monster {
         }
     }
 
-    fn can_attack(&mut self) -> Option<super::AttackAction> {
-        if self.last_attacked + self.stats.attack_speed < Instant::now() {
-            self.last_attacked = Instant::now();
+    fn can_attack(&mut self, now: GameInstant) -> Option<super::AttackAction> {
+        if self.last_attacked + self.stats.attack_speed < now {
+            self.last_attacked = now;
             let animation = MeleeAnimation::new(self.id);
             Some(AttackAction::Melee {
                 damage: self.stats.attack_damage,
@@ -206,8 +213,7 @@ mod tests {
         assert_eq!(monster.state, MonsterState::Idle);
         assert!(monster.target_champion_id.is_none());
 
-        // Check that last_attack_time is recent, allowing for a small delay
-        assert!(monster.last_attacked.elapsed() < Duration::from_secs(1));
+        assert_eq!(monster.last_attacked, GameInstant::ZERO);
     }
 
     #[test]
@@ -215,7 +221,7 @@ mod tests {
         let monster_def = create_test_monster_def();
         let mut monster = Monster::new(1, monster_def);
 
-        monster.take_effect(vec![GameplayEffect::Damage(40)]);
+        monster.take_effect(vec![GameplayEffect::Damage(40)], GameInstant::ZERO);
 
         assert_eq!(monster.stats.health, 60);
         // State should NOT change, as per the new design
@@ -246,12 +252,13 @@ mod tests {
         assert_eq!(monster.state, MonsterState::Aggro);
 
         // Apply lethal damage (more than its health)
-        monster.take_effect(vec![GameplayEffect::Damage(150)]);
+        let now = GameInstant::from_millis(1000);
+        monster.take_effect(vec![GameplayEffect::Damage(150)], now);
 
         // Verify the monster is dead
         assert_eq!(monster.stats.health, 0);
         assert_eq!(monster.state, MonsterState::Dead);
-        assert!(monster.death_time.is_some(), "death_time should be set");
+        assert_eq!(monster.death_time, Some(now), "death_time should be set");
 
         // Verify the target is cleared upon death
         assert!(
@@ -265,11 +272,11 @@ mod tests {
         let monster_def = create_test_monster_def();
         let mut monster = Monster::new(1, monster_def);
 
-        // 1. Manually expire the cooldown.
+        // 1. Move time past the cooldown.
         let cooldown = monster.stats.attack_speed;
-        monster.last_attacked = Instant::now() - (cooldown + Duration::from_millis(100));
+        let now = GameInstant::ZERO + cooldown + Duration::from_millis(100);
 
-        let attack_action = monster.can_attack();
+        let attack_action = monster.can_attack(now);
         assert!(
             attack_action.is_some(),
             "Should be able to attack after cooldown"
@@ -328,7 +335,7 @@ mod tests {
         let board = Board::new(20, 20);
 
         // Damage the monster and make it return
-        monster.take_effect(vec![GameplayEffect::Damage(50)]);
+        monster.take_effect(vec![GameplayEffect::Damage(50)], GameInstant::ZERO);
         monster.start_returning(&board);
         assert_eq!(monster.stats.health, 50);
         assert_eq!(monster.state, MonsterState::Returning);
@@ -353,18 +360,22 @@ mod tests {
 
         // Kill the monster
         monster.state = MonsterState::Dead;
-        monster.death_time = Some(Instant::now());
+        let death_time = GameInstant::from_millis(1000);
+        monster.death_time = Some(death_time);
 
         // Immediately after death, it should not be able to respawn
-        assert!(!monster.can_respawn(), "Should not respawn immediately");
+        assert!(
+            !monster.can_respawn(death_time),
+            "Should not respawn immediately"
+        );
 
-        // Manually set the death time to be far in the past
+        // Move time past the respawn timer
         let respawn_duration = monster.respawn_timer;
-        monster.death_time = Some(Instant::now() - respawn_duration - Duration::from_secs(1));
+        let now = death_time + respawn_duration + Duration::from_secs(1);
 
         // Now it should be able to respawn
         assert!(
-            monster.can_respawn(),
+            monster.can_respawn(now),
             "Should be able to respawn after timer expires"
         );
     }
@@ -2,7 +2,7 @@ use crate::game::{
     algorithms::bresenham::Bresenham,
     animation::{AnimationCommand, AnimationTrait},
     buffs::Buff,
-    cell::{CellAnimation, Team},
+    cell::{CellAnimation, PlayerId, Team},
 };
 
 use super::Target;
@@ -54,6 +54,8 @@ pub struct Projectile {
     pub id: u64,
     pub team_id: Team,
     pub owner_id: u64,
+    // Champion credited with the hit, towers shoot for nobody
+    pub attacker: Option<PlayerId>,
     // Path and Movement
     pub current_position: (u16, u16),
     pub pathing: PathingLogic,
@@ -67,6 +69,7 @@ pub struct Projectile {
 }
 
 impl Projectile {
+    #[allow(clippy::too_many_arguments)]
     pub fn from_skillshot(
         id: u64,
         owner_id: u64,
@@ -85,6 +88,7 @@ impl Projectile {
         Projectile {
             id,
             owner_id,
+            attacker: None,
             team_id,
             current_position: start_pos,
             pathing,
@@ -95,6 +99,7 @@ impl Projectile {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn from_homing_shot(
         id: u64,
         owner_id: u64,
@@ -109,6 +114,7 @@ impl Projectile {
         Projectile {
             id,
             owner_id,
+            attacker: None,
             team_id,
             current_position: start_pos,
             pathing,
@@ -1,11 +1,9 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-use rand::seq::IndexedRandom;
-
-use crate::errors::GameError;
 use crate::game::BaseTerrain;
 use crate::game::board::Board;
 use crate::game::cell::{Cell, CellAnimation, CellContent, Team, TowerId};
+use crate::game::clock::GameInstant;
 use crate::game::entities::reduced_damage;
 
 use super::projectile::GameplayEffect;
@@ -18,7 +16,7 @@ pub struct Tower {
     stats: Stats,
     tower_stats: TowerStats,
     destroyed: bool,
-    last_attacked: Instant,
+    last_attacked: GameInstant,
     pub row: u16,
     pub col: u16,
 }
@@ -47,12 +45,23 @@ impl Tower {
             },
             tower_stats,
             destroyed: false,
-            last_attacked: Instant::now(),
+            last_attacked: GameInstant::ZERO,
             row,
             col,
         }
     }
 
+    /// Applies reloaded balance values to this live tower.
+    pub fn rescale(&mut self, tower_stats: TowerStats) {
+        self.stats.rescale(
+            tower_stats.health,
+            tower_stats.attack_damage,
+            Duration::from_secs(tower_stats.attack_speed_secs),
+            tower_stats.armor,
+        );
+        self.tower_stats = tower_stats;
+    }
+
     pub fn place_tower(&self, board: &mut Board) {
         board.place_cell(
             CellContent::Tower(self.tower_id, self.team_id),
@@ -101,25 +110,22 @@ impl Tower {
 }
 
 impl Fighter for Tower {
-    fn take_effect(&mut self, effects: Vec<GameplayEffect>) {
+    fn take_effect(&mut self, effects: Vec<GameplayEffect>, _now: GameInstant) {
         for effect in effects.into_iter() {
-            match effect {
-                GameplayEffect::Damage(damage) => {
-                    let reduced_damage = reduced_damage(damage, self.stats.armor);
-                    self.stats.health = self.stats.health.saturating_sub(reduced_damage as u16);
-                    if self.stats.health == 0 {
-                        self.destroyed = true;
-                    }
+            // Tower cannot be affected by buff or debuff
+            if let GameplayEffect::Damage(damage) = effect {
+                let reduced_damage = reduced_damage(damage, self.stats.armor);
+                self.stats.health = self.stats.health.saturating_sub(reduced_damage);
+                if self.stats.health == 0 {
+                    self.destroyed = true;
                 }
-                // Tower cannot be affected by buff or debuff
-                _ => {}
             }
         }
     }
 
-    fn can_attack(&mut self) -> Option<AttackAction> {
-        if self.last_attacked + self.stats.attack_speed < Instant::now() {
-            self.last_attacked = Instant::now();
+    fn can_attack(&mut self, now: GameInstant) -> Option<AttackAction> {
+        if self.last_attacked + self.stats.attack_speed < now {
+            self.last_attacked = now;
             Some(AttackAction::Projectile {
                 damage: self.stats.attack_damage,
                 speed: 1,
@@ -172,16 +178,6 @@ impl Fighter for Tower {
     }
 }
 
-pub fn generate_tower_id() -> Result<TowerId, GameError> {
-    let mut rng = rand::rng();
-    let nums: Vec<usize> = (1..99999).collect();
-    if let Some(id) = nums.choose(&mut rng) {
-        Ok(*id)
-    } else {
-        Err(GameError::GenerateIdError)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,7 +221,6 @@ mod tests {
             !tower.destroyed,
             "Newly created tower should not be destroyed"
         );
-        // last_attacked will be Instant::now(), difficult to assert exact value
     }
 
     #[test]
@@ -248,9 +243,9 @@ mod tests {
         let mut tower = Tower::new(1, Team::Red, 10, 20, tower_stats);
         let initial_health = tower.stats.health;
         let damage = 50;
-        let armor = tower.stats.armor as u16;
+        let armor = tower.stats.armor;
 
-        tower.take_effect(vec![GameplayEffect::Damage(damage)]);
+        tower.take_effect(vec![GameplayEffect::Damage(damage)], GameInstant::ZERO);
 
         // Calculate expected health after damage reduction by armor
         let reduced_damage = reduced_damage(damage, armor);
@@ -268,7 +263,10 @@ mod tests {
         let mut tower_to_destroy = Tower::new(2, Team::Red, 10, 20, create_default_tower_stats());
         let lethal_damage = 500; // Damage exceeding health + armor
 
-        tower_to_destroy.take_effect(vec![GameplayEffect::Damage(lethal_damage)]);
+        tower_to_destroy.take_effect(
+            vec![GameplayEffect::Damage(lethal_damage)],
+            GameInstant::ZERO,
+        );
 
         assert_eq!(
             tower_to_destroy.stats.health, 0,
@@ -286,7 +284,10 @@ mod tests {
         tower_already_destroyed.destroyed = true;
         let additional_damage = 10;
 
-        tower_already_destroyed.take_effect(vec![GameplayEffect::Damage(additional_damage)]);
+        tower_already_destroyed.take_effect(
+            vec![GameplayEffect::Damage(additional_damage)],
+            GameInstant::ZERO,
+        );
         assert_eq!(
             tower_already_destroyed.stats.health, 0,
             "Health should remain at 0 if already destroyed"
@@ -320,7 +321,7 @@ mod tests {
         for (r, c) in &placed_cells {
             let cell = board
                 .get_cell(*r as usize, *c as usize)
-                .expect(&format!("Cell at ({}, {}) should exist", r, c));
+                .unwrap_or_else(|| panic!("Cell at ({}, {}) should exist", r, c));
             assert_eq!(
                 cell.content,
                 Some(tower_content.clone()),
@@ -359,10 +360,9 @@ mod tests {
         for (r, c) in &outside_cells {
             // Check boundaries before getting cell
             if *r < board.rows as u16 && *c < board.cols as u16 {
-                let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                    "Cell at ({}, {}) should exist for outside check",
-                    r, c
-                ));
+                let cell = board.get_cell(*r as usize, *c as usize).unwrap_or_else(|| {
+                    panic!("Cell at ({}, {}) should exist for outside check", r, c)
+                });
                 assert!(
                     cell.content.is_none() || cell.content != Some(tower_content.clone()),
                     "Cell at ({}, {}) should not have tower content",
@@ -397,10 +397,9 @@ mod tests {
             (row, col + 1),
         ];
         for (r, c) in &placed_cells {
-            let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                "Cell at ({}, {}) should exist before destroying",
-                r, c
-            ));
+            let cell = board
+                .get_cell(*r as usize, *c as usize)
+                .unwrap_or_else(|| panic!("Cell at ({}, {}) should exist before destroying", r, c));
             assert_eq!(
                 cell.content,
                 Some(tower_content.clone()),
@@ -424,10 +423,12 @@ mod tests {
             (row, col + 1),
         ];
         for (r, c) in &cleared_content_cells {
-            let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                "Cell at ({}, {}) should exist after destroying for content check",
-                r, c
-            ));
+            let cell = board.get_cell(*r as usize, *c as usize).unwrap_or_else(|| {
+                panic!(
+                    "Cell at ({}, {}) should exist after destroying for content check",
+                    r, c
+                )
+            });
             assert!(
                 cell.content.is_none(),
                 "Cell at ({}, {}) should have no content after destroying",
@@ -438,10 +439,12 @@ mod tests {
 
         let destroyed_base_cells = [(row, col), (row, col + 1)];
         for (r, c) in &destroyed_base_cells {
-            let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                "Cell at ({}, {}) should exist after destroying for base check",
-                r, c
-            ));
+            let cell = board.get_cell(*r as usize, *c as usize).unwrap_or_else(|| {
+                panic!(
+                    "Cell at ({}, {}) should exist after destroying for base check",
+                    r, c
+                )
+            });
             assert_eq!(
                 cell.base,
                 BaseTerrain::TowerDestroyed,
@@ -454,10 +457,12 @@ mod tests {
         // Verify the other two cells in the 2x2 area still have their original base (Floor)
         let original_base_cells = [(row - 1, col), (row - 1, col + 1)];
         for (r, c) in &original_base_cells {
-            let cell = board.get_cell(*r as usize, *c as usize).expect(&format!(
-                "Cell at ({}, {}) should exist after destroying for original base check",
-                r, c
-            ));
+            let cell = board.get_cell(*r as usize, *c as usize).unwrap_or_else(|| {
+                panic!(
+                    "Cell at ({}, {}) should exist after destroying for original base check",
+                    r, c
+                )
+            });
             assert_eq!(
                 cell.base,
                 BaseTerrain::Floor,
@@ -0,0 +1,201 @@
+use std::collections::VecDeque;
+
+/// Frames kept while waiting for an ack, older ones force a keyframe.
+pub const MAX_UNACKED_FRAMES: usize = 64;
+
+/// A cell of the view whose encoded value differs from the base frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellChange {
+    pub row: u8,
+    pub col: u8,
+    pub value: u8,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FrameUpdate {
+    /// The whole view has to be sent
+    Keyframe,
+    /// Only the cells that changed since the frame `base_tick`
+    Delta {
+        base_tick: u32,
+        changes: Vec<CellChange>,
+    },
+}
+
+/// Board views sent to one client, so the next update only carries the cells that changed
+/// since the last frame the client acknowledged.
+#[derive(Debug, Default)]
+pub struct FrameSync {
+    // Sent frames from the acknowledged one onward, oldest first
+    sent: VecDeque<(u32, Vec<u8>)>,
+    acked: Option<u32>,
+    since_keyframe: u32,
+}
+
+impl FrameSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that the client applied frame `tick`, returns false for stale or unknown ticks.
+    pub fn ack(&mut self, tick: u32) -> bool {
+        if self.acked.is_some_and(|acked| tick <= acked)
+            || !self.sent.iter().any(|(sent, _)| *sent == tick)
+        {
+            return false;
+        }
+        while self.sent.front().is_some_and(|(sent, _)| *sent < tick) {
+            self.sent.pop_front();
+        }
+        self.acked = Some(tick);
+        true
+    }
+
+    /// View of the most recent frame.
+    pub fn last_view(&self) -> Option<&[u8]> {
+        self.sent.back().map(|(_, view)| view.as_slice())
+    }
+
+    /// Decides how to send the view of frame `tick` and remembers it as a future base.
+    pub fn next_frame(
+        &mut self,
+        tick: u32,
+        view: Vec<u8>,
+        view_width: usize,
+        keyframe_interval: u32,
+    ) -> FrameUpdate {
+        let base = self
+            .acked
+            .and_then(|acked| self.sent.iter().find(|(sent, _)| *sent == acked));
+        let update = match base {
+            Some((base_tick, base_view))
+                if self.since_keyframe < keyframe_interval && base_view.len() == view.len() =>
+            {
+                let changes = diff_views(base_view, &view, view_width);
+                // Past this size a keyframe costs about the same and resets the client
+                if changes.len() * 3 < view.len() {
+                    FrameUpdate::Delta {
+                        base_tick: *base_tick,
+                        changes,
+                    }
+                } else {
+                    FrameUpdate::Keyframe
+                }
+            }
+            _ => FrameUpdate::Keyframe,
+        };
+
+        match update {
+            FrameUpdate::Keyframe => self.since_keyframe = 0,
+            FrameUpdate::Delta { .. } => self.since_keyframe += 1,
+        }
+        self.sent.push_back((tick, view));
+        if self.sent.len() > MAX_UNACKED_FRAMES
+            && let Some((dropped, _)) = self.sent.pop_front()
+            && self.acked == Some(dropped)
+        {
+            self.acked = None;
+        }
+        update
+    }
+}
+
+fn diff_views(base: &[u8], view: &[u8], view_width: usize) -> Vec<CellChange> {
+    base.iter()
+        .zip(view)
+        .enumerate()
+        .filter(|(_, (old, new))| old != new)
+        .map(|(index, (_, new))| CellChange {
+            row: (index / view_width) as u8,
+            col: (index % view_width) as u8,
+            value: *new,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(changed: &[usize]) -> Vec<u8> {
+        let mut view = vec![1; 12];
+        for index in changed {
+            view[*index] = 4;
+        }
+        view
+    }
+
+    #[test]
+    fn test_keyframe_until_first_ack() {
+        let mut sync = FrameSync::new();
+        assert_eq!(sync.next_frame(1, view(&[]), 4, 10), FrameUpdate::Keyframe);
+        assert_eq!(sync.next_frame(2, view(&[]), 4, 10), FrameUpdate::Keyframe);
+    }
+
+    #[test]
+    fn test_delta_against_acked_frame() {
+        let mut sync = FrameSync::new();
+        sync.next_frame(1, view(&[]), 4, 10);
+        assert!(sync.ack(1));
+        sync.next_frame(2, view(&[5]), 4, 10);
+
+        // Frame 2 is not acked yet, so frame 3 is still relative to frame 1
+        assert_eq!(
+            sync.next_frame(3, view(&[6]), 4, 10),
+            FrameUpdate::Delta {
+                base_tick: 1,
+                changes: vec![CellChange {
+                    row: 1,
+                    col: 2,
+                    value: 4
+                }],
+            }
+        );
+        assert!(sync.ack(3));
+        assert!(!sync.ack(2));
+        assert_eq!(
+            sync.next_frame(4, view(&[6]), 4, 10),
+            FrameUpdate::Delta {
+                base_tick: 3,
+                changes: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn test_periodic_and_oversized_keyframes() {
+        let mut sync = FrameSync::new();
+        sync.next_frame(1, view(&[]), 4, 2);
+        sync.ack(1);
+        assert!(matches!(
+            sync.next_frame(2, view(&[]), 4, 2),
+            FrameUpdate::Delta { .. }
+        ));
+        assert!(matches!(
+            sync.next_frame(3, view(&[]), 4, 2),
+            FrameUpdate::Delta { .. }
+        ));
+        assert_eq!(sync.next_frame(4, view(&[]), 4, 2), FrameUpdate::Keyframe);
+
+        sync.ack(4);
+        assert_eq!(
+            sync.next_frame(5, view(&[0, 1, 2, 3, 4]), 4, 2),
+            FrameUpdate::Keyframe
+        );
+    }
+
+    #[test]
+    fn test_lagging_client_gets_keyframes() {
+        let mut sync = FrameSync::new();
+        sync.next_frame(1, view(&[]), 4, u32::MAX);
+        sync.ack(1);
+        for tick in 2..=MAX_UNACKED_FRAMES as u32 + 1 {
+            sync.next_frame(tick, view(&[]), 4, u32::MAX);
+        }
+        assert_eq!(
+            sync.next_frame(MAX_UNACKED_FRAMES as u32 + 2, view(&[]), 4, u32::MAX),
+            FrameUpdate::Keyframe
+        );
+        assert!(!sync.ack(1));
+    }
+}
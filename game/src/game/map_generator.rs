@@ -0,0 +1,396 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::errors::GameError;
+
+use super::Board;
+use super::board::BoardLayout;
+use super::cell::{BaseTerrain, Team};
+use super::entities::minion::Lane;
+use super::map_layout::{Camp, LanePath, MapLayout, PlayerSpawn, Structure, StructureKind};
+use super::map_lint::{check_reachability, place_structures};
+
+pub const MIN_SIZE: u16 = 40;
+pub const MAX_SIZE: u16 = 250;
+pub const MIN_LANE_WIDTH: u16 = 3;
+// Seeds tried, starting from the requested one, before giving up on a playable map
+const MAX_ATTEMPTS: u64 = 10;
+const SPAWNS_PER_TEAM: usize = 5;
+
+#[derive(Debug, Clone)]
+pub struct GeneratorParams {
+    pub seed: u64,
+    pub size: u16,
+    pub lane_width: u16,
+    pub bush_density: f64,
+    pub wall_complexity: f64,
+    /// Monster ids placed in each camp, Blue side first.
+    pub camps: Vec<(String, String)>,
+}
+
+type Pos = (usize, usize);
+
+/// Generates a 3-lane map, mirrored along the main diagonal so that
+/// the Blue half (below the diagonal) matches the Red half.
+pub fn generate(params: &GeneratorParams) -> Result<BoardLayout, GameError> {
+    check_params(params)?;
+    for attempt in 0..MAX_ATTEMPTS {
+        let seed = params.seed.wrapping_add(attempt);
+        if let Some(layout) = generate_with_seed(params, seed)
+            && is_playable(&layout)
+        {
+            return Ok(layout);
+        }
+    }
+    Err(GameError::InvalidInput(format!(
+        "no playable map found after {} seeds, try a lower wall complexity",
+        MAX_ATTEMPTS
+    )))
+}
+
+fn check_params(params: &GeneratorParams) -> Result<(), GameError> {
+    if !(MIN_SIZE..=MAX_SIZE).contains(&params.size) {
+        return Err(GameError::InvalidInput(format!(
+            "size must be between {} and {}",
+            MIN_SIZE, MAX_SIZE
+        )));
+    }
+    if params.lane_width < MIN_LANE_WIDTH || params.lane_width > params.size / 10 {
+        return Err(GameError::InvalidInput(format!(
+            "lane width must be between {} and {}",
+            MIN_LANE_WIDTH,
+            params.size / 10
+        )));
+    }
+    if !(0.0..=1.0).contains(&params.bush_density) {
+        return Err(GameError::InvalidInput(
+            "bush density must be between 0 and 1".to_string(),
+        ));
+    }
+    if !(0.0..=1.0).contains(&params.wall_complexity) {
+        return Err(GameError::InvalidInput(
+            "wall complexity must be between 0 and 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn transpose((row, col): Pos) -> Pos {
+    (col, row)
+}
+
+fn to_u16((row, col): Pos) -> (u16, u16) {
+    (row as u16, col as u16)
+}
+
+/// Cells from `from` to `to`, both included, for straight or 45 degree segments.
+fn segment(from: Pos, to: Pos) -> Vec<Pos> {
+    let mut cells = vec![from];
+    let (mut row, mut col) = (from.0 as isize, from.1 as isize);
+    let row_step = (to.0 as isize - row).signum();
+    let col_step = (to.1 as isize - col).signum();
+    while (row, col) != (to.0 as isize, to.1 as isize) {
+        if row != to.0 as isize {
+            row += row_step;
+        }
+        if col != to.1 as isize {
+            col += col_step;
+        }
+        cells.push((row as usize, col as usize));
+    }
+    cells
+}
+
+fn polyline(corners: &[Pos]) -> Vec<Pos> {
+    let mut cells = vec![corners[0]];
+    for pair in corners.windows(2) {
+        cells.extend(segment(pair[0], pair[1]).into_iter().skip(1));
+    }
+    cells
+}
+
+fn generate_with_seed(params: &GeneratorParams, seed: u64) -> Option<BoardLayout> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let n = params.size as usize;
+    let half_width = params.lane_width as usize / 2;
+    // Lanes run on these two tracks, one lane width away from the border
+    let near = params.lane_width as usize + 2;
+    let far = n - 1 - near;
+
+    let mut grid = vec![vec![BaseTerrain::Wall; n]; n];
+    let mut open_area = vec![vec![false; n]; n];
+
+    // Jungle between the lanes, broken up by wall clusters
+    for row in grid.iter_mut().take(far).skip(near + 1) {
+        for cell in row.iter_mut().take(far).skip(near + 1) {
+            *cell = BaseTerrain::Floor;
+        }
+    }
+    let interior = (far - near - 1) * (far - near - 1);
+    let clusters = (params.wall_complexity * interior as f64 / 40.0) as usize;
+    for _ in 0..clusters {
+        let mut row = rng.random_range(near + 1..far);
+        let mut col = rng.random_range(near + 1..far);
+        for _ in 0..rng.random_range(4..=12) {
+            if row >= col {
+                grid[row][col] = BaseTerrain::Wall;
+            }
+            row = (row as isize + rng.random_range(-1i32..=1) as isize)
+                .clamp(near as isize + 1, far as isize - 1) as usize;
+            col = (col as isize + rng.random_range(-1i32..=1) as isize)
+                .clamp(near as isize + 1, far as isize - 1) as usize;
+        }
+    }
+
+    // Blue lanes, from the Blue base in the bottom-left corner to the Red one
+    let lanes = [
+        (
+            Lane::Top,
+            polyline(&[(far - 3, near), (near, near), (near, far - 3)]),
+        ),
+        (
+            Lane::Mid,
+            polyline(&[(far - 3, near + 3), (near + 3, far - 3)]),
+        ),
+        (
+            Lane::Bottom,
+            polyline(&[(far, near + 3), (far, far), (near + 3, far)]),
+        ),
+    ];
+    let mut carve = |grid: &mut Vec<Vec<BaseTerrain>>, center: Pos, radius: usize| {
+        for row in center.0.saturating_sub(radius)..=(center.0 + radius).min(n - 2) {
+            for col in center.1.saturating_sub(radius)..=(center.1 + radius).min(n - 2) {
+                if row > 0 && col > 0 {
+                    grid[row][col] = BaseTerrain::Floor;
+                    open_area[row][col] = true;
+                }
+            }
+        }
+    };
+    for (_, path) in &lanes {
+        for &cell in path {
+            carve(&mut grid, cell, half_width);
+        }
+    }
+    // Base clearings
+    let blue_base_center = (far, near);
+    carve(&mut grid, blue_base_center, near - 1);
+    carve(&mut grid, transpose(blue_base_center), near - 1);
+
+    // Camps sit in the jungle on the Blue side, alternating between the two jungles
+    let mut camp_cells = Vec::new();
+    for i in 0..params.camps.len() {
+        let upper_jungle = i % 2 == 0;
+        let cell = (0..200).find_map(|_| {
+            let row = rng.random_range(near + 3..far - 2);
+            let col = rng.random_range(near + 3..far - 2);
+            let in_jungle = if upper_jungle {
+                row + col < n - 1
+            } else {
+                row + col > n - 1
+            };
+            let clear_of_lanes =
+                (row - 2..=row + 2).all(|r| (col - 2..=col + 2).all(|c| !open_area[r][c]));
+            (row > col + 3 && in_jungle && clear_of_lanes).then_some((row, col))
+        })?;
+        for row in cell.0 - 1..=cell.0 + 1 {
+            for col in cell.1 - 1..=cell.1 + 1 {
+                grid[row][col] = BaseTerrain::Floor;
+                open_area[row][col] = true;
+            }
+        }
+        camp_cells.push(cell);
+    }
+
+    // Bushes only grow in the jungle
+    for row in 1..n - 1 {
+        for col in 1..row {
+            if grid[row][col] == BaseTerrain::Floor
+                && !open_area[row][col]
+                && rng.random_bool(params.bush_density)
+            {
+                grid[row][col] = BaseTerrain::Bush;
+            }
+        }
+    }
+
+    // Mirror the Blue half onto the Red half
+    let grid: Vec<Vec<BaseTerrain>> = (0..n)
+        .map(|row| (0..n).map(|col| grid[row.max(col)][row.min(col)]).collect())
+        .collect();
+
+    let mut map_layout = MapLayout::default();
+    for (lane, path) in &lanes {
+        let len = path.len();
+        let blue_towers = [path[len / 5], path[2 * len / 5]];
+        // Red towers cover the transposed footprint of the Blue ones
+        let red_towers = blue_towers.map(|(row, col)| (col + 1, row - 1));
+        for (team, towers) in [(Team::Blue, blue_towers), (Team::Red, red_towers)] {
+            for tower in towers {
+                map_layout.structures.push(Structure {
+                    kind: StructureKind::Tower,
+                    team,
+                    row: tower.0 as u16,
+                    col: tower.1 as u16,
+                });
+            }
+        }
+
+        let end = path[len - 1];
+        let blue_waypoints = [
+            blue_towers[0],
+            blue_towers[1],
+            red_towers[1],
+            red_towers[0],
+            end,
+        ];
+        let red_waypoints = [
+            red_towers[0],
+            red_towers[1],
+            blue_towers[1],
+            blue_towers[0],
+            path[0],
+        ];
+        map_layout.lanes.push(LanePath {
+            team: Team::Blue,
+            lane: *lane,
+            start: to_u16(path[0]),
+            waypoints: blue_waypoints.iter().map(|&cell| to_u16(cell)).collect(),
+        });
+        map_layout.lanes.push(LanePath {
+            team: Team::Red,
+            lane: *lane,
+            start: to_u16(end),
+            waypoints: red_waypoints.iter().map(|&cell| to_u16(cell)).collect(),
+        });
+    }
+
+    // Bases are anchored on their top-left cell, one cell up and left of the center
+    for (team, center) in [
+        (Team::Blue, blue_base_center),
+        (Team::Red, transpose(blue_base_center)),
+    ] {
+        map_layout.structures.push(Structure {
+            kind: StructureKind::Base,
+            team,
+            row: center.0 as u16 - 1,
+            col: center.1 as u16 - 1,
+        });
+    }
+
+    for i in 0..SPAWNS_PER_TEAM {
+        let blue_spawn = (n - 2, near - 2 + i);
+        for (team, spawn) in [(Team::Blue, blue_spawn), (Team::Red, transpose(blue_spawn))] {
+            map_layout.spawns.push(PlayerSpawn {
+                team,
+                row: spawn.0 as u16,
+                col: spawn.1 as u16,
+            });
+        }
+    }
+
+    for ((blue_monster, red_monster), cell) in params.camps.iter().zip(camp_cells) {
+        for (monster, camp) in [(blue_monster, cell), (red_monster, transpose(cell))] {
+            map_layout.camps.push(Camp {
+                monster: monster.clone(),
+                row: camp.0 as u16,
+                col: camp.1 as u16,
+            });
+        }
+    }
+
+    let layout = grid
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|terrain| {
+                    match terrain {
+                        BaseTerrain::Wall => "wall",
+                        BaseTerrain::Bush => "bush",
+                        _ => "floor",
+                    }
+                    .to_string()
+                })
+                .collect()
+        })
+        .collect();
+
+    Some(BoardLayout {
+        rows: n,
+        cols: n,
+        layout,
+        map_layout,
+    })
+}
+
+/// Checks that minions can walk every lane and that players can reach every camp.
+fn is_playable(layout: &BoardLayout) -> bool {
+    let mut board = Board::from_layout(layout.clone(), &layout.map_layout.source);
+    place_structures(&mut board);
+    check_reachability(&board).is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_params(seed: u64) -> GeneratorParams {
+        GeneratorParams {
+            seed,
+            size: 100,
+            lane_width: 5,
+            bush_density: 0.2,
+            wall_complexity: 0.5,
+            camps: vec![
+                ("wolf_blue".to_string(), "wolf_red".to_string()),
+                ("golem_blue".to_string(), "golem_red".to_string()),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_generated_map_is_mirrored() {
+        let layout = generate(&default_params(7)).unwrap();
+        for row in 0..layout.rows {
+            for col in 0..layout.cols {
+                assert_eq!(layout.layout[row][col], layout.layout[col][row]);
+            }
+        }
+
+        let map_layout = &layout.map_layout;
+        assert_eq!(map_layout.structures.len(), 14);
+        assert_eq!(map_layout.lanes.len(), 6);
+        assert_eq!(map_layout.spawns_of(Team::Red).len(), SPAWNS_PER_TEAM);
+        assert_eq!(map_layout.camps.len(), 4);
+        let blue_base = map_layout.base_of(Team::Blue).unwrap();
+        let red_base = map_layout.base_of(Team::Red).unwrap();
+        assert_eq!((blue_base.row, blue_base.col), (red_base.col, red_base.row));
+    }
+
+    #[test]
+    fn test_generated_map_is_playable() {
+        for seed in 0..5 {
+            let layout = generate(&default_params(seed)).unwrap();
+            assert!(is_playable(&layout));
+        }
+    }
+
+    #[test]
+    fn test_same_seed_gives_same_map() {
+        let first = generate(&default_params(42)).unwrap();
+        let second = generate(&default_params(42)).unwrap();
+        assert_eq!(first.layout, second.layout);
+        assert_eq!(first.map_layout.camps, second.map_layout.camps);
+    }
+
+    #[test]
+    fn test_invalid_params_are_rejected() {
+        let mut params = default_params(1);
+        params.lane_width = 1;
+        assert!(generate(&params).is_err());
+
+        let mut params = default_params(1);
+        params.size = 1000;
+        assert!(generate(&params).is_err());
+    }
+}
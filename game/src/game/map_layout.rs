@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use super::cell::Team;
+use super::entities::minion::Lane;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StructureKind {
+    Tower,
+    Base,
+}
+
+/// A tower or base, anchored on its top-left cell for bases and its bottom-left cell for towers.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Structure {
+    pub kind: StructureKind,
+    pub team: Team,
+    pub row: u16,
+    pub col: u16,
+}
+
+impl Structure {
+    /// Cells covered by the structure once placed on the board.
+    pub fn footprint(&self) -> Vec<(u16, u16)> {
+        match self.kind {
+            StructureKind::Tower => vec![
+                (self.row, self.col),
+                (self.row.saturating_sub(1), self.col),
+                (self.row, self.col + 1),
+                (self.row.saturating_sub(1), self.col + 1),
+            ],
+            StructureKind::Base => (0..3)
+                .flat_map(|i| (0..3).map(move |j| (self.row + i, self.col + j)))
+                .collect(),
+        }
+    }
+}
+
+/// Path followed by one team's minions on one lane.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LanePath {
+    pub team: Team,
+    pub lane: Lane,
+    pub start: (u16, u16),
+    pub waypoints: Vec<(u16, u16)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PlayerSpawn {
+    pub team: Team,
+    pub row: u16,
+    pub col: u16,
+}
+
+/// Where a neutral monster from `stats.toml` lives on this map.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Camp {
+    pub monster: String,
+    pub row: u16,
+    pub col: u16,
+}
+
+/// Gameplay placements read from the map file alongside the terrain.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MapLayout {
+    #[serde(skip)]
+    pub source: String,
+    #[serde(default)]
+    pub structures: Vec<Structure>,
+    #[serde(default)]
+    pub lanes: Vec<LanePath>,
+    #[serde(default)]
+    pub spawns: Vec<PlayerSpawn>,
+    #[serde(default)]
+    pub camps: Vec<Camp>,
+}
+
+impl MapLayout {
+    pub fn structures_of(&self, kind: StructureKind) -> impl Iterator<Item = &Structure> {
+        self.structures
+            .iter()
+            .filter(move |structure| structure.kind == kind)
+    }
+
+    pub fn base_of(&self, team: Team) -> Option<&Structure> {
+        self.structures_of(StructureKind::Base)
+            .find(|structure| structure.team == team)
+    }
+
+    pub fn spawns_of(&self, team: Team) -> Vec<&PlayerSpawn> {
+        self.spawns
+            .iter()
+            .filter(|spawn| spawn.team == team)
+            .collect()
+    }
+
+    pub fn camp_of(&self, monster_id: &str) -> Option<&Camp> {
+        self.camps.iter().find(|camp| camp.monster == monster_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_map_layout() {
+        let content = r#"{
+            "structures": [
+                {"kind": "base", "team": "blue", "row": 190, "col": 10},
+                {"kind": "tower", "team": "red", "row": 150, "col": 196}
+            ],
+            "lanes": [
+                {"team": "blue", "lane": "top", "start": [184, 10], "waypoints": [[120, 8], [10, 184]]}
+            ],
+            "spawns": [{"team": "red", "row": 0, "col": 199}],
+            "camps": [{"monster": "wolf_blue", "row": 126, "col": 49}]
+        }"#;
+        let layout: MapLayout = serde_json::from_str(content).unwrap();
+
+        assert_eq!(layout.base_of(Team::Blue).unwrap().row, 190);
+        assert!(layout.base_of(Team::Red).is_none());
+        assert_eq!(layout.structures_of(StructureKind::Tower).count(), 1);
+        assert_eq!(layout.lanes[0].lane, Lane::Top);
+        assert_eq!(layout.lanes[0].waypoints, vec![(120, 8), (10, 184)]);
+        assert_eq!(layout.spawns_of(Team::Red).len(), 1);
+        assert!(layout.spawns_of(Team::Blue).is_empty());
+        assert_eq!(layout.camp_of("wolf_blue").unwrap().col, 49);
+    }
+
+    #[test]
+    fn test_sections_are_optional() {
+        let layout: MapLayout = serde_json::from_str("{}").unwrap();
+        assert!(layout.structures.is_empty());
+        assert!(layout.camps.is_empty());
+    }
+}
@@ -0,0 +1,435 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use super::Board;
+use super::algorithms::pathfinding::{get_valid_neighbors, is_reachable};
+use super::cell::{CellContent, EncodedCellValue, Team};
+use super::map_layout::{Structure, StructureKind};
+
+/// A problem found in a map file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    pub section: String,
+    pub message: String,
+}
+
+impl LintIssue {
+    fn new(section: impl Into<String>, message: impl Into<String>) -> Self {
+        LintIssue {
+            section: section.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.section, self.message)
+    }
+}
+
+/// Runs every check on a board freshly loaded from a map file.
+/// The structures are placed on the board along the way.
+pub fn lint(board: &mut Board) -> Vec<LintIssue> {
+    let mut issues = check_placements(board);
+    issues.extend(check_symmetry(board));
+    place_structures(board);
+    issues.extend(check_reachability(board));
+    issues
+}
+
+/// Occupies the cells of every structure, as the game does when a match starts.
+pub fn place_structures(board: &mut Board) {
+    let structures = board.map_layout.structures.clone();
+    for structure in structures {
+        let content = match structure.kind {
+            StructureKind::Tower => CellContent::Tower(0, structure.team),
+            StructureKind::Base => CellContent::Base(structure.team),
+        };
+        for (row, col) in structure.footprint() {
+            board.place_cell(content.clone(), row as usize, col as usize);
+        }
+    }
+}
+
+fn is_free(board: &Board, (row, col): (u16, u16)) -> bool {
+    board
+        .get_cell(row as usize, col as usize)
+        .is_some_and(|cell| cell.is_passable())
+}
+
+/// Structures, spawns and camps must stand on passable cells inside the board.
+pub fn check_placements(board: &Board) -> Vec<LintIssue> {
+    let map_layout = &board.map_layout;
+    let mut issues = Vec::new();
+    for (i, structure) in map_layout.structures.iter().enumerate() {
+        for cell in structure.footprint() {
+            if !is_free(board, cell) {
+                issues.push(LintIssue::new(
+                    format!("structures.{}", i),
+                    format!(
+                        "{:?} {:?} covers impassable cell {:?}",
+                        structure.team, structure.kind, cell
+                    ),
+                ));
+            }
+        }
+    }
+    for (i, spawn) in map_layout.spawns.iter().enumerate() {
+        if !is_free(board, (spawn.row, spawn.col)) {
+            issues.push(LintIssue::new(
+                format!("spawns.{}", i),
+                format!("spawn ({}, {}) is impassable", spawn.row, spawn.col),
+            ));
+        }
+    }
+    for (i, camp) in map_layout.camps.iter().enumerate() {
+        if !is_free(board, (camp.row, camp.col)) {
+            issues.push(LintIssue::new(
+                format!("camps.{}", i),
+                format!(
+                    "{} spawns on impassable cell ({}, {})",
+                    camp.monster, camp.row, camp.col
+                ),
+            ));
+        }
+    }
+    issues
+}
+
+/// Lane waypoints must be walkable in order, and camps reachable from their side's spawn.
+/// Expects the structures to be placed already.
+pub fn check_reachability(board: &Board) -> Vec<LintIssue> {
+    let map_layout = &board.map_layout;
+    let mut issues = Vec::new();
+    for (i, lane) in map_layout.lanes.iter().enumerate() {
+        let mut current = lane.start;
+        for &waypoint in &lane.waypoints {
+            if !is_reachable(board, current, waypoint) {
+                issues.push(LintIssue::new(
+                    format!("lanes.{}", i),
+                    format!(
+                        "{:?} {:?} lane cannot walk from {:?} to waypoint {:?}",
+                        lane.team, lane.lane, current, waypoint
+                    ),
+                ));
+            }
+            // Minions move on once they stand next to the waypoint
+            current = get_valid_neighbors(board, waypoint.0, waypoint.1)
+                .first()
+                .copied()
+                .unwrap_or(waypoint);
+        }
+    }
+    for (i, camp) in map_layout.camps.iter().enumerate() {
+        let team = half_of((camp.row, camp.col));
+        let Some(spawn) = map_layout.spawns_of(team).first().map(|s| (s.row, s.col)) else {
+            issues.push(LintIssue::new(
+                format!("camps.{}", i),
+                format!("no {:?} spawn to reach {} from", team, camp.monster),
+            ));
+            continue;
+        };
+        if !is_reachable(board, spawn, (camp.row, camp.col)) {
+            issues.push(LintIssue::new(
+                format!("camps.{}", i),
+                format!(
+                    "{} at ({}, {}) cannot be reached from the {:?} spawn",
+                    camp.monster, camp.row, camp.col, team
+                ),
+            ));
+        }
+    }
+    issues
+}
+
+// Maps are mirrored along the main diagonal, Blue owning the cells below it
+fn half_of((row, col): (u16, u16)) -> Team {
+    if row > col { Team::Blue } else { Team::Red }
+}
+
+fn transpose((row, col): (u16, u16)) -> (u16, u16) {
+    (col, row)
+}
+
+fn mirrored_footprint(structure: &Structure) -> Vec<(u16, u16)> {
+    let mut cells: Vec<_> = structure.footprint().into_iter().map(transpose).collect();
+    cells.sort();
+    cells
+}
+
+/// The Red half must mirror the Blue half along the main diagonal.
+pub fn check_symmetry(board: &Board) -> Vec<LintIssue> {
+    let map_layout = &board.map_layout;
+    let mut issues = Vec::new();
+
+    if board.rows != board.cols {
+        issues.push(LintIssue::new(
+            "layout",
+            format!(
+                "board is {}x{}, only square boards can be mirrored",
+                board.rows, board.cols
+            ),
+        ));
+    } else {
+        let mismatches: Vec<(usize, usize)> = (0..board.rows)
+            .flat_map(|row| (0..row).map(move |col| (row, col)))
+            .filter(|&(row, col)| {
+                board.get_cell(row, col).map(|cell| cell.base)
+                    != board.get_cell(col, row).map(|cell| cell.base)
+            })
+            .collect();
+        if let Some(first) = mismatches.first() {
+            issues.push(LintIssue::new(
+                "layout",
+                format!(
+                    "{} cells differ from their mirror, first at {:?}",
+                    mismatches.len(),
+                    first
+                ),
+            ));
+        }
+    }
+
+    for structure in map_layout
+        .structures
+        .iter()
+        .filter(|structure| structure.team == Team::Blue)
+    {
+        let mirrored = mirrored_footprint(structure);
+        let has_mirror = map_layout.structures.iter().any(|other| {
+            other.team == Team::Red && other.kind == structure.kind && {
+                let mut cells = other.footprint();
+                cells.sort();
+                cells == mirrored
+            }
+        });
+        if !has_mirror {
+            issues.push(LintIssue::new(
+                "structures",
+                format!(
+                    "Blue {:?} at ({}, {}) has no mirrored Red {:?}",
+                    structure.kind, structure.row, structure.col, structure.kind
+                ),
+            ));
+        }
+    }
+    for kind in [StructureKind::Tower, StructureKind::Base] {
+        let count = |team: Team| {
+            map_layout
+                .structures_of(kind)
+                .filter(|structure| structure.team == team)
+                .count()
+        };
+        if count(Team::Blue) != count(Team::Red) {
+            issues.push(LintIssue::new(
+                "structures",
+                format!(
+                    "Blue has {} {:?}, Red has {}",
+                    count(Team::Blue),
+                    kind,
+                    count(Team::Red)
+                ),
+            ));
+        }
+    }
+
+    let spawns = |team: Team| -> HashSet<(u16, u16)> {
+        map_layout
+            .spawns_of(team)
+            .iter()
+            .map(|spawn| (spawn.row, spawn.col))
+            .collect()
+    };
+    let mirrored_blue_spawns: HashSet<_> = spawns(Team::Blue).into_iter().map(transpose).collect();
+    if mirrored_blue_spawns != spawns(Team::Red) {
+        issues.push(LintIssue::new(
+            "spawns",
+            "Red spawns do not mirror Blue spawns",
+        ));
+    }
+
+    for blue_lane in map_layout
+        .lanes
+        .iter()
+        .filter(|lane| lane.team == Team::Blue)
+    {
+        let red_lane = map_layout
+            .lanes
+            .iter()
+            .find(|lane| lane.team == Team::Red && lane.lane == blue_lane.lane);
+        match red_lane {
+            None => issues.push(LintIssue::new(
+                "lanes",
+                format!("Red has no {:?} lane", blue_lane.lane),
+            )),
+            Some(red_lane) => {
+                if red_lane.start != transpose(blue_lane.start)
+                    || red_lane.waypoints.len() != blue_lane.waypoints.len()
+                {
+                    issues.push(LintIssue::new(
+                        "lanes",
+                        format!("Red {:?} lane does not mirror the Blue one", blue_lane.lane),
+                    ));
+                }
+            }
+        }
+    }
+
+    let camps = |team: Team| -> HashSet<(u16, u16)> {
+        map_layout
+            .camps
+            .iter()
+            .map(|camp| (camp.row, camp.col))
+            .filter(|&cell| half_of(cell) == team)
+            .collect()
+    };
+    let mirrored_blue_camps: HashSet<_> = camps(Team::Blue).into_iter().map(transpose).collect();
+    if mirrored_blue_camps != camps(Team::Red) {
+        issues.push(LintIssue::new(
+            "camps",
+            "Red camps do not mirror Blue camps",
+        ));
+    }
+
+    issues
+}
+
+fn preview_glyph(code: EncodedCellValue, colored: bool) -> &'static str {
+    // Background colors follow the client palette
+    match (code, colored) {
+        (EncodedCellValue::Wall, true) => "\x1b[100m \x1b[0m",
+        (EncodedCellValue::Bush, true) => "\x1b[42m \x1b[0m",
+        (EncodedCellValue::TowerDestroyed, true) => "\x1b[47m \x1b[0m",
+        (EncodedCellValue::Champion, true) => "\x1b[46m \x1b[0m",
+        (EncodedCellValue::Tower, true) => "\x1b[43m \x1b[0m",
+        (EncodedCellValue::BaseBlue, true) => "\x1b[44m \x1b[0m",
+        (EncodedCellValue::BaseRed, true) => "\x1b[41m \x1b[0m",
+        (EncodedCellValue::Monster, true) => "\x1b[45m \x1b[0m",
+        (EncodedCellValue::Wall, false) => "#",
+        (EncodedCellValue::Bush, false) => "\"",
+        (EncodedCellValue::TowerDestroyed, false) => "x",
+        (EncodedCellValue::Champion, false) => "@",
+        (EncodedCellValue::Tower, false) => "T",
+        (EncodedCellValue::BaseBlue, false) => "B",
+        (EncodedCellValue::BaseRed, false) => "R",
+        (EncodedCellValue::Monster, false) => "M",
+        _ => " ",
+    }
+}
+
+/// Terminal preview of the whole board, spawns drawn as champions and camps as monsters.
+pub fn render_preview(board: &Board, colored: bool) -> String {
+    let map_layout = &board.map_layout;
+    let spawns: HashSet<(u16, u16)> = map_layout
+        .spawns
+        .iter()
+        .map(|spawn| (spawn.row, spawn.col))
+        .collect();
+    let camps: HashSet<(u16, u16)> = map_layout
+        .camps
+        .iter()
+        .map(|camp| (camp.row, camp.col))
+        .collect();
+
+    let mut preview = String::new();
+    for row in 0..board.rows {
+        for col in 0..board.cols {
+            let position = (row as u16, col as u16);
+            let code = if camps.contains(&position) {
+                EncodedCellValue::Monster
+            } else if spawns.contains(&position) {
+                EncodedCellValue::Champion
+            } else if let Some(cell) = board.get_cell(row, col) {
+                EncodedCellValue::from(cell)
+            } else {
+                EncodedCellValue::Wall
+            };
+            preview.push_str(preview_glyph(code, colored));
+        }
+        preview.push('\n');
+    }
+    preview
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::BaseTerrain;
+    use crate::game::map_generator::{GeneratorParams, generate};
+
+    fn generated_board() -> Board {
+        let layout = generate(&GeneratorParams {
+            seed: 3,
+            size: 60,
+            lane_width: 4,
+            bush_density: 0.1,
+            wall_complexity: 0.3,
+            camps: vec![("wolf_blue".to_string(), "wolf_red".to_string())],
+        })
+        .unwrap();
+        Board::from_layout(layout, "generated.json")
+    }
+
+    #[test]
+    fn test_generated_map_is_clean() {
+        let mut board = generated_board();
+        assert_eq!(lint(&mut board), Vec::new());
+    }
+
+    #[test]
+    fn test_structure_on_wall_is_reported() {
+        let mut board = generated_board();
+        let tower = board
+            .map_layout
+            .structures_of(StructureKind::Tower)
+            .next()
+            .unwrap()
+            .clone();
+        board.change_base(BaseTerrain::Wall, tower.row as usize, tower.col as usize);
+
+        let issues = check_placements(&board);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("impassable"));
+        // The wall only exists on one side of the diagonal
+        assert!(
+            check_symmetry(&board)
+                .iter()
+                .any(|issue| issue.section == "layout")
+        );
+    }
+
+    #[test]
+    fn test_blocked_lane_is_reported() {
+        let mut board = generated_board();
+        let lane = board.map_layout.lanes[0].clone();
+        // Wall off the lane start
+        for (row, col) in get_valid_neighbors(&board, lane.start.0, lane.start.1) {
+            board.change_base(BaseTerrain::Wall, row as usize, col as usize);
+        }
+        place_structures(&mut board);
+
+        let issues = check_reachability(&board);
+        assert!(issues.iter().any(|issue| issue.section == "lanes.0"));
+    }
+
+    #[test]
+    fn test_moved_camp_breaks_symmetry() {
+        let mut board = generated_board();
+        board.map_layout.camps[0].col += 1;
+        let issues = check_symmetry(&board);
+        assert!(issues.iter().any(|issue| issue.section == "camps"));
+    }
+
+    #[test]
+    fn test_render_preview_uses_cell_codes() {
+        let board = generated_board();
+        let preview = render_preview(&board, false);
+        let lines: Vec<&str> = preview.lines().collect();
+        assert_eq!(lines.len(), board.rows);
+        assert!(lines.iter().all(|line| line.chars().count() == board.cols));
+        assert!(preview.contains('M'));
+        assert!(preview.contains('@'));
+        assert!(lines[0].chars().all(|c| c == '#'));
+    }
+}
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::errors::GameError;
+
+/// Map used when the server is started without `--map`.
+pub const DEFAULT_MAP_ID: u8 = 1;
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct MapEntry {
+    pub id: u8,
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MapRegistryFile {
+    map: Vec<MapEntry>,
+}
+
+/// Maps the ids sent by the room manager to map files on disk.
+#[derive(Debug, Clone)]
+pub struct MapRegistry {
+    maps: HashMap<u8, MapEntry>,
+}
+
+impl MapRegistry {
+    pub fn load(registry_path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(registry_path)?;
+        Ok(Self::from_toml(&content)?)
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, GameError> {
+        let registry_file: MapRegistryFile =
+            toml::from_str(content).map_err(|e| GameError::InvalidInput(e.to_string()))?;
+
+        let mut maps = HashMap::new();
+        for entry in registry_file.map {
+            if maps.contains_key(&entry.id) {
+                return Err(GameError::InvalidInput(format!(
+                    "duplicate map id {}",
+                    entry.id
+                )));
+            }
+            maps.insert(entry.id, entry);
+        }
+        Ok(MapRegistry { maps })
+    }
+
+    pub fn get(&self, map_id: u8) -> Result<&MapEntry, GameError> {
+        self.maps
+            .get(&map_id)
+            .ok_or_else(|| GameError::InvalidInput(format!("unknown map id {}", map_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+    use crate::game::board::Board;
+    use std::path::Path;
+
+    const REGISTRY: &str = r#"
+        [[map]]
+        id = 1
+        name = "Classic"
+        path = "game/assets/map.json"
+
+        [[map]]
+        id = 2
+        name = "Small"
+        path = "game/assets/small.json"
+    "#;
+
+    #[test]
+    fn test_get_map_by_id() {
+        let registry = MapRegistry::from_toml(REGISTRY).unwrap();
+        assert_eq!(registry.get(2).unwrap().path, "game/assets/small.json");
+        assert_eq!(registry.get(1).unwrap().name, "Classic");
+        assert!(registry.get(3).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_map_id_is_rejected() {
+        let content = format!(
+            "{}\n[[map]]\nid = 1\nname = \"Copy\"\npath = \"x.json\"\n",
+            REGISTRY
+        );
+        assert!(MapRegistry::from_toml(&content).is_err());
+    }
+
+    #[test]
+    fn test_repo_registry_maps_are_valid() {
+        let registry = MapRegistry::load("maps.toml").unwrap();
+        assert!(registry.get(DEFAULT_MAP_ID).is_ok());
+        let config =
+            GameConfig::load("stats.toml", "spells.toml", "items.toml", "rules.toml").unwrap();
+        for entry in registry.maps.values() {
+            // Registry paths are relative to the repository root
+            let path = Path::new("..").join(&entry.path);
+            let board = Board::from_json(path.to_str().unwrap())
+                .unwrap_or_else(|e| panic!("map {} ({}): {}", entry.id, entry.path, e));
+            assert_eq!(config.validate(&board), Ok(()), "map {}", entry.id);
+        }
+    }
+}
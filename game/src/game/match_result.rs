@@ -0,0 +1,139 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::Champion;
+use super::cell::{PlayerId, Team};
+
+/// Exit status of the server when Blue destroys the Red base.
+pub const EXIT_BLUE_WIN: i32 = 10;
+/// Exit status of the server when Red destroys the Blue base.
+pub const EXIT_RED_WIN: i32 = 11;
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PlayerResult {
+    pub player_id: PlayerId,
+    pub username: String,
+    pub team: Team,
+    pub win: bool,
+    pub level: u8,
+    pub xp: u32,
+    pub gold: u16,
+    pub kills: u16,
+    pub deaths: u16,
+    pub assists: u16,
+}
+
+/// Outcome of a finished match, emitted once the last packets are sent.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct MatchResult {
+    pub winner: Team,
+    pub duration_secs: u64,
+    pub players: Vec<PlayerResult>,
+}
+
+impl MatchResult {
+    pub fn new<'a>(
+        winner: Team,
+        duration: Duration,
+        champions: impl Iterator<Item = &'a Champion>,
+        usernames: &HashMap<PlayerId, String>,
+    ) -> Self {
+        let mut players: Vec<PlayerResult> = champions
+            .map(|champion| PlayerResult {
+                player_id: champion.player_id,
+                username: usernames
+                    .get(&champion.player_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                team: champion.team_id,
+                win: champion.team_id == winner,
+                level: champion.level,
+                xp: champion.xp,
+                gold: champion.gold,
+                kills: champion.kills,
+                deaths: champion.deaths,
+                assists: champion.assists,
+            })
+            .collect();
+        players.sort_by_key(|player| player.player_id);
+        MatchResult {
+            winner,
+            duration_secs: duration.as_secs(),
+            players,
+        }
+    }
+
+    /// Status code read by the room manager once the process exits.
+    pub fn exit_code(&self) -> i32 {
+        match self.winner {
+            Team::Blue => EXIT_BLUE_WIN,
+            Team::Red => EXIT_RED_WIN,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ChampionStats;
+
+    fn champion(player_id: PlayerId, team: Team) -> Champion {
+        let stats = ChampionStats {
+            attack_damage: 20,
+            attack_speed_ms: 2500,
+            health: 200,
+            mana: 100,
+            armor: 5,
+            xp_per_level: vec![35, 40],
+            level_up_health_increase: 20,
+            level_up_attack_damage_increase: 5,
+            level_up_armor_increase: 2,
+            health_regen: 2,
+            mana_regen: 3,
+            level_up_health_regen_increase: 1,
+            level_up_mana_regen_increase: 1,
+            attack_range_row: 3,
+            attack_range_col: 3,
+        };
+        Champion::new(player_id, team, 10, 10, stats, HashMap::new())
+    }
+
+    #[test]
+    fn test_match_result_marks_winners() {
+        let champions = [
+            champion(2, Team::Red),
+            champion(1, Team::Blue),
+            champion(3, Team::Blue),
+        ];
+        let usernames = HashMap::from([(2, "alice".to_string())]);
+        let result = MatchResult::new(
+            Team::Red,
+            Duration::from_millis(62_500),
+            champions.iter(),
+            &usernames,
+        );
+
+        assert_eq!(result.duration_secs, 62);
+        let ids: Vec<PlayerId> = result.players.iter().map(|p| p.player_id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        let winners: Vec<bool> = result.players.iter().map(|p| p.win).collect();
+        assert_eq!(winners, vec![false, true, false]);
+        assert_eq!(result.players[1].username, "alice");
+        assert_eq!(result.exit_code(), EXIT_RED_WIN);
+    }
+
+    #[test]
+    fn test_match_result_serializes_for_the_room_manager() {
+        let usernames = HashMap::from([(1, "bob".to_string())]);
+        let mut bob = champion(1, Team::Blue);
+        bob.kills = 3;
+        let result = MatchResult::new(Team::Blue, Duration::ZERO, [bob].iter(), &usernames);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["winner"], "blue");
+        assert_eq!(json["players"][0]["team"], "blue");
+        assert_eq!(json["players"][0]["username"], "bob");
+        assert_eq!(json["players"][0]["kills"], 3);
+        assert_eq!(result.exit_code(), EXIT_BLUE_WIN);
+    }
+}
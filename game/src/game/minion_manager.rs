@@ -1,21 +1,11 @@
-use rand::prelude::*;
-use std::{
-    collections::HashMap,
-    time::{Duration, Instant},
-};
-use strum::IntoEnumIterator;
-
-use crate::errors::GameError;
+use std::{collections::BTreeMap, time::Duration};
 
 use super::{
     Board, CellContent, MinionId,
     animation::AnimationTrait,
-    cell::Team,
-    entities::{
-        Target,
-        minion::{Lane, Minion},
-        projectile::GameplayEffect,
-    },
+    clock::GameInstant,
+    entities::{Target, minion::Minion, projectile::GameplayEffect},
+    map_layout::LanePath,
 };
 use crate::config::MinionStats;
 
@@ -23,161 +13,85 @@ use crate::config::MinionStats;
 pub struct MinionManager {
     minions_per_wave: u8,
     pub minions_this_wave: u8,
-    pub minions: HashMap<MinionId, Minion>,
-    pub wave_creation_time: Instant,
+    pub minions: BTreeMap<MinionId, Minion>,
+    pub wave_creation_time: GameInstant,
+    wave_interval: Duration,
     minion_stats: MinionStats,
+    lanes: Vec<LanePath>,
+    next_minion_id: MinionId,
 }
 
 impl MinionManager {
-    pub fn new(minion_stats: MinionStats) -> Self {
+    pub fn new(
+        minion_stats: MinionStats,
+        lanes: Vec<LanePath>,
+        minions_per_wave: u8,
+        wave_interval: Duration,
+    ) -> Self {
         Self {
-            minions_per_wave: 6,
+            minions_per_wave,
             minions_this_wave: 0,
-            minions: HashMap::new(),
-            wave_creation_time: Instant::now(),
+            minions: BTreeMap::new(),
+            wave_creation_time: GameInstant::ZERO,
+            wave_interval,
             minion_stats,
+            lanes,
+            next_minion_id: 1,
+        }
+    }
+
+    /// Replaces the stats used for future waves, optionally rescaling live minions.
+    pub fn set_minion_stats(&mut self, minion_stats: MinionStats, rescale_live: bool) {
+        if rescale_live {
+            for minion in self.minions.values_mut() {
+                minion.rescale(minion_stats.clone());
+            }
         }
+        self.minion_stats = minion_stats;
     }
 
-    pub fn make_wave(&mut self, board: &mut Board) {
-        let now = Instant::now();
+    pub fn schedule_first_wave(&mut self, now: GameInstant) {
+        self.wave_creation_time = now + self.wave_interval;
+    }
+
+    pub fn make_wave(&mut self, board: &mut Board, now: GameInstant) {
         if now >= self.wave_creation_time {
-            for team in Team::iter() {
-                match team {
-                    Team::Blue => {
-                        for lane in Lane::iter() {
-                            let minion_id = generate_minion_id().unwrap();
-                            match lane {
-                                Lane::Top => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                                Lane::Mid => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                                Lane::Bottom => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                            }
-                        }
-                    }
-                    Team::Red => {
-                        for lane in Lane::iter() {
-                            let minion_id = generate_minion_id().unwrap();
-                            match lane {
-                                Lane::Top => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                                Lane::Mid => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                                Lane::Bottom => {
-                                    let minion = Minion::new(
-                                        minion_id,
-                                        team,
-                                        lane,
-                                        self.minion_stats.clone(),
-                                    );
-                                    board.place_cell(
-                                        CellContent::Minion(minion_id, team),
-                                        minion.row as usize,
-                                        minion.col as usize,
-                                    );
-                                    self.minions.insert(minion_id, minion);
-                                }
-                            }
-                        }
-                    }
-                }
+            for lane_path in &self.lanes {
+                let minion_id = self.next_minion_id;
+                self.next_minion_id += 1;
+                let minion = Minion::new(minion_id, lane_path, self.minion_stats.clone());
+                board.place_cell(
+                    CellContent::Minion(minion_id, minion.team_id),
+                    minion.row as usize,
+                    minion.col as usize,
+                );
+                self.minions.insert(minion_id, minion);
             }
             // Stopping wave creation
             self.minions_this_wave += 1;
-            self.wave_creation_time = Instant::now() + Duration::from_millis(80);
+            self.wave_creation_time = now + Duration::from_millis(80);
             if self.minions_this_wave >= self.minions_per_wave {
-                self.wave_creation_time = Instant::now() + Duration::from_secs(30);
+                self.wave_creation_time = now + self.wave_interval;
                 self.minions_this_wave = 0;
             }
         }
     }
 
-    pub fn manage_minions_mouvements(&mut self, mut board: &mut Board) {
+    pub fn manage_minions_mouvements(&mut self, board: &mut Board, now: GameInstant) {
         self.minions.iter_mut().for_each(|(_, minion)| {
-            let _ = minion.movement_phase(&mut board);
+            let _ = minion.movement_phase(board, now);
         });
     }
 
     pub fn manage_minions_attack(
         &mut self,
-        mut board: &mut Board,
+        board: &mut Board,
         new_animations: &mut Vec<Box<dyn AnimationTrait>>,
         pending_effects: &mut Vec<(Option<super::PlayerId>, Target, Vec<GameplayEffect>)>,
+        now: GameInstant,
     ) {
         self.minions.iter_mut().for_each(|(_, minion)| {
-            minion.attack_phase(&mut board, new_animations, pending_effects);
+            minion.attack_phase(board, new_animations, pending_effects, now);
         });
     }
 }
-
-fn generate_minion_id() -> Result<MinionId, GameError> {
-    let mut rng = rand::rng();
-    let nums: Vec<usize> = (1..99999).collect();
-    if let Some(id) = nums.choose(&mut rng) {
-        Ok(*id)
-    } else {
-        Err(GameError::GenerateIdError)
-    }
-}
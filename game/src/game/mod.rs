@@ -3,49 +3,81 @@ pub mod animation;
 pub mod board;
 pub mod buffs;
 pub mod cell;
+pub mod clock;
 pub mod entities;
+pub mod frame_sync;
+pub mod map_generator;
+pub mod map_layout;
+pub mod map_lint;
+pub mod map_registry;
+pub mod match_result;
 pub mod minion_manager;
 pub mod monster_manager;
+pub mod phase;
 pub mod projectile_manager;
+pub mod replay;
+pub mod roster;
+pub mod spectator;
 pub mod spell;
+pub mod vision;
 
-use crate::config::GameConfig;
+use crate::config::{GameConfig, MonsterStats};
+use crate::errors::GameError;
 use crate::packet::board_packet::BoardPacket;
+use crate::packet::codec::ServerPacket;
+use crate::packet::end_game_packet::EndGamePacket;
+use crate::packet::frame_packet::FramePacket;
+use crate::packet::kill_packet::KillPacket;
+use crate::packet::phase_packet::PhasePacket;
+use crate::packet::rle;
 use animation::{AnimationCommand, AnimationTrait};
 pub use board::Board;
+use board::Viewport;
 use buffs::Buff;
-use bytes::BytesMut;
 use cell::Team;
 pub use cell::{BaseTerrain, Cell, CellContent, MinionId, PlayerId, TowerId};
+use clock::{GameInstant, SimClock};
 pub use entities::champion::{Action, Champion};
 use entities::{
-    AttackAction, Fighter, Target,
-    base::Base,
-    projectile::GameplayEffect,
-    tower::{Tower, generate_tower_id},
+    AttackAction, Fighter, Target, base::Base, projectile::GameplayEffect, tower::Tower,
 };
+use frame_sync::{FrameSync, FrameUpdate};
+use map_layout::StructureKind;
+use match_result::MatchResult;
 use minion_manager::MinionManager;
 use monster_manager::MonsterManager;
+use phase::MatchPhase;
 use projectile_manager::ProjectileManager;
+use replay::{ReplayEvent, ReplayRecorder, ReplayTick};
+use roster::Roster;
+use spectator::{Camera, FIRST_SPECTATOR_ID, Spectator, SpectatorVision};
 use spell::Spell;
 use tokio::sync::mpsc;
+use vision::{BushRegions, SightSource, TeamVision};
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     mem::take,
-    time::{Duration, Instant},
-    usize, vec,
+    time::Duration,
+    vec,
 };
 
-pub type ClientMessage = BytesMut;
+pub type ClientMessage = ServerPacket;
+pub type ReconnectToken = u64;
 
 pub struct GameManager {
     players_count: usize,
+    reconnect_tokens: HashMap<ReconnectToken, PlayerId>,
+    usernames: HashMap<PlayerId, String>,
+    roster: Option<Roster>,
+    // Disconnected players and the grace time left before their slot is abandoned
+    disconnected: BTreeMap<PlayerId, Duration>,
     max_players: usize,
-    pub game_started: bool,
-    player_action: HashMap<PlayerId, Action>,
-    champions: HashMap<PlayerId, Champion>,
-    towers: HashMap<TowerId, Tower>,
+    phase: MatchPhase,
+    last_phase_packet: Option<PhasePacket>,
+    player_action: BTreeMap<PlayerId, Action>,
+    champions: BTreeMap<PlayerId, Champion>,
+    towers: BTreeMap<TowerId, Tower>,
     red_base: Base,
     blue_base: Base,
     minion_manager: MinionManager,
@@ -53,78 +85,96 @@ pub struct GameManager {
     projectile_manager: ProjectileManager,
     animations: Vec<Box<dyn AnimationTrait>>,
     pub client_channel: HashMap<PlayerId, mpsc::Sender<ClientMessage>>,
+    // Players receiving delta frames, see `track_frames`
+    frame_syncs: HashMap<PlayerId, FrameSync>,
+    frame_tick: u32,
+    viewports: HashMap<PlayerId, Viewport>,
+    // Spectators use the client channels too, with ids from `FIRST_SPECTATOR_ID`
+    spectators: HashMap<PlayerId, Spectator>,
+    max_spectators: usize,
+    spectator_full_vision: bool,
+    bush_regions: BushRegions,
     board: Board,
-    pub tick: u64,
-    dead_minion_positions: Vec<(u16, u16, Team)>,
+    clock: SimClock,
+    // Position, team and last hitter of the minions killed this tick
+    dead_minions: Vec<(u16, u16, Team, Option<PlayerId>)>,
+    // Kills of the last tick, broadcast with the next updates
+    kill_feed: Vec<KillPacket>,
     config: GameConfig,
-    game_start_time: Option<Instant>,
+    game_start_time: Option<GameInstant>,
     initial_monsters_spawned: bool,
+    match_result: Option<MatchResult>,
+    replay: Option<ReplayRecorder>,
 }
 
 impl GameManager {
-    pub fn new(config: GameConfig, max_players: u8) -> Self {
+    pub fn new(config: GameConfig, mut board: Board, max_players: u8) -> Self {
         println!("Initializing GameManager...");
-        let file_path = "game/assets/map.json";
-        let mut board = match Board::from_json(file_path) {
-            Ok(board) => board,
-            Err(e) => {
-                eprintln!("Failed to initialize the board from {}: {}", file_path, e);
-                std::process::exit(1);
-            }
-        };
-        let mut towers: HashMap<TowerId, Tower> = HashMap::new();
-        // Tower placement
-        {
-            let placement = vec![
-                (196, 150),
-                (39, 7),
-                (115, 82),
-                (191, 79),
-                (120, 8),
-                (148, 67),
-            ];
-            // Bottom t1
-            placement.into_iter().for_each(|place| {
-                let id = generate_tower_id().unwrap();
-                let tower_blue = Tower::new(id, Team::Blue, place.0, place.1, config.tower.clone());
-                tower_blue.place_tower(&mut board);
-                let id = generate_tower_id().unwrap();
-                let tower_red = Tower::new(id, Team::Red, place.1, place.0, config.tower.clone());
-                tower_red.place_tower(&mut board);
-                towers.insert(tower_blue.tower_id, tower_blue);
-                towers.insert(tower_red.tower_id, tower_red);
-            });
+        let mut towers: BTreeMap<TowerId, Tower> = BTreeMap::new();
+        // Ids follow the map order so a match replays the same way
+        for (id, structure) in (1..).zip(board.map_layout.structures_of(StructureKind::Tower)) {
+            let tower = Tower::new(
+                id,
+                structure.team,
+                structure.row,
+                structure.col,
+                config.tower.clone(),
+            );
+            towers.insert(id, tower);
+        }
+        for tower in towers.values() {
+            tower.place_tower(&mut board);
         }
 
-        let red_base = Base::new(Team::Red, (190, 10), config.base.clone());
-        let blue_base = Base::new(Team::Blue, (10, 190), config.base.clone());
+        // Validation guarantees each team has a base
+        let make_base = |team: Team| {
+            let structure = board
+                .map_layout
+                .base_of(team)
+                .expect("map defines a base for each team");
+            Base::new(
+                team,
+                (structure.row as i32, structure.col as i32),
+                config.base.clone(),
+            )
+        };
+        let red_base = make_base(Team::Red);
+        let blue_base = make_base(Team::Blue);
 
-        for i in 0..3 {
-            for j in 0..3 {
-                board.place_cell(
-                    CellContent::Base(Team::Blue),
-                    (red_base.position.0 + i) as usize,
-                    (red_base.position.1 + j) as usize,
-                );
-                board.place_cell(
-                    CellContent::Base(Team::Red),
-                    (blue_base.position.0 + i) as usize,
-                    (blue_base.position.1 + j) as usize,
-                );
+        for base in [&red_base, &blue_base] {
+            for i in 0..3 {
+                for j in 0..3 {
+                    board.place_cell(
+                        CellContent::Base(base.team),
+                        (base.position.0 + i) as usize,
+                        (base.position.1 + j) as usize,
+                    );
+                }
             }
         }
 
-        let minion_manager = MinionManager::new(config.minion.clone());
-        let monster_manager = MonsterManager::new(config.neutral_monsters.clone());
+        let minion_manager = MinionManager::new(
+            config.minion.clone(),
+            board.map_layout.lanes.clone(),
+            config.rules.minion_rules.minions_per_wave,
+            config.rules.timing_rules.minion_wave_interval(),
+        );
+        let monster_manager = MonsterManager::new(monster_definitions(&config, &board));
         let projectile_manager = ProjectileManager::new();
+        let clock = SimClock::new(config.rules.timing_rules.game_tick());
 
         GameManager {
             players_count: 0,
+            reconnect_tokens: HashMap::new(),
+            usernames: HashMap::new(),
+            roster: None,
+            disconnected: BTreeMap::new(),
             max_players: max_players as usize,
-            game_started: false,
+            phase: MatchPhase::Lobby,
+            last_phase_packet: None,
             config,
-            player_action: HashMap::new(),
-            champions: HashMap::new(),
+            player_action: BTreeMap::new(),
+            champions: BTreeMap::new(),
             towers,
             red_base,
             blue_base,
@@ -133,11 +183,21 @@ impl GameManager {
             projectile_manager,
             animations: Vec::new(),
             client_channel: HashMap::new(),
+            frame_syncs: HashMap::new(),
+            frame_tick: 0,
+            viewports: HashMap::new(),
+            spectators: HashMap::new(),
+            max_spectators: 0,
+            spectator_full_vision: false,
+            bush_regions: BushRegions::new(&board),
             board,
-            tick: 20,
-            dead_minion_positions: Vec::new(),
+            clock,
+            dead_minions: Vec::new(),
+            kill_feed: Vec::new(),
             game_start_time: None,
             initial_monsters_spawned: false,
+            match_result: None,
+            replay: None,
         }
     }
 
@@ -153,6 +213,49 @@ impl GameManager {
         &self.config
     }
 
+    /// Swaps in reloaded balance values between two ticks.
+    /// Rules stay fixed for the whole match; live entities are only rescaled on request.
+    pub fn reload_config(&mut self, mut config: GameConfig, rescale_live: bool) {
+        self.record(ReplayEvent::ConfigReload {
+            config: config.files.clone(),
+            rescale_live,
+        });
+        config.rules = self.config.rules.clone();
+
+        self.minion_manager
+            .set_minion_stats(config.minion.clone(), rescale_live);
+        self.monster_manager
+            .set_definitions(monster_definitions(&config, &self.board), rescale_live);
+        if rescale_live {
+            for tower in self.towers.values_mut() {
+                tower.rescale(config.tower.clone());
+            }
+            for champion in self.champions.values_mut() {
+                champion.rescale(config.champion.clone(), &config.spells);
+            }
+        }
+        self.config = config;
+    }
+
+    /// Set once a base is destroyed, the match does not tick anymore afterwards.
+    pub fn match_result(&self) -> Option<&MatchResult> {
+        self.match_result.as_ref()
+    }
+
+    /// Hands the client channels over so the last packets can be flushed.
+    /// Dropping them ends the writer tasks, which then close their sockets.
+    pub fn take_client_channels(&mut self) -> HashMap<PlayerId, mpsc::Sender<ClientMessage>> {
+        take(&mut self.client_channel)
+    }
+
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    pub fn tick_duration(&self) -> Duration {
+        self.clock.tick_duration()
+    }
+
     pub fn print_game_state(&self) {
         println!(
             "Player connected: {}/{}",
@@ -162,7 +265,12 @@ impl GameManager {
             println!("No action received");
         } else {
             for (player_id, action) in &self.player_action {
-                println!("Player: {} / Action: {:?}", player_id, action);
+                println!(
+                    "Player: {} ({}) / Action: {:?}",
+                    player_id,
+                    self.username_of(*player_id),
+                    action
+                );
             }
         }
         println!("Board size: {}.{}", self.board.rows, self.board.cols);
@@ -172,34 +280,79 @@ impl GameManager {
         self.player_action.clear();
     }
 
-    pub fn add_player(&mut self, spell1_id: u8, spell2_id: u8) -> Option<PlayerId> {
-        if self.players_count < self.max_players {
-            self.players_count += 1;
-            let player_id = self.players_count;
+    /// Reserves the slots of the accounts the room manager expects.
+    pub fn set_roster(&mut self, roster: Roster) {
+        self.max_players = roster.len();
+        self.roster = Some(roster);
+    }
 
-            let team_id = if player_id % 2 != 0 { Team::Blue } else { Team::Red };
+    // Roster accounts get their reserved slot, otherwise ids follow the join order with
+    // teams alternating and teammates taking the team's spawn points in turn.
+    // Slots held by disconnected players stay reserved during their grace window.
+    fn assign_slot(&self, username: Option<&str>) -> Option<(PlayerId, Team, usize)> {
+        if let Some(roster) = &self.roster {
+            let (player_id, entry) = roster.entry_of(username?)?;
+            return Some((player_id, entry.team, entry.slot as usize));
+        }
+        let player_id =
+            (1..=self.max_players).find(|player_id| !self.champions.contains_key(player_id))?;
+        Some((player_id, Team::of_player(player_id), (player_id - 1) / 2))
+    }
 
-            let (row, col) = if team_id == Team::Blue {
-                (199, (player_id - 1) as u16 / 2)
-            } else {
-                (0, 199 - (player_id - 1) as u16 / 2)
+    /// Adds a player to the lobby, `username` being the verified account name when
+    /// sessions are checked. An account can only hold one slot.
+    pub fn add_player(
+        &mut self,
+        spell1_id: u8,
+        spell2_id: u8,
+        username: Option<String>,
+    ) -> Option<PlayerId> {
+        if let Some(name) = &username
+            && self.usernames.values().any(|taken| taken == name)
+        {
+            return None;
+        }
+        // Two champions never share a spawn point, the join is refused instead
+        if let Some((player_id, team_id, spawn_slot)) = self.assign_slot(username.as_deref())
+            && let Some((row, col)) = self
+                .board
+                .map_layout
+                .spawns_of(team_id)
+                .get(spawn_slot)
+                .map(|spawn| (spawn.row, spawn.col))
+            && self.phase == MatchPhase::Lobby
+        {
+            self.record(ReplayEvent::Join {
+                spell1: spell1_id,
+                spell2: spell2_id,
+                username: username.clone(),
+            });
+            self.players_count += 1;
+            let token = loop {
+                let token = rand::random::<ReconnectToken>();
+                if !self.reconnect_tokens.contains_key(&token) {
+                    break token;
+                }
             };
+            self.reconnect_tokens.insert(token, player_id);
+            self.usernames.insert(
+                player_id,
+                username.unwrap_or_else(|| format!("player{}", player_id)),
+            );
 
             // Assign Champion to player, and place it on the board
             {
                 // We get the choosen spell
                 let mut selected_spell: HashMap<u8, Box<dyn Spell>> = HashMap::new();
-                if let Some(spell_stats) = self.config.spells.get(&spell1_id) {
-                    selected_spell.insert(
-                        0,
-                        spell::create_spell_from_id(spell1_id, spell_stats.clone()),
-                    );
-                }
-                if let Some(spell_stats) = self.config.spells.get(&spell2_id) {
-                    selected_spell.insert(
-                        1,
-                        spell::create_spell_from_id(spell2_id, spell_stats.clone()),
-                    );
+                for (slot, spell_id) in [(0, spell1_id), (1, spell2_id)] {
+                    if let Some(spell) = self
+                        .config
+                        .spells
+                        .get(&spell_id)
+                        .and_then(|stats| spell::create_spell_from_id(spell_id, stats.clone()))
+                    {
+                        selected_spell.insert(slot, spell);
+                    }
                 }
                 let champion = Champion::new(
                     player_id,
@@ -217,11 +370,9 @@ impl GameManager {
                 );
             }
 
-            // We check if we can start the game and send a Start to each player
+            // The countdown starts once everyone has joined
             if self.players_count == self.max_players {
-                self.game_started = true;
-                self.game_start_time = Some(Instant::now());
-                self.minion_manager.wave_creation_time = Instant::now() + Duration::from_secs(30);
+                self.start_countdown();
             }
             Some(player_id)
         } else {
@@ -229,23 +380,270 @@ impl GameManager {
         }
     }
 
+    pub fn username_of(&self, player_id: PlayerId) -> &str {
+        self.usernames
+            .get(&player_id)
+            .map(String::as_str)
+            .unwrap_or("unknown")
+    }
+
+    pub fn reconnect_token_of(&self, player_id: PlayerId) -> Option<ReconnectToken> {
+        self.reconnect_tokens
+            .iter()
+            .find(|(_, id)| **id == player_id)
+            .map(|(token, _)| *token)
+    }
+
+    /// Gives a returning client its player back, the caller then attaches the new channel.
+    /// A player still seen as connected is taken over by the new connection.
+    pub fn reconnect_player(&mut self, token: ReconnectToken) -> Option<PlayerId> {
+        let player_id = *self.reconnect_tokens.get(&token)?;
+        self.rejoin(player_id);
+        Some(player_id)
+    }
+
+    fn rejoin(&mut self, player_id: PlayerId) {
+        self.record(ReplayEvent::Reconnect { player_id });
+        if self.disconnected.remove(&player_id).is_some() {
+            self.players_count += 1;
+            println!(
+                "Player {} ({}) reconnected. Total player now: {}/{}",
+                player_id,
+                self.username_of(player_id),
+                self.players_count,
+                self.max_players
+            );
+            if self.phase == MatchPhase::Lobby && self.players_count == self.max_players {
+                self.start_countdown();
+            }
+        }
+    }
+
+    // Drops the slots of players that did not come back in time
+    fn expire_disconnected(&mut self, elapsed: Duration) {
+        let mut abandoned = Vec::new();
+        for (player_id, remaining) in self.disconnected.iter_mut() {
+            *remaining = remaining.saturating_sub(elapsed);
+            if remaining.is_zero() {
+                abandoned.push(*player_id);
+            }
+        }
+        for player_id in abandoned {
+            println!(
+                "Player {} ({}) did not reconnect in time",
+                player_id,
+                self.username_of(player_id)
+            );
+            self.disconnected.remove(&player_id);
+            self.reconnect_tokens.retain(|_, id| *id != player_id);
+            // Before the match starts the slot is freed for someone else
+            if self.phase == MatchPhase::Lobby
+                && let Some(champion) = self.champions.remove(&player_id)
+            {
+                self.board
+                    .clear_cell(champion.row as usize, champion.col as usize);
+                self.usernames.remove(&player_id);
+            }
+        }
+    }
+
     pub fn remove_player(&mut self, player_id: &PlayerId) {
         if self.players_count > 0 {
+            self.record(ReplayEvent::Leave {
+                player_id: *player_id,
+            });
             self.players_count -= 1;
-            self.player_action.remove(&player_id);
-            self.client_channel.remove(&player_id);
+            self.player_action.remove(player_id);
+            self.client_channel.remove(player_id);
+            self.frame_syncs.remove(player_id);
+            self.disconnected
+                .insert(*player_id, self.config.rules.timing_rules.reconnect_grace());
             println!(
-                "Player {} disconnected. Total player now: {}/{}",
-                player_id, self.players_count, self.max_players
+                "Player {} ({}) disconnected. Total player now: {}/{}",
+                player_id,
+                self.username_of(*player_id),
+                self.players_count,
+                self.max_players
             );
-            if self.game_started && self.players_count < self.max_players {
-                self.game_started = false;
+            match &mut self.phase {
+                MatchPhase::Countdown { .. } if self.game_start_time.is_none() => {
+                    self.phase = MatchPhase::Lobby;
+                }
+                MatchPhase::Countdown { .. } | MatchPhase::InProgress => {
+                    self.phase = MatchPhase::paused(self.config.rules.timing_rules.pause_timeout());
+                }
+                MatchPhase::Paused { resume_votes, .. } => {
+                    resume_votes.remove(player_id);
+                    self.resume_if_agreed();
+                }
+                MatchPhase::Lobby | MatchPhase::Ended => {}
             }
         } else {
             println!("Warning: Tried to remove player, but player count already at 0.");
         }
     }
 
+    /// Switches a connection between delta frames and full `BoardPacket`s, a new connection
+    /// always starts again from a keyframe.
+    pub fn track_frames(&mut self, player_id: PlayerId, enabled: bool) {
+        if enabled {
+            self.frame_syncs.insert(player_id, FrameSync::new());
+        } else {
+            self.frame_syncs.remove(&player_id);
+        }
+    }
+
+    /// Lets up to `max` spectators watch, on top of the players.
+    pub fn allow_spectators(&mut self, max: usize, full_vision: bool) {
+        self.max_spectators = max;
+        self.spectator_full_vision = full_vision;
+    }
+
+    /// Adds a spectator looking at the middle of the map, `None` when spectators are full or
+    /// the vision is not allowed.
+    pub fn add_spectator(&mut self, vision: SpectatorVision) -> Option<PlayerId> {
+        if self.spectators.len() >= self.max_spectators
+            || (vision == SpectatorVision::Full && !self.spectator_full_vision)
+        {
+            return None;
+        }
+        let spectator_id = (FIRST_SPECTATOR_ID..)
+            .find(|id| !self.spectators.contains_key(id))
+            .expect("spectator ids are not exhausted");
+        let spectator = Spectator::new(
+            vision,
+            (self.board.rows / 2) as u16,
+            (self.board.cols / 2) as u16,
+        );
+        self.spectators.insert(spectator_id, spectator);
+        println!(
+            "Spectator {} joined with {:?} vision. Spectators now: {}/{}",
+            spectator_id,
+            vision,
+            self.spectators.len(),
+            self.max_spectators
+        );
+        Some(spectator_id)
+    }
+
+    pub fn is_spectator(&self, client_id: PlayerId) -> bool {
+        self.spectators.contains_key(&client_id)
+    }
+
+    /// Moves a spectator camera, a free camera is kept on the board.
+    pub fn set_camera(&mut self, spectator_id: PlayerId, camera: Camera) {
+        let camera = match camera {
+            Camera::Free { row, col } => Camera::Free {
+                row: row.min(self.board.rows as u16 - 1),
+                col: col.min(self.board.cols as u16 - 1),
+            },
+            follow => follow,
+        };
+        if let Some(spectator) = self.spectators.get_mut(&spectator_id) {
+            spectator.camera = camera;
+        }
+    }
+
+    pub fn remove_spectator(&mut self, spectator_id: PlayerId) {
+        if self.spectators.remove(&spectator_id).is_some() {
+            self.client_channel.remove(&spectator_id);
+            self.frame_syncs.remove(&spectator_id);
+            self.viewports.remove(&spectator_id);
+            println!("Spectator {} left", spectator_id);
+        }
+    }
+
+    /// Applies the viewport a client asked for, clamped to what the server allows, and returns
+    /// the size it will get.
+    pub fn set_viewport(&mut self, player_id: PlayerId, rows: u16, cols: u16) -> Viewport {
+        let viewport = self.board.clamp_viewport(rows, cols);
+        if self.viewports.insert(player_id, viewport) != Some(viewport)
+            && let Some(sync) = self.frame_syncs.get_mut(&player_id)
+        {
+            // Frames of another size cannot serve as a delta base
+            *sync = FrameSync::new();
+        }
+        viewport
+    }
+
+    pub fn viewport_of(&self, player_id: PlayerId) -> Viewport {
+        self.viewports.get(&player_id).copied().unwrap_or_else(|| {
+            self.board
+                .clamp_viewport(Viewport::DEFAULT.rows, Viewport::DEFAULT.cols)
+        })
+    }
+
+    pub fn ack_frame(&mut self, player_id: PlayerId, tick: u32) {
+        if let Some(sync) = self.frame_syncs.get_mut(&player_id) {
+            sync.ack(tick);
+        }
+    }
+
+    pub fn phase(&self) -> &MatchPhase {
+        &self.phase
+    }
+
+    fn start_countdown(&mut self) {
+        self.phase = MatchPhase::Countdown {
+            remaining: self.config.rules.timing_rules.countdown(),
+        };
+    }
+
+    fn start_play(&mut self) {
+        self.phase = MatchPhase::InProgress;
+        if self.game_start_time.is_none() {
+            let now = self.clock.now();
+            self.game_start_time = Some(now);
+            self.minion_manager.schedule_first_wave(now);
+        }
+    }
+
+    pub fn request_pause(&mut self, player_id: PlayerId) {
+        if self.phase == MatchPhase::InProgress {
+            self.record(ReplayEvent::Pause { player_id });
+            println!(
+                "Player {} ({}) paused the match",
+                player_id,
+                self.username_of(player_id)
+            );
+            self.phase = MatchPhase::paused(self.config.rules.timing_rules.pause_timeout());
+        }
+    }
+
+    pub fn vote_resume(&mut self, player_id: PlayerId) {
+        if let MatchPhase::Paused { resume_votes, .. } = &mut self.phase {
+            resume_votes.insert(player_id);
+            self.record(ReplayEvent::Resume { player_id });
+            self.resume_if_agreed();
+        }
+    }
+
+    // Every connected player has to agree before the resume countdown starts, an empty
+    // server waits for the pause timeout instead
+    fn resume_if_agreed(&mut self) {
+        if let MatchPhase::Paused { resume_votes, .. } = &self.phase
+            && self.players_count > 0
+            && resume_votes.len() >= self.players_count
+        {
+            self.start_countdown();
+        }
+    }
+
+    /// Packet describing the current phase, only returned when it changed since the last call.
+    fn phase_update(&mut self) -> Option<PhasePacket> {
+        let packet = PhasePacket::new(
+            self.phase.code(),
+            self.phase.seconds_left(),
+            self.phase.resume_votes() as u8,
+            self.players_count as u8,
+        );
+        if self.last_phase_packet.as_ref() == Some(&packet) {
+            return None;
+        }
+        self.last_phase_packet = Some(packet.clone());
+        Some(packet)
+    }
+
     pub fn store_player_action(&mut self, player_id: PlayerId, action_value: u8) {
         let action = match action_value {
             1 => Action::MoveUp,
@@ -260,8 +658,71 @@ impl GameManager {
         self.player_action.insert(player_id, action);
     }
 
-    pub async fn send_to_player(&self, player_id: PlayerId, message: ClientMessage) {
-        println!("Send_to_player message lenght: {}", message.len());
+    /// Buys an item for a player's champion, which is returned to report its new stats.
+    pub fn purchase_item(
+        &mut self,
+        player_id: PlayerId,
+        item_id: u32,
+    ) -> Result<&Champion, GameError> {
+        let item = self
+            .config
+            .items
+            .get(&item_id)
+            .cloned()
+            .ok_or_else(|| GameError::InvalidInput(format!("unknown item {}", item_id)))?;
+        let champion = self.champions.get_mut(&player_id).ok_or_else(|| {
+            GameError::InvalidInput(format!("no champion for player {}", player_id))
+        })?;
+        champion.add_item(item)?;
+        self.record(ReplayEvent::Purchase { player_id, item_id });
+        Ok(&self.champions[&player_id])
+    }
+
+    /// Records every input from now on, see `replay`.
+    pub fn record_replay(&mut self, recorder: ReplayRecorder) {
+        self.replay = Some(recorder);
+    }
+
+    fn record(&mut self, event: ReplayEvent) {
+        if let Some(recorder) = &mut self.replay {
+            recorder.record(event);
+        }
+    }
+
+    /// Applies the inputs a recorded tick had, the caller then runs `game_tick`.
+    pub fn apply_replay_tick(&mut self, tick: &ReplayTick) {
+        for event in &tick.events {
+            match event {
+                ReplayEvent::Join {
+                    spell1,
+                    spell2,
+                    username,
+                } => {
+                    self.add_player(*spell1, *spell2, username.clone());
+                }
+                ReplayEvent::Reconnect { player_id } => self.rejoin(*player_id),
+                ReplayEvent::Leave { player_id } => self.remove_player(player_id),
+                ReplayEvent::Purchase { player_id, item_id } => {
+                    if let Err(e) = self.purchase_item(*player_id, *item_id) {
+                        eprintln!("Replayed purchase failed: {}", e);
+                    }
+                }
+                ReplayEvent::Pause { player_id } => self.request_pause(*player_id),
+                ReplayEvent::Resume { player_id } => self.vote_resume(*player_id),
+                ReplayEvent::ConfigReload {
+                    config,
+                    rescale_live,
+                } => match GameConfig::from_files(config.clone(), self.config.sources.clone()) {
+                    Ok(config) => self.reload_config(config, *rescale_live),
+                    Err(e) => eprintln!("Replayed config reload failed: {}", e),
+                },
+            }
+        }
+        self.player_action
+            .extend(tick.actions.iter().map(|(id, action)| (*id, *action)));
+    }
+
+    pub fn send_to_player(&self, player_id: PlayerId, message: ClientMessage) {
         if let Some(sender) = self.client_channel.get(&player_id) {
             let sender_clone = sender.clone();
             // We use spawn to send without blocking the game manager lock
@@ -278,19 +739,242 @@ impl GameManager {
         }
     }
 
-    pub fn game_tick(&mut self) -> HashMap<PlayerId, ClientMessage> {
-        if let Some(start_time) = self.game_start_time {
-            if !self.initial_monsters_spawned && start_time.elapsed() >= Duration::from_secs(5) {
-                self.monster_manager.spawn_initial_monsters(&mut self.board);
-                self.initial_monsters_spawned = true;
+    pub fn game_tick(&mut self) -> HashMap<PlayerId, Vec<ClientMessage>> {
+        if let Some(recorder) = &mut self.replay
+            && let Err(e) = recorder.begin_tick(&self.player_action)
+        {
+            eprintln!("Replay recording stopped: {}", e);
+            self.replay = None;
+        }
+        let updates = self.run_tick();
+        if self.match_result.is_some()
+            && let Some(recorder) = &mut self.replay
+            && let Err(e) = recorder.finish()
+        {
+            eprintln!("Failed to finish the replay: {}", e);
+        }
+        updates
+    }
+
+    fn run_tick(&mut self) -> HashMap<PlayerId, Vec<ClientMessage>> {
+        self.expire_disconnected(self.clock.tick_duration());
+        if self.phase.elapse(self.clock.tick_duration()) {
+            match self.phase {
+                MatchPhase::Countdown { .. } => self.start_play(),
+                MatchPhase::Paused { .. } => {
+                    println!("Pause timed out, resuming the match");
+                    self.start_countdown();
+                }
+                _ => {}
             }
         }
+        if self.phase == MatchPhase::InProgress {
+            self.play_tick();
+        }
+
+        let mut updates: HashMap<PlayerId, Vec<ClientMessage>> = HashMap::new();
+        if let Some(packet) = self.phase_update() {
+            for player_id in self.client_channel.keys() {
+                updates.insert(*player_id, vec![ServerPacket::Phase(packet.clone())]);
+            }
+        }
+        for packet in self.kill_feed.drain(..) {
+            for player_id in self.client_channel.keys() {
+                updates
+                    .entry(*player_id)
+                    .or_default()
+                    .push(ServerPacket::Kill(packet.clone()));
+            }
+        }
+
+        if let Some(result) = &self.match_result {
+            for (player_id, champion) in &self.champions {
+                let packet = EndGamePacket::new(champion.team_id == result.winner);
+                updates
+                    .entry(*player_id)
+                    .or_default()
+                    .push(ServerPacket::EndGame(packet));
+            }
+            // Spectators win with the team whose vision they had, full vision backs neither
+            for (spectator_id, spectator) in &self.spectators {
+                let win = spectator.vision == SpectatorVision::Team(result.winner);
+                updates
+                    .entry(*spectator_id)
+                    .or_default()
+                    .push(ServerPacket::EndGame(EndGamePacket::new(win)));
+            }
+            return updates;
+        }
+        if !self.phase.shows_board() {
+            return updates;
+        }
+
+        // --- Send per player there board view ---
+        let blue_vision = self.team_vision(Team::Blue);
+        let red_vision = self.team_vision(Team::Red);
+        let vision_of = |team: Team| match team {
+            Team::Blue => &blue_vision,
+            Team::Red => &red_vision,
+        };
+        for spectator in self.spectators.values_mut() {
+            spectator.update_position(|player_id| {
+                self.champions
+                    .get(&player_id)
+                    .map(|champion| (champion.row, champion.col))
+            });
+        }
+        self.frame_tick += 1;
+        let now = self.clock.now();
+        let keyframe_interval = self.config.rules.timing_rules.keyframe_interval_ticks;
+
+        // Who receives a view, where it is centered, what it may show and whose stats it carries
+        let players = self.champions.iter().map(|(player_id, champion)| {
+            (
+                *player_id,
+                (champion.row, champion.col),
+                Some(vision_of(champion.team_id)),
+                Some(champion),
+            )
+        });
+        let spectators = self.spectators.iter().map(|(spectator_id, spectator)| {
+            let vision = match spectator.vision {
+                SpectatorVision::Full => None,
+                SpectatorVision::Team(team) => Some(vision_of(team)),
+            };
+            let followed = match spectator.camera {
+                Camera::Follow(player_id) => self.champions.get(&player_id),
+                Camera::Free { .. } => None,
+            };
+            (
+                *spectator_id,
+                (spectator.row, spectator.col),
+                vision,
+                followed,
+            )
+        });
+        for (client_id, (row, col), vision, champion) in players.chain(spectators) {
+            let board_packet = |encoded_board| match champion {
+                Some(champion) => {
+                    let health = champion.get_health();
+                    let xp_needed = champion.xp_for_next_level().unwrap_or(0); // Get XP needed, 0 if max level
+                    let mut packet = BoardPacket::new(
+                        health.0,
+                        health.1,
+                        champion.stats.mana,
+                        champion.stats.max_mana,
+                        champion.level,
+                        champion.xp,
+                        xp_needed,
+                        encoded_board,
+                    );
+                    // Whole seconds left, rounded up so 0 only shows once alive
+                    packet.respawn_in = champion
+                        .respawn_in(now)
+                        .map_or(0, |left| left.as_millis().div_ceil(1000) as u16);
+                    packet
+                }
+                None => BoardPacket::new(0, 0, 0, 0, 0, 0, 0, encoded_board),
+            };
+
+            let viewport = self.viewport_of(client_id);
+            let packet = match self.frame_syncs.get_mut(&client_id) {
+                Some(sync) => {
+                    let view =
+                        self.board
+                            .encoded_view(row, col, viewport, &self.minion_manager, vision);
+                    match sync.next_frame(
+                        self.frame_tick,
+                        view,
+                        viewport.cols as usize,
+                        keyframe_interval,
+                    ) {
+                        FrameUpdate::Keyframe => {
+                            let rle = rle::encode_binary(
+                                sync.last_view().expect("frame was just recorded"),
+                            );
+                            ServerPacket::Frame(FramePacket::keyframe(
+                                self.frame_tick,
+                                board_packet(rle),
+                            ))
+                        }
+                        FrameUpdate::Delta { base_tick, changes } => {
+                            ServerPacket::Frame(FramePacket::delta(
+                                self.frame_tick,
+                                base_tick,
+                                board_packet(Vec::new()),
+                                changes,
+                            ))
+                        }
+                    }
+                }
+                None => {
+                    let board_rle_vec = self.board.run_length_encode(
+                        row,
+                        col,
+                        viewport,
+                        &self.minion_manager,
+                        vision,
+                    );
+                    ServerPacket::Board(board_packet(board_rle_vec))
+                }
+            };
+            updates.entry(client_id).or_default().push(packet);
+        }
+        updates
+    }
+
+    /// What a team sees through its champions, minions, towers and base.
+    fn team_vision(&self, team: Team) -> TeamVision {
+        let vision = &self.config.rules.vision_rules;
+        let champions = self
+            .champions
+            .values()
+            .filter(|champion| champion.team_id == team && !champion.is_dead())
+            .map(|champion| (champion.row, champion.col, vision.champion_sight_radius));
+        let minions = self
+            .minion_manager
+            .minions
+            .values()
+            .filter(|minion| minion.team_id == team)
+            .map(|minion| (minion.row, minion.col, vision.minion_sight_radius));
+        let towers = self
+            .towers
+            .values()
+            .filter(|tower| tower.team_id == team)
+            .map(|tower| (tower.row, tower.col, vision.tower_sight_radius));
+        let base = match team {
+            Team::Blue => &self.blue_base,
+            Team::Red => &self.red_base,
+        };
+        // The base covers 3x3 cells from its position, it sees from its center
+        let base_center = (
+            (base.position.0 + 1) as u16,
+            (base.position.1 + 1) as u16,
+            vision.tower_sight_radius,
+        );
+        let sources = champions
+            .chain(minions)
+            .chain(towers)
+            .chain([base_center])
+            .map(|(row, col, radius)| SightSource { row, col, radius });
+        self.bush_regions.team_vision(&self.board, team, sources)
+    }
+
+    fn play_tick(&mut self) {
+        self.clock.advance();
+        let now = self.clock.now();
+
+        if let Some(start_time) = self.game_start_time
+            && !self.initial_monsters_spawned
+            && now.duration_since(start_time) >= Duration::from_secs(5)
+        {
+            self.monster_manager.spawn_initial_monsters(&mut self.board);
+            self.initial_monsters_spawned = true;
+        }
 
-        self.tick = self.tick.saturating_add(1);
         println!("---- Game Tick -----");
         self.print_game_state();
 
-        let mut updates = HashMap::new();
         let mut new_animations: Vec<Box<dyn AnimationTrait>> = Vec::new();
         let mut animation_commands_executable: Vec<AnimationCommand> = Vec::new();
         let mut pending_effects: Vec<(Option<PlayerId>, Target, Vec<GameplayEffect>)> = Vec::new();
@@ -301,9 +985,9 @@ impl GameManager {
         // Champions
         for (_, champ) in self.champions.iter_mut() {
             let current_buff = take(&mut champ.active_buffs);
-            let mut kept_buff: HashMap<String, Box<dyn Buff>> = HashMap::new();
+            let mut kept_buff: BTreeMap<String, Box<dyn Buff>> = BTreeMap::new();
             for (id, mut buff) in current_buff.into_iter() {
-                if buff.on_tick(champ) {
+                if buff.on_tick(champ, now) {
                     buff.on_remove(champ);
                 } else {
                     kept_buff.insert(id, buff);
@@ -315,9 +999,9 @@ impl GameManager {
         // Minions
         for (_, minion) in self.minion_manager.minions.iter_mut() {
             let current_buff = take(&mut minion.active_buffs);
-            let mut kept_buff: HashMap<String, Box<dyn Buff>> = HashMap::new();
+            let mut kept_buff: BTreeMap<String, Box<dyn Buff>> = BTreeMap::new();
             for (id, mut buff) in current_buff.into_iter() {
-                if buff.on_tick(minion) {
+                if buff.on_tick(minion, now) {
                     buff.on_remove(minion);
                 } else {
                     kept_buff.insert(id, buff);
@@ -328,21 +1012,30 @@ impl GameManager {
 
         // --- Turn ---
         // Player turn
+        let tick_duration = self.clock.tick_duration();
         for (player_id, champ) in &mut self.champions {
-            // 0. Check death and replace
-            // BUG: Champ dead can still move but is replace each tick
+            // 0. A dead champion waits off the board and ignores its actions
             if champ.is_dead() {
-                champ.put_at_max_health();
-                champ.place_at_base(&mut self.board);
+                let base = match champ.team_id {
+                    Team::Blue => &self.blue_base,
+                    Team::Red => &self.red_base,
+                };
+                // It waits for a free cell when the whole base is crowded
+                if champ.can_respawn(now)
+                    && let Some(cell) = base.spawn_cell(&self.board, champ.spawn())
+                {
+                    champ.respawn(&mut self.board, cell);
+                    println!("Player {} respawned", player_id);
+                }
                 continue;
             }
+            champ.regen_tick(tick_duration);
             // 1. Iterate through player action
-            if let Some(action) = self.player_action.get(&player_id) {
-                if let Err(e) =
-                    champ.take_action(action, &mut self.board, &mut self.projectile_manager)
-                {
-                    println!("Error on player action: {}", e);
-                }
+            if let Some(action) = self.player_action.get(player_id)
+                && let Err(e) =
+                    champ.take_action(action, &mut self.board, &mut self.projectile_manager, now)
+            {
+                println!("Error on player action: {}", e);
             }
 
             // 2. auto_attack
@@ -352,78 +1045,63 @@ impl GameManager {
                         println!("Got content: {:?}", content);
                         match content {
                             CellContent::Tower(id, _) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Tower(*id),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
+                                if let Some(attack) = champ.can_attack(now)
+                                    && let AttackAction::Melee { damage, animation } = attack
+                                {
+                                    new_animations.push(animation);
+                                    pending_effects.push((
+                                        Some(*player_id),
+                                        Target::Tower(*id),
+                                        vec![GameplayEffect::Damage(damage)],
+                                    ))
                                 }
                             }
                             CellContent::Monster(id) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Monster(*id),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
+                                if let Some(attack) = champ.can_attack(now)
+                                    && let AttackAction::Melee { damage, animation } = attack
+                                {
+                                    new_animations.push(animation);
+                                    pending_effects.push((
+                                        Some(*player_id),
+                                        Target::Monster(*id),
+                                        vec![GameplayEffect::Damage(damage)],
+                                    ))
                                 }
                             }
                             CellContent::Minion(id, _) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Minion(*id),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
+                                if let Some(attack) = champ.can_attack(now)
+                                    && let AttackAction::Melee { damage, animation } = attack
+                                {
+                                    new_animations.push(animation);
+                                    pending_effects.push((
+                                        Some(*player_id),
+                                        Target::Minion(*id),
+                                        vec![GameplayEffect::Damage(damage)],
+                                    ))
                                 }
                             }
                             CellContent::Champion(id, _) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Champion(*id),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
+                                if let Some(attack) = champ.can_attack(now)
+                                    && let AttackAction::Melee { damage, animation } = attack
+                                {
+                                    new_animations.push(animation);
+                                    pending_effects.push((
+                                        Some(*player_id),
+                                        Target::Champion(*id),
+                                        vec![GameplayEffect::Damage(damage)],
+                                    ))
                                 }
                             }
                             CellContent::Base(team) => {
-                                if let Some(attack) = champ.can_attack() {
-                                    match attack {
-                                        AttackAction::Melee { damage, animation } => {
-                                            new_animations.push(animation);
-                                            pending_effects.push((
-                                                Some(*player_id),
-                                                Target::Base(*team),
-                                                vec![GameplayEffect::Damage(damage)],
-                                            ))
-                                        }
-                                        _ => {}
-                                    }
+                                if let Some(attack) = champ.can_attack(now)
+                                    && let AttackAction::Melee { damage, animation } = attack
+                                {
+                                    new_animations.push(animation);
+                                    pending_effects.push((
+                                        Some(*player_id),
+                                        Target::Base(*team),
+                                        vec![GameplayEffect::Damage(damage)],
+                                    ))
                                 }
                             }
                             _ => break,
@@ -436,13 +1114,13 @@ impl GameManager {
 
         // Minion mouvement turn
         self.minion_manager
-            .manage_minions_mouvements(&mut self.board);
-        self.minion_manager.make_wave(&mut self.board);
+            .manage_minions_mouvements(&mut self.board, now);
+        self.minion_manager.make_wave(&mut self.board, now);
         println!(
             "Minions: {} | Minions per wave {} | Tick: {}",
             self.minion_manager.minions.len(),
             self.minion_manager.minions_this_wave,
-            self.tick,
+            self.clock.tick(),
         );
 
         // Adding minion damages dealt
@@ -450,12 +1128,13 @@ impl GameManager {
             &mut self.board,
             &mut new_animations,
             &mut pending_effects,
+            now,
         );
 
         // Monster turn
-        let (monster_effects, monster_animations) = self
-            .monster_manager
-            .update(&mut self.board, &self.champions);
+        let (monster_effects, monster_animations) =
+            self.monster_manager
+                .update(&mut self.board, &self.champions, now);
         pending_effects.extend(
             monster_effects
                 .into_iter()
@@ -466,7 +1145,8 @@ impl GameManager {
         // Tower turn
         // 1. Scan range
         // 2. attack closest enemy
-        self.tower_turn();
+        self.tower_turn(now);
+        self.fountain_turn(now, &mut pending_effects);
 
         let (projectile_effects, projectile_commands) =
             self.projectile_manager.update_and_check_collisions(
@@ -476,20 +1156,18 @@ impl GameManager {
                 &self.towers,
                 &self.monster_manager.active_monsters,
             );
-        pending_effects.extend(
-            projectile_effects
-                .into_iter()
-                .map(|(owner, target, effects)| (Some(owner), target, effects)),
-        );
+        pending_effects.extend(projectile_effects);
         animation_commands_executable.extend(projectile_commands);
 
         // 3. Apply dealt damages
+        // Champion landing the blow that brought each champion to 0 health, if any
+        let mut killing_blows: BTreeMap<PlayerId, Option<PlayerId>> = BTreeMap::new();
         pending_effects
             .into_iter()
             .for_each(|(attacker_id, target, effect)| match target {
                 Target::Tower(id) => {
                     if let Some(tower) = self.towers.get_mut(&id) {
-                        tower.take_effect(effect);
+                        tower.take_effect(effect, now);
                         if tower.is_destroyed() {
                             tower.destroy_tower(&mut self.board);
                             self.towers.remove(&id);
@@ -498,59 +1176,116 @@ impl GameManager {
                 }
                 Target::Minion(id) => {
                     if let Some(minion) = self.minion_manager.minions.get_mut(&id) {
-                        minion.take_effect(effect);
-                        self.handle_minion_death(&id);
+                        minion.take_effect(effect, now);
+                        self.handle_minion_death(&id, attacker_id);
                     }
                 }
                 Target::Champion(id) => {
-                    if let Some(champ) = self.champions.get_mut(&id) {
-                        champ.take_effect(effect);
+                    if let Some(champ) = self.champions.get_mut(&id)
+                        && champ.take_hit(attacker_id, effect, now)
+                    {
+                        killing_blows.insert(id, attacker_id);
                     }
                 }
                 Target::Base(team) => match team {
-                    Team::Red => self.red_base.take_effect(effect),
-                    Team::Blue => self.blue_base.take_effect(effect),
+                    Team::Red => self.red_base.take_effect(effect, now),
+                    Team::Blue => self.blue_base.take_effect(effect, now),
                 },
                 Target::Monster(id) => {
-                    if let Some(..) = self.monster_manager.active_monsters.get_mut(&id) {
-                        if let Some(attacker) = attacker_id {
-                            if let Some(reward) = self
-                                .monster_manager
-                                .apply_effects_to_monster(&id, effect, attacker)
-                            {
-                                monster_rewards.push(reward);
-                            }
-                        }
+                    if self.monster_manager.active_monsters.get_mut(&id).is_some()
+                        && let Some(attacker) = attacker_id
+                        && let Some(reward) = self
+                            .monster_manager
+                            .apply_effects_to_monster(&id, effect, attacker, now)
+                    {
+                        monster_rewards.push(reward);
                     }
                 }
             });
 
+        // Champions brought to 0 health leave the board until they respawn
+        let champion_rules = &self.config.rules.champion_rules;
+        let mut kills = Vec::new();
+        for champion in self.champions.values_mut() {
+            if champion.stats.health == 0 && !champion.is_dead() {
+                let killing_blow = killing_blows.get(&champion.player_id).copied().flatten();
+                let (killer, assists) =
+                    champion.kill_credit(killing_blow, now, champion_rules.assist_window());
+                let bounty = champion_rules.bounty(champion.kill_streak);
+                kills.push((champion.player_id, killer, assists, bounty));
+
+                let respawn_time = champion_rules.respawn_time(champion.level);
+                champion.die(&mut self.board, now + respawn_time);
+                println!(
+                    "Player {} died, respawning in {}s",
+                    champion.player_id,
+                    respawn_time.as_secs()
+                );
+            }
+        }
+        // Pay the killers and assists, then tell everyone
+        for (victim, killer, assists, bounty) in kills {
+            let mut paid = 0;
+            if let Some(champion) = killer.and_then(|id| self.champions.get_mut(&id)) {
+                champion.add_gold(bounty);
+                champion.add_xp(champion_rules.champion_kill_xp);
+                champion.kills += 1;
+                champion.kill_streak += 1;
+                paid = bounty;
+            }
+            for player_id in &assists {
+                if let Some(champion) = self.champions.get_mut(player_id) {
+                    champion.add_gold(champion_rules.assist_gold);
+                    champion.add_xp(champion_rules.assist_xp);
+                    champion.assists += 1;
+                }
+            }
+            println!(
+                "Player {} killed by {:?} for {} gold, assisted by {:?}",
+                victim, killer, paid, assists
+            );
+            self.kill_feed.push(KillPacket::new(
+                killer.map(|id| id as u8),
+                victim as u8,
+                paid,
+                assists.into_iter().map(|id| id as u8).collect(),
+            ));
+        }
+
         // Distribute XP from dead monster
         for (player_id, xp_reward, gold_reward) in monster_rewards.into_iter() {
             if let Some(champion) = self.champions.get_mut(&player_id) {
                 champion.add_xp(xp_reward as u32);
-                champion.add_gold(gold_reward as u16);
+                champion.add_gold(gold_reward);
             }
         }
-        // Distribute XP from dead minions
-        for (minion_row, minion_col, minion_team) in self.dead_minion_positions.drain(..) {
-            let mut champions_in_range = Vec::new();
-            for (_, champion) in self.champions.iter_mut() {
-                // Check if champion is in 5x5 range and is on the opposing team
-                if champion.team_id != minion_team
-                    && (champion.row as i32 - minion_row as i32).abs() <= 2
-                    && (champion.col as i32 - minion_col as i32).abs() <= 2
-                {
-                    champions_in_range.push(champion);
-                }
+        // Distribute XP and gold from dead minions
+        let minion_rules = &self.config.rules.minion_rules;
+        let radius = minion_rules.xp_share_radius as i32;
+        for (minion_row, minion_col, minion_team, last_hitter) in self.dead_minions.drain(..) {
+            // The last hitter takes the gold, even when it died on the same tick
+            if let Some(champion) = last_hitter.and_then(|id| self.champions.get_mut(&id)) {
+                champion.add_gold(minion_rules.minion_kill_gold);
             }
-
-            if !champions_in_range.is_empty() {
-                let xp_per_champion = 5 / champions_in_range.len() as u32;
-                let gold_per_champion = 25 / champions_in_range.len() as u16;
-                for champion in champions_in_range {
-                    champion.add_xp(xp_per_champion);
-                    champion.add_gold(gold_per_champion);
+            // Living enemy champions within the radius share the XP, the last hitter first
+            let mut xp_receivers: Vec<PlayerId> = self
+                .champions
+                .values()
+                .filter(|champion| {
+                    let drow = champion.row as i32 - minion_row as i32;
+                    let dcol = champion.col as i32 - minion_col as i32;
+                    champion.team_id != minion_team
+                        && !champion.is_dead()
+                        && (Some(champion.player_id) == last_hitter
+                            || drow * drow + dcol * dcol <= radius * radius)
+                })
+                .map(|champion| champion.player_id)
+                .collect();
+            xp_receivers.sort_by_key(|player_id| Some(*player_id) != last_hitter);
+            let shares = split_evenly(minion_rules.minion_kill_xp, xp_receivers.len());
+            for (player_id, xp) in xp_receivers.into_iter().zip(shares) {
+                if let Some(champion) = self.champions.get_mut(&player_id) {
+                    champion.add_xp(xp);
                 }
             }
         }
@@ -570,10 +1305,11 @@ impl GameManager {
                 Some((champ.row, champ.col))
             } else if let Some(tower) = self.towers.get(&anim.get_owner_id()) {
                 Some((tower.row, tower.col))
-            } else if let Some(minion) = self.minion_manager.minions.get(&anim.get_owner_id()) {
-                Some((minion.row, minion.col))
             } else {
-                None // Owner might have been removed
+                self.minion_manager
+                    .minions
+                    .get(&anim.get_owner_id())
+                    .map(|minion| (minion.row, minion.col))
             };
 
             if let Some((owner_row, owner_col)) = owner_pos {
@@ -625,83 +1361,52 @@ impl GameManager {
         }
 
         // Check for win condition
-        if self.red_base.stats.health <= 0 {
-            println!("Sending EndGamePacket: Red base destroyed, Blue team wins!");
-            let packet = crate::packet::end_game_packet::EndGamePacket::new(Team::Red);
-            println!("EndGamePacket: {:?}", packet);
-            let serialized_packet = packet.serialize();
-            for (player_id, _) in &self.client_channel {
-                self.send_to_player(*player_id, BytesMut::from(&serialized_packet[..]));
-            }
-            std::process::exit(0);
-        } else if self.blue_base.stats.health <= 0 {
-            println!("Sending EndGamePacket: Blue base destroyed, Red team wins!");
-            let packet = crate::packet::end_game_packet::EndGamePacket::new(Team::Blue);
-            println!("EndGamePacket: {:?}", packet);
-            let serialized_packet = packet.serialize();
-            for (player_id, _) in &self.client_channel {
-                self.send_to_player(*player_id, BytesMut::from(&serialized_packet[..]));
-            }
-            std::process::exit(0);
-        }
-
-        // --- Send per player there board view ---
-        for (player_id, champion) in &self.champions {
-            // 1. Get player-specific board view
-            let board_rle_vec =
-                self.board
-                    .run_length_encode(champion.row, champion.col, &self.minion_manager);
-            // 2. Create the board packet
-            let health = champion.get_health();
-            let xp_needed = champion.xp_for_next_level().unwrap_or(0); // Get XP needed, 0 if max level
-            let board_packet = BoardPacket::new(
-                health.0,
-                health.1,
-                champion.stats.mana,
-                champion.stats.max_mana,
-                champion.level,
-                champion.xp,
-                xp_needed,
-                board_rle_vec,
-            );
-            let serialized_packet = board_packet.serialize();
-            // 3. Store the serialized packet to be sent later
-            updates.insert(*player_id, serialized_packet);
+        let winner = if self.red_base.stats.health == 0 {
+            Some(Team::Blue)
+        } else if self.blue_base.stats.health == 0 {
+            Some(Team::Red)
+        } else {
+            None
+        };
+        if let Some(winner) = winner {
+            println!("Base destroyed, {:?} team wins!", winner);
+            let duration = self
+                .game_start_time
+                .map(|start_time| now.duration_since(start_time))
+                .unwrap_or_default();
+            self.match_result = Some(MatchResult::new(
+                winner,
+                duration,
+                self.champions.values(),
+                &self.usernames,
+            ));
+            self.phase = MatchPhase::Ended;
         }
         println!("--------------------");
-        updates
     }
 
-    fn tower_turn(&mut self) {
+    fn tower_turn(&mut self, now: GameInstant) {
         let mut projectiles_to_create = Vec::new();
 
         for (_, tower) in self.towers.iter_mut() {
-            if let Some(enemy_cell) = tower.get_potential_target(&self.board) {
-                if let Some(enemy_content) = &enemy_cell.content {
-                    let target = match enemy_content {
-                        CellContent::Champion(id, _) => Some(Target::Champion(*id)),
-                        CellContent::Minion(id, _) => Some(Target::Minion(*id)),
-                        _ => None, // Towers can't target other entities
-                    };
+            if let Some(enemy_cell) = tower.get_potential_target(&self.board)
+                && let Some(enemy_content) = &enemy_cell.content
+            {
+                let target = match enemy_content {
+                    CellContent::Champion(id, _) => Some(Target::Champion(*id)),
+                    CellContent::Minion(id, _) => Some(Target::Minion(*id)),
+                    _ => None, // Towers can't target other entities
+                };
 
-                    if let Some(target) = target {
-                        if let Some(attack_action) = tower.can_attack() {
-                            if let AttackAction::Projectile {
-                                damage,
-                                speed,
-                                visual,
-                            } = attack_action
-                            {
-                                projectiles_to_create.push((
-                                    tower.tower_id,
-                                    target,
-                                    damage,
-                                    speed,
-                                    visual,
-                                ));
-                            }
-                        }
-                    }
+                if let Some(target) = target
+                    && let Some(attack_action) = tower.can_attack(now)
+                    && let AttackAction::Projectile {
+                        damage,
+                        speed,
+                        visual,
+                    } = attack_action
+                {
+                    projectiles_to_create.push((tower.tower_id, target, damage, speed, visual));
                 }
             }
         }
@@ -721,15 +1426,549 @@ impl GameManager {
         }
     }
 
-    fn handle_minion_death(&mut self, id: &MinionId) {
-        if let Some(minion) = self.minion_manager.minions.get(id) {
-            if minion.is_dead() {
-                self.dead_minion_positions
-                    .push((minion.row, minion.col, minion.team_id));
-                self.board
-                    .clear_cell(minion.row as usize, minion.col as usize);
-                self.minion_manager.minions.remove(&id);
+    /// Each base heals and restores the mana of allied champions in its fountain, and hurts
+    /// enemy champions standing there.
+    fn fountain_turn(
+        &mut self,
+        now: GameInstant,
+        pending_effects: &mut Vec<(Option<PlayerId>, Target, Vec<GameplayEffect>)>,
+    ) {
+        for base in [&mut self.blue_base, &mut self.red_base] {
+            if !base.fountain_ready(now) {
+                continue;
             }
+            for champion in self.champions.values_mut() {
+                if champion.is_dead() || !base.in_fountain(champion.row, champion.col) {
+                    continue;
+                }
+                if champion.team_id == base.team {
+                    let share = |max: u16| {
+                        (max as u32 * base.fountain_heal_percent as u32 / 100).min(u16::MAX as u32)
+                            as u16
+                    };
+                    champion.regenerate(
+                        share(champion.stats.max_health),
+                        share(champion.stats.max_mana),
+                    );
+                } else {
+                    pending_effects.push((
+                        None,
+                        Target::Champion(champion.player_id),
+                        vec![GameplayEffect::Damage(base.fountain_damage)],
+                    ));
+                }
+            }
+        }
+    }
+
+    fn handle_minion_death(&mut self, id: &MinionId, attacker_id: Option<PlayerId>) {
+        if let Some(minion) = self.minion_manager.minions.get(id)
+            && minion.is_dead()
+        {
+            self.dead_minions
+                .push((minion.row, minion.col, minion.team_id, attacker_id));
+            self.board
+                .clear_cell(minion.row as usize, minion.col as usize);
+            self.minion_manager.minions.remove(id);
+        }
+    }
+}
+
+/// Splits `total` into `parts` shares differing by at most one, the larger ones first.
+fn split_evenly(total: u32, parts: usize) -> impl Iterator<Item = u32> {
+    let parts = parts.max(1) as u32;
+    (0..parts).map(move |i| total / parts + u32::from(i < total % parts))
+}
+
+/// Neutral monster definitions, moved to the camps the map assigns them.
+fn monster_definitions(config: &GameConfig, board: &Board) -> Vec<MonsterStats> {
+    config
+        .neutral_monsters
+        .iter()
+        .cloned()
+        .map(|mut monster| {
+            if let Some(camp) = board.map_layout.camp_of(&monster.id) {
+                monster.spawn_row = camp.row;
+                monster.spawn_col = camp.col;
+            }
+            monster
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use map_generator::{GeneratorParams, generate};
+
+    fn create_manager(max_players: u8) -> GameManager {
+        let mut config =
+            GameConfig::load("stats.toml", "spells.toml", "items.toml", "rules.toml").unwrap();
+        config.rules.timing_rules.game_tick_ms = 500;
+        config.rules.timing_rules.countdown_secs = 1;
+        config.rules.timing_rules.pause_timeout_secs = 2;
+        config.rules.timing_rules.reconnect_grace_secs = 1;
+        let layout = generate(&GeneratorParams {
+            seed: 1,
+            size: 60,
+            lane_width: 4,
+            bush_density: 0.1,
+            wall_complexity: 0.3,
+            camps: Vec::new(),
+        })
+        .unwrap();
+        GameManager::new(config, Board::from_layout(layout, "map.json"), max_players)
+    }
+
+    #[test]
+    fn test_countdown_starts_the_match() {
+        let mut manager = create_manager(2);
+        manager.add_player(0, 0, None);
+        manager.game_tick();
+        assert_eq!(manager.phase(), &MatchPhase::Lobby);
+
+        manager.add_player(0, 0, None);
+        assert!(matches!(manager.phase(), MatchPhase::Countdown { .. }));
+        manager.game_tick();
+        assert!(manager.game_start_time.is_none());
+        manager.game_tick();
+        assert_eq!(manager.phase(), &MatchPhase::InProgress);
+        assert!(manager.game_start_time.is_some());
+        // Nobody can join a running match
+        assert_eq!(manager.add_player(0, 0, None), None);
+    }
+
+    #[test]
+    fn test_leaving_during_countdown_returns_to_lobby() {
+        let mut manager = create_manager(2);
+        manager.add_player(0, 0, None);
+        manager.add_player(0, 0, None);
+        manager.remove_player(&2);
+        assert_eq!(manager.phase(), &MatchPhase::Lobby);
+    }
+
+    #[test]
+    fn test_pause_resumes_after_votes() {
+        let mut manager = create_manager(2);
+        manager.add_player(0, 0, None);
+        manager.add_player(0, 0, None);
+        manager.game_tick();
+        manager.game_tick();
+
+        manager.request_pause(1);
+        assert!(matches!(manager.phase(), MatchPhase::Paused { .. }));
+        let clock_before = manager.clock.now();
+        manager.game_tick();
+        assert_eq!(manager.clock.now(), clock_before);
+
+        manager.vote_resume(1);
+        manager.vote_resume(1);
+        assert_eq!(manager.phase().resume_votes(), 1);
+        manager.vote_resume(2);
+        assert!(matches!(manager.phase(), MatchPhase::Countdown { .. }));
+    }
+
+    #[test]
+    fn test_pause_does_not_resume_once_everyone_left() {
+        let mut manager = create_manager(2);
+        manager.add_player(0, 0, None);
+        manager.add_player(0, 0, None);
+        manager.game_tick();
+        manager.game_tick();
+
+        manager.request_pause(1);
+        manager.vote_resume(1);
+        manager.remove_player(&1);
+        manager.remove_player(&2);
+        assert_eq!(manager.players_count, 0);
+        assert!(matches!(manager.phase(), MatchPhase::Paused { .. }));
+    }
+
+    #[test]
+    fn test_disconnect_pauses_until_timeout() {
+        let mut manager = create_manager(2);
+        manager.add_player(0, 0, None);
+        manager.add_player(0, 0, None);
+        manager.game_tick();
+        manager.game_tick();
+
+        manager.remove_player(&2);
+        assert!(matches!(manager.phase(), MatchPhase::Paused { .. }));
+        for _ in 0..4 {
+            manager.game_tick();
+        }
+        assert!(matches!(manager.phase(), MatchPhase::Countdown { .. }));
+    }
+
+    #[test]
+    fn test_phase_packet_sent_on_change_only() {
+        let mut manager = create_manager(1);
+        let (tx, _rx) = mpsc::channel(4);
+        manager.client_channel.insert(1, tx);
+        manager.add_player(0, 0, None);
+
+        let first = manager.game_tick();
+        assert!(matches!(first[&1][0], ServerPacket::Phase(_)));
+        manager.game_tick();
+        let running = manager.game_tick();
+        // Only the board update once the phase is stable
+        assert!(matches!(running[&1][..], [ServerPacket::Board(_)]));
+    }
+
+    #[test]
+    fn test_reconnect_keeps_the_champion() {
+        let mut manager = create_manager(2);
+        manager.add_player(0, 0, None);
+        manager.add_player(0, 0, None);
+        manager.game_tick();
+        manager.game_tick();
+        manager.get_mut_champion(&2).unwrap().gold = 321;
+
+        let token = manager.reconnect_token_of(2).unwrap();
+        manager.remove_player(&2);
+        assert_eq!(manager.reconnect_player(token + 1), None);
+        assert_eq!(manager.reconnect_player(token), Some(2));
+        assert_eq!(manager.get_champion(&2).unwrap().gold, 321);
+        assert_eq!(manager.players_count, 2);
+
+        manager.vote_resume(1);
+        manager.vote_resume(2);
+        assert!(matches!(manager.phase(), MatchPhase::Countdown { .. }));
+    }
+
+    #[test]
+    fn test_abandoned_lobby_slot_is_freed() {
+        let mut manager = create_manager(2);
+        manager.add_player(0, 0, None);
+        let token = manager.reconnect_token_of(1).unwrap();
+        manager.remove_player(&1);
+        // The slot stays reserved during the grace window
+        manager.game_tick();
+        assert_eq!(manager.add_player(0, 0, None), Some(2));
+        manager.game_tick();
+
+        assert_eq!(manager.reconnect_player(token), None);
+        assert!(manager.get_champion(&1).is_none());
+        assert_eq!(manager.add_player(0, 0, None), Some(1));
+        assert!(matches!(manager.phase(), MatchPhase::Countdown { .. }));
+    }
+
+    #[test]
+    fn test_account_holds_one_slot() {
+        let mut manager = create_manager(2);
+        assert_eq!(manager.add_player(0, 0, Some("alice".to_string())), Some(1));
+        assert_eq!(manager.add_player(0, 0, Some("alice".to_string())), None);
+        assert_eq!(manager.add_player(0, 0, None), Some(2));
+        assert_eq!(manager.username_of(1), "alice");
+        assert_eq!(manager.username_of(2), "player2");
+    }
+
+    #[test]
+    fn test_join_is_refused_without_a_free_spawn() {
+        let mut manager = create_manager(12);
+        let blue_spawns = manager.board.map_layout.spawns_of(Team::Blue).len();
+        for player_id in 1..=blue_spawns * 2 {
+            assert_eq!(manager.add_player(0, 0, None), Some(player_id));
+        }
+        // The next blue player would share a spawn point with a teammate
+        assert_eq!(manager.add_player(0, 0, None), None);
+        assert_eq!(manager.champions.len(), blue_spawns * 2);
+    }
+
+    #[test]
+    fn test_roster_binds_accounts_to_slots() {
+        let mut manager = create_manager(1);
+        manager.set_roster(Roster::new(
+            vec![
+                roster::RosterEntry::parse("alice:red:1").unwrap(),
+                roster::RosterEntry::parse("bob:red:0").unwrap(),
+            ],
+            "roster.json",
+        ));
+
+        assert_eq!(manager.add_player(0, 0, Some("mallory".to_string())), None);
+        assert_eq!(manager.add_player(0, 0, None), None);
+        assert_eq!(manager.add_player(0, 0, Some("bob".to_string())), Some(2));
+        assert_eq!(manager.add_player(0, 0, Some("alice".to_string())), Some(1));
+
+        let red_spawns = manager.board.map_layout.spawns_of(Team::Red);
+        let alice = manager.get_champion(&1).unwrap();
+        assert_eq!(alice.team_id, Team::Red);
+        assert_eq!(
+            (alice.row, alice.col),
+            (red_spawns[1].row, red_spawns[1].col)
+        );
+        assert!(matches!(manager.phase(), MatchPhase::Countdown { .. }));
+    }
+
+    #[test]
+    fn test_tracked_player_gets_deltas_after_ack() {
+        let mut manager = create_manager(1);
+        manager.add_player(0, 0, None);
+        manager.track_frames(1, true);
+
+        let first = manager.game_tick();
+        let Some(ServerPacket::Frame(keyframe)) = first[&1].last() else {
+            panic!("expected a frame packet");
+        };
+        assert_eq!(keyframe.base_tick, 0);
+        let acked = keyframe.tick;
+        manager.ack_frame(1, acked);
+
+        let next = manager.game_tick();
+        let Some(ServerPacket::Frame(delta)) = next[&1].last() else {
+            panic!("expected a frame packet");
+        };
+        assert_eq!(delta.base_tick, acked);
+
+        // A new connection starts again from a keyframe
+        manager.track_frames(1, true);
+        let again = manager.game_tick();
+        assert!(matches!(
+            again[&1].last(),
+            Some(ServerPacket::Frame(FramePacket { base_tick: 0, .. }))
+        ));
+    }
+
+    #[test]
+    fn test_viewport_changes_frame_size() {
+        let mut manager = create_manager(1);
+        manager.add_player(0, 0, None);
+        manager.track_frames(1, true);
+        manager.game_tick();
+
+        let viewport = manager.set_viewport(1, 200, 30);
+        assert_eq!(viewport, Viewport { rows: 59, cols: 29 });
+        let updates = manager.game_tick();
+        let Some(ServerPacket::Frame(frame)) = updates[&1].last() else {
+            panic!("expected a frame packet");
+        };
+        // A resize restarts from a keyframe of the new size
+        assert_eq!(frame.base_tick, 0);
+        let view = rle::decode_binary(&frame.board.encoded_board).unwrap();
+        assert_eq!(view.len(), viewport.len());
+    }
+
+    #[test]
+    fn test_dead_champion_respawns_at_spawn() {
+        let mut manager = create_manager(1);
+        manager.add_player(0, 0, None);
+        manager.game_tick();
+        manager.game_tick();
+        let champion = manager.get_mut_champion(&1).unwrap();
+        let spawn = (champion.row as usize, champion.col as usize);
+        champion.stats.health = 0;
+
+        let updates = manager.game_tick();
+        let Some(ServerPacket::Board(packet)) = updates[&1].last() else {
+            panic!("expected a board packet");
+        };
+        // 10s at level 1, counted down in whole seconds
+        assert_eq!(packet.respawn_in, 10);
+        assert!(manager.get_champion(&1).unwrap().is_dead());
+        assert!(
+            manager
+                .board
+                .get_cell(spawn.0, spawn.1)
+                .unwrap()
+                .content
+                .is_none()
+        );
+
+        for _ in 0..20 {
+            manager.game_tick();
+        }
+        let champion = manager.get_champion(&1).unwrap();
+        assert!(!champion.is_dead());
+        assert_eq!((champion.row as usize, champion.col as usize), spawn);
+        assert_eq!(champion.stats.health, champion.stats.max_health);
+    }
+
+    #[test]
+    fn test_fountain_heals_allies_and_hurts_enemies() {
+        let mut manager = create_manager(2);
+        manager.add_player(0, 0, None);
+        manager.add_player(0, 0, None);
+        manager.game_tick();
+        manager.game_tick();
+
+        // Player 1 is Blue and waits on its spawn, player 2 walks into the Blue base
+        let ally = manager.get_mut_champion(&1).unwrap();
+        ally.stats.health = 50;
+        ally.stats.mana = 0;
+        // Only the fountain heals here
+        (ally.health_regen, ally.mana_regen) = (0, 0);
+        let base = &manager.blue_base;
+        let corner = (base.position.0 as u16, base.position.1 as u16);
+        let (row, col) = base.spawn_cell(&manager.board, corner).unwrap();
+        let enemy = manager.champions.get_mut(&2).unwrap();
+        manager.board.move_cell(
+            enemy.row as usize,
+            enemy.col as usize,
+            row as usize,
+            col as usize,
+        );
+        (enemy.row, enemy.col) = (row, col);
+
+        // The fountain pulses once a second, two ticks of 500ms
+        manager.game_tick();
+        manager.game_tick();
+        let ally = manager.get_champion(&1).unwrap();
+        assert_eq!(ally.stats.health, 50 + ally.stats.max_health / 10);
+        assert_eq!(ally.stats.mana, ally.stats.max_mana / 10);
+        let enemy = manager.get_champion(&2).unwrap();
+        assert!(enemy.stats.health < enemy.stats.max_health);
+    }
+
+    #[test]
+    fn test_kill_pays_the_bounty_and_reaches_the_feed() {
+        let mut manager = create_manager(2);
+        for player_id in [1, 2] {
+            let (tx, _rx) = mpsc::channel(4);
+            manager.client_channel.insert(player_id, tx);
+        }
+        manager.add_player(0, 0, None);
+        manager.add_player(0, 0, None);
+        manager.game_tick();
+        manager.game_tick();
+
+        let now = manager.clock.now();
+        manager.get_mut_champion(&2).unwrap().kill_streak = 2;
+        let victim = manager.get_mut_champion(&1).unwrap();
+        victim.kill_streak = 1;
+        victim.record_damage(2, now);
+        victim.stats.health = 0;
+        let updates = manager.game_tick();
+
+        // Nobody landed the last blow, the last champion to hit gets the kill
+        let rules = &manager.config.rules.champion_rules;
+        let bounty = rules.bounty(1);
+        let killer = manager.get_champion(&2).unwrap();
+        assert_eq!(killer.gold, bounty);
+        assert_eq!((killer.kills, killer.kill_streak), (1, 3));
+        let victim = manager.get_champion(&1).unwrap();
+        assert_eq!((victim.deaths, victim.kill_streak), (1, 0));
+        for player_id in [1, 2] {
+            assert!(updates[&player_id].iter().any(|packet| matches!(
+                packet,
+                ServerPacket::Kill(kill) if *kill == KillPacket::new(Some(2), 1, bounty, vec![])
+            )));
+        }
+    }
+
+    #[test]
+    fn test_split_evenly_loses_nothing() {
+        assert_eq!(split_evenly(7, 3).collect::<Vec<_>>(), vec![3, 2, 2]);
+        assert_eq!(split_evenly(2, 3).collect::<Vec<_>>(), vec![1, 1, 0]);
+        assert_eq!(split_evenly(5, 0).collect::<Vec<_>>(), vec![5]);
+    }
+
+    #[test]
+    fn test_minion_gold_goes_to_the_last_hitter() {
+        let mut manager = create_manager(3);
+        for _ in 0..3 {
+            manager.add_player(0, 0, None);
+        }
+        manager.game_tick();
+        manager.game_tick();
+
+        // A red minion dies in the red base, far from both blue champions
+        let red = manager.get_champion(&2).unwrap();
+        let (row, col) = (red.row, red.col);
+        let radius = manager.config.rules.minion_rules.xp_share_radius as i32;
+        for player_id in [1, 3] {
+            let blue = manager.get_champion(&player_id).unwrap();
+            assert_eq!(blue.team_id, Team::Blue);
+            let (drow, dcol) = (blue.row as i32 - row as i32, blue.col as i32 - col as i32);
+            assert!(drow * drow + dcol * dcol > radius * radius);
+        }
+        manager.dead_minions.push((row, col, Team::Red, Some(1)));
+        manager.game_tick();
+
+        // The last hitter gets all the gold and XP, the ally out of range nothing
+        let rules = &manager.config.rules.minion_rules;
+        let killer = manager.get_champion(&1).unwrap();
+        assert_eq!(
+            (killer.gold, killer.xp),
+            (rules.minion_kill_gold, rules.minion_kill_xp)
+        );
+        let ally = manager.get_champion(&3).unwrap();
+        assert_eq!((ally.gold, ally.xp), (0, 0));
+        let enemy = manager.get_champion(&2).unwrap();
+        assert_eq!((enemy.gold, enemy.xp), (0, 0));
+    }
+
+    #[test]
+    fn test_spectators_do_not_take_player_slots() {
+        let mut manager = create_manager(1);
+        assert_eq!(
+            manager.add_spectator(SpectatorVision::Team(Team::Blue)),
+            None
+        );
+
+        manager.allow_spectators(1, false);
+        assert_eq!(manager.add_spectator(SpectatorVision::Full), None);
+        let spectator = manager
+            .add_spectator(SpectatorVision::Team(Team::Red))
+            .unwrap();
+        assert!(spectator >= FIRST_SPECTATOR_ID);
+        assert_eq!(
+            manager.add_spectator(SpectatorVision::Team(Team::Red)),
+            None
+        );
+
+        // The only player slot is still free and filling it starts the match
+        assert_eq!(manager.add_player(0, 0, None), Some(1));
+        assert!(matches!(manager.phase(), MatchPhase::Countdown { .. }));
+
+        manager.set_camera(spectator, Camera::Follow(1));
+        let updates = manager.game_tick();
+        assert!(matches!(
+            updates[&spectator].last(),
+            Some(ServerPacket::Board(_))
+        ));
+        let champion = manager.get_champion(&1).unwrap();
+        let position = (champion.row, champion.col);
+        let watched = &manager.spectators[&spectator];
+        assert_eq!((watched.row, watched.col), position);
+
+        manager.remove_spectator(spectator);
+        assert!(!manager.is_spectator(spectator));
+        assert!(
+            manager
+                .add_spectator(SpectatorVision::Team(Team::Blue))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_spectators_are_told_the_match_ended() {
+        let mut manager = create_manager(1);
+        manager.allow_spectators(3, true);
+        let blue = manager
+            .add_spectator(SpectatorVision::Team(Team::Blue))
+            .unwrap();
+        let red = manager
+            .add_spectator(SpectatorVision::Team(Team::Red))
+            .unwrap();
+        let full = manager.add_spectator(SpectatorVision::Full).unwrap();
+        manager.add_player(0, 0, None);
+        manager.game_tick();
+        manager.game_tick();
+        assert_eq!(manager.phase(), &MatchPhase::InProgress);
+
+        manager.red_base.stats.health = 0;
+        let updates = manager.game_tick();
+        assert_eq!(manager.phase(), &MatchPhase::Ended);
+        for (client_id, expected_win) in [(1, true), (blue, true), (red, false), (full, false)] {
+            assert!(
+                matches!(
+                    updates[&client_id].last(),
+                    Some(ServerPacket::EndGame(packet)) if packet.win == expected_win
+                ),
+                "client {} should get the end of the match",
+                client_id
+            );
         }
     }
 }
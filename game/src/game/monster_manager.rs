@@ -1,19 +1,20 @@
 use crate::config::MonsterStats;
 use crate::game::entities::monster::Monster;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use super::algorithms::pathfinding::{find_path_on_board, is_adjacent_to_goal};
 use super::animation::AnimationTrait;
 use super::cell::MonsterId;
+use super::clock::GameInstant;
 use super::entities::monster::MonsterState;
 use super::entities::projectile::GameplayEffect;
 use super::entities::{AttackAction, Fighter, Target};
 use super::{Board, CellContent, Champion, PlayerId};
 
 pub struct MonsterManager {
-    pub monster_definitions: HashMap<String, MonsterStats>,
+    pub monster_definitions: BTreeMap<String, MonsterStats>,
 
-    pub active_monsters: HashMap<usize, Monster>,
+    pub active_monsters: BTreeMap<usize, Monster>,
     next_instance_id: MonsterId,
 }
 
@@ -25,11 +26,26 @@ impl MonsterManager {
             .collect();
         MonsterManager {
             monster_definitions,
-            active_monsters: HashMap::new(),
+            active_monsters: BTreeMap::new(),
             next_instance_id: 1,
         }
     }
 
+    /// Replaces the monster definitions used for future spawns, optionally rescaling live monsters.
+    pub fn set_definitions(&mut self, monsters: Vec<MonsterStats>, rescale_live: bool) {
+        self.monster_definitions = monsters
+            .into_iter()
+            .map(|monster| (monster.id.clone(), monster))
+            .collect();
+        if rescale_live {
+            for monster in self.active_monsters.values_mut() {
+                if let Some(monster_def) = self.monster_definitions.get(&monster.monster_id) {
+                    monster.rescale(monster_def);
+                }
+            }
+        }
+    }
+
     pub fn spawn_monster(&mut self, name_id: &str, board: &mut Board) {
         if let Some(monster_def) = self.monster_definitions.get(name_id) {
             let monster = Monster::new(self.next_instance_id, monster_def.clone());
@@ -40,8 +56,6 @@ impl MonsterManager {
             );
             self.active_monsters.insert(self.next_instance_id, monster);
             self.next_instance_id += 1;
-        } else {
-            ()
         }
     }
 
@@ -57,9 +71,10 @@ impl MonsterManager {
         monster_id: &MonsterId,
         effects: Vec<GameplayEffect>,
         player_id: PlayerId,
+        now: GameInstant,
     ) -> Option<(PlayerId, u8, u16)> {
         if let Some(monster) = self.active_monsters.get_mut(monster_id) {
-            monster.take_effect(effects);
+            monster.take_effect(effects, now);
             monster.attach_target(player_id);
             if monster.stats.health == 0 {
                 let monster_def = self.monster_definitions.get(&monster.monster_id).unwrap();
@@ -69,10 +84,12 @@ impl MonsterManager {
         None
     }
 
+    #[allow(clippy::type_complexity)]
     pub fn update(
         &mut self,
         board: &mut Board,
-        champions: &HashMap<PlayerId, Champion>,
+        champions: &BTreeMap<PlayerId, Champion>,
+        now: GameInstant,
     ) -> (
         Vec<(Target, Vec<GameplayEffect>)>,
         Vec<Box<dyn AnimationTrait>>,
@@ -86,7 +103,10 @@ impl MonsterManager {
                 MonsterState::Aggro => {
                     //  First we ensure the monster has a valid target champion.
                     if let Some(champion_id) = monster.target_champion_id {
-                        if let Some(champion) = champions.get(&champion_id) {
+                        if let Some(champion) = champions
+                            .get(&champion_id)
+                            .filter(|champion| !champion.is_dead())
+                        {
                             // 1. We check leash range
                             let delta_row = monster.row.abs_diff(monster.spawn_row);
                             let delta_col = monster.col.abs_diff(monster.spawn_col);
@@ -101,15 +121,14 @@ impl MonsterManager {
                                 (monster.row, monster.col),
                                 (champion.row, champion.col),
                             ) {
-                                if let Some(attack_action) = monster.can_attack() {
-                                    if let AttackAction::Melee { damage, animation } = attack_action
-                                    {
-                                        new_animations.push(animation);
-                                        pending_damages.push((
-                                            Target::Champion(champion_id),
-                                            vec![GameplayEffect::Damage(damage)],
-                                        ));
-                                    }
+                                if let Some(attack_action) = monster.can_attack(now)
+                                    && let AttackAction::Melee { damage, animation } = attack_action
+                                {
+                                    new_animations.push(animation);
+                                    pending_damages.push((
+                                        Target::Champion(champion_id),
+                                        vec![GameplayEffect::Damage(damage)],
+                                    ));
                                 }
                             } else {
                                 if monster.path.is_none() {
@@ -137,7 +156,7 @@ impl MonsterManager {
                                 }
                             }
                         } else {
-                            // Target champion doesn't exist anymore
+                            // Target champion is gone or dead
                             monster.start_returning(board);
                         }
                     }
@@ -173,7 +192,7 @@ impl MonsterManager {
                 }
                 MonsterState::Dead => {
                     board.clear_cell(monster.row as usize, monster.col as usize);
-                    if monster.can_respawn() {
+                    if monster.can_respawn(now) {
                         dead_monster.push((monster.id, monster.monster_id.clone()));
                     }
                 }
@@ -183,12 +202,14 @@ impl MonsterManager {
             self.active_monsters.remove(id);
             self.spawn_monster(monster_id, board);
         }
-        return (pending_damages, new_animations);
+        (pending_damages, new_animations)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use crate::{
         config::ChampionStats,
         game::{Board, Champion, cell::Team, entities::monster::MonsterState},
@@ -230,6 +251,10 @@ mod tests {
             level_up_health_increase: 20,
             level_up_attack_damage_increase: 5,
             level_up_armor_increase: 2,
+            health_regen: 2,
+            mana_regen: 3,
+            level_up_health_regen_increase: 1,
+            level_up_mana_regen_increase: 1,
             attack_range_row: 3,
             attack_range_col: 3,
         }
@@ -288,10 +313,10 @@ mod tests {
             "Should be one active monster"
         );
         // Check that the cellcontent got correctly set
-        if let Some(cell) = board.get_cell(10, 10) {
-            if let Some(content) = &cell.content {
-                assert_eq!(CellContent::Monster(1), *content)
-            }
+        if let Some(cell) = board.get_cell(10, 10)
+            && let Some(content) = &cell.content
+        {
+            assert_eq!(CellContent::Monster(1), *content)
         }
 
         // Check that the next ID has been incremented
@@ -339,7 +364,7 @@ mod tests {
 
         // Apply damage effect
         let effects = vec![GameplayEffect::Damage(30)];
-        manager.apply_effects_to_monster(&monster_id, effects, attacker_id);
+        manager.apply_effects_to_monster(&monster_id, effects, attacker_id, GameInstant::ZERO);
 
         // Get the monster to check its new state
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -362,7 +387,12 @@ mod tests {
         let attacker_2 = 99; // Second attacker
 
         // First attack sets the aggro
-        manager.apply_effects_to_monster(&monster_id, vec![GameplayEffect::Damage(10)], attacker_1);
+        manager.apply_effects_to_monster(
+            &monster_id,
+            vec![GameplayEffect::Damage(10)],
+            attacker_1,
+            GameInstant::ZERO,
+        );
         let monster = manager.active_monsters.get(&monster_id).unwrap();
         assert_eq!(
             monster.target_champion_id,
@@ -372,7 +402,12 @@ mod tests {
         assert_eq!(monster.stats.health, 90);
 
         // Second attack from a different champion
-        manager.apply_effects_to_monster(&monster_id, vec![GameplayEffect::Damage(10)], attacker_2);
+        manager.apply_effects_to_monster(
+            &monster_id,
+            vec![GameplayEffect::Damage(10)],
+            attacker_2,
+            GameInstant::ZERO,
+        );
         let monster = manager.active_monsters.get(&monster_id).unwrap();
 
         // Verify health is reduced, but target remains unchanged
@@ -394,11 +429,11 @@ mod tests {
         let monster_id = 1;
         let attacker_id = 42;
 
-        let mut champions = HashMap::new();
+        let mut champions = BTreeMap::new();
         champions.insert(attacker_id, create_champion(15, 15)); // Champion position is irrelevant for the leash calculation itself
 
         // Make the monster aggro
-        manager.apply_effects_to_monster(&monster_id, vec![], attacker_id);
+        manager.apply_effects_to_monster(&monster_id, vec![], attacker_id, GameInstant::ZERO);
 
         // Manually move the monster far from its spawn point to simulate it being kited
         let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
@@ -411,7 +446,7 @@ mod tests {
         );
 
         // Call the update loop
-        manager.update(&mut board, &champions);
+        manager.update(&mut board, &champions, GameInstant::ZERO);
 
         // Verify the monster is now returning because it's too far from its spawn
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -436,18 +471,18 @@ mod tests {
         let monster_id = 1;
         let attacker_id = 42;
 
-        let mut champions = HashMap::new();
+        let mut champions = BTreeMap::new();
         // Place champion within leash range but outside attack range
         champions.insert(attacker_id, create_champion(15, 10));
 
         // Make monster aggro
-        manager.apply_effects_to_monster(&monster_id, vec![], attacker_id);
+        manager.apply_effects_to_monster(&monster_id, vec![], attacker_id, GameInstant::ZERO);
         let monster = manager.active_monsters.get(&monster_id).unwrap();
         let initial_pos = (monster.row, monster.col);
         assert_eq!(initial_pos, (10, 10));
 
         // Call the update loop
-        manager.update(&mut board, &champions);
+        manager.update(&mut board, &champions, GameInstant::ZERO);
 
         // Verify the monster has moved one step towards the champion
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -471,18 +506,18 @@ mod tests {
         let monster_id = 1;
         let attacker_id = 42;
 
-        let mut champions = HashMap::new();
+        let mut champions = BTreeMap::new();
         // Place champion right next to the monster
         champions.insert(attacker_id, create_champion(10, 11));
 
-        // Make monster aggro and expire its attack cooldown so it can attack immediately
-        manager.apply_effects_to_monster(&monster_id, vec![], attacker_id);
-        let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
-        monster.last_attacked = std::time::Instant::now() - std::time::Duration::from_secs(5);
+        // Make monster aggro and move time past its attack cooldown so it can attack immediately
+        manager.apply_effects_to_monster(&monster_id, vec![], attacker_id, GameInstant::ZERO);
+        let monster = manager.active_monsters.get(&monster_id).unwrap();
+        let now = GameInstant::from_millis(5000);
         let initial_pos = (monster.row, monster.col);
 
         // Call the update loop
-        let (pending_effects, animation) = manager.update(&mut board, &mut champions);
+        let (pending_effects, animation) = manager.update(&mut board, &champions, now);
 
         // Verify the monster did NOT move
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -513,7 +548,7 @@ mod tests {
         manager.spawn_monster("wolf_red", &mut board);
         let monster_id = 1;
 
-        let mut champions = HashMap::new();
+        let champions = BTreeMap::new();
 
         // Manually put the monster in a returning state from a different position
         let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
@@ -524,7 +559,7 @@ mod tests {
         let initial_pos = (monster.row, monster.col);
 
         // Call the update loop
-        manager.update(&mut board, &mut champions);
+        manager.update(&mut board, &champions, GameInstant::ZERO);
 
         // Verify the monster has moved one step towards its spawn diagonally
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -545,7 +580,7 @@ mod tests {
         manager.spawn_monster("wolf_red", &mut board);
         let monster_id = 1;
 
-        let mut champions = HashMap::new();
+        let champions = BTreeMap::new();
 
         // Manually put the monster in a returning state, right next to its spawn
         // We create a scope for the mutable borrow
@@ -556,12 +591,12 @@ mod tests {
             monster.stats.health = 50; // Make sure it needs healing
             monster.start_returning(&board);
         }
-        manager.update(&mut board, &champions);
+        manager.update(&mut board, &champions, GameInstant::ZERO);
         let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
         assert_eq!(monster.state, MonsterState::Returning);
 
         // Call the update loop
-        manager.update(&mut board, &mut champions);
+        manager.update(&mut board, &champions, GameInstant::ZERO);
 
         // Verify the monster has been reset
         let monster = manager.active_monsters.get(&monster_id).unwrap();
@@ -593,24 +628,24 @@ mod tests {
         manager.spawn_monster("wolf_red", &mut board);
         let monster_id = 1;
 
-        let champions = HashMap::new();
+        let champions = BTreeMap::new();
 
         // Manually kill the monster and set its death time to be in the past
         // to ensure its `can_respawn()` method will return true.
         let monster = manager.active_monsters.get_mut(&monster_id).unwrap();
         monster.state = MonsterState::Dead;
         let respawn_duration = monster.respawn_timer;
-        monster.death_time =
-            Some(std::time::Instant::now() - respawn_duration - std::time::Duration::from_secs(1));
+        monster.death_time = Some(GameInstant::ZERO);
+        let now = GameInstant::ZERO + respawn_duration + std::time::Duration::from_secs(1);
 
         let next_id = manager.next_instance_id;
 
         // Call the update loop
-        manager.update(&mut board, &champions);
+        manager.update(&mut board, &champions, now);
 
         // The old monster should be gone, and a new one should exist.
         assert!(
-            manager.active_monsters.get(&monster_id).is_none(),
+            !manager.active_monsters.contains_key(&monster_id),
             "Old monster should be removed"
         );
         assert_eq!(
@@ -0,0 +1,116 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use super::cell::PlayerId;
+
+/// Lifecycle of a match, advanced by `GameManager::game_tick`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchPhase {
+    /// Waiting for every player to join.
+    Lobby,
+    /// Board is shown but frozen until the countdown runs out.
+    Countdown {
+        remaining: Duration,
+    },
+    InProgress,
+    /// Frozen until every connected player votes to resume or the timeout runs out.
+    Paused {
+        remaining: Duration,
+        resume_votes: HashSet<PlayerId>,
+    },
+    Ended,
+}
+
+impl MatchPhase {
+    pub fn paused(timeout: Duration) -> Self {
+        MatchPhase::Paused {
+            remaining: timeout,
+            resume_votes: HashSet::new(),
+        }
+    }
+
+    /// Code sent to clients in the `PhasePacket`.
+    pub fn code(&self) -> u8 {
+        match self {
+            MatchPhase::Lobby => 0,
+            MatchPhase::Countdown { .. } => 1,
+            MatchPhase::InProgress => 2,
+            MatchPhase::Paused { .. } => 3,
+            MatchPhase::Ended => 4,
+        }
+    }
+
+    /// Whole seconds left before the phase times out, rounded up.
+    pub fn seconds_left(&self) -> u16 {
+        match self {
+            MatchPhase::Countdown { remaining } | MatchPhase::Paused { remaining, .. } => {
+                remaining.as_millis().div_ceil(1000).min(u16::MAX as u128) as u16
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn resume_votes(&self) -> usize {
+        match self {
+            MatchPhase::Paused { resume_votes, .. } => resume_votes.len(),
+            _ => 0,
+        }
+    }
+
+    /// Counts down the timed phases, returns true once the timer has run out.
+    pub fn elapse(&mut self, elapsed: Duration) -> bool {
+        match self {
+            MatchPhase::Countdown { remaining } | MatchPhase::Paused { remaining, .. } => {
+                *remaining = remaining.saturating_sub(elapsed);
+                remaining.is_zero()
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether players can see the board, even if it is frozen.
+    pub fn shows_board(&self) -> bool {
+        matches!(
+            self,
+            MatchPhase::Countdown { .. } | MatchPhase::InProgress | MatchPhase::Paused { .. }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_elapses() {
+        let mut phase = MatchPhase::Countdown {
+            remaining: Duration::from_millis(1500),
+        };
+        assert_eq!(phase.seconds_left(), 2);
+        assert!(!phase.elapse(Duration::from_millis(600)));
+        assert_eq!(phase.seconds_left(), 1);
+        assert!(phase.elapse(Duration::from_millis(1000)));
+        assert_eq!(phase.seconds_left(), 0);
+    }
+
+    #[test]
+    fn test_untimed_phases_never_elapse() {
+        for mut phase in [MatchPhase::Lobby, MatchPhase::InProgress, MatchPhase::Ended] {
+            assert!(!phase.elapse(Duration::from_secs(3600)));
+            assert_eq!(phase.seconds_left(), 0);
+        }
+    }
+
+    #[test]
+    fn test_pause_counts_votes() {
+        let mut phase = MatchPhase::paused(Duration::from_secs(60));
+        if let MatchPhase::Paused { resume_votes, .. } = &mut phase {
+            resume_votes.insert(1);
+            resume_votes.insert(1);
+            resume_votes.insert(2);
+        }
+        assert_eq!(phase.resume_votes(), 2);
+        assert_eq!(phase.code(), 3);
+        assert!(phase.shows_board());
+    }
+}
@@ -9,50 +9,53 @@ use super::entities::projectile::{GameplayEffect, PathingLogic, Projectile};
 use super::entities::tower::Tower;
 use super::spell::ProjectileBlueprint;
 use super::{Board, CellContent, Champion, MinionId, PlayerId, TowerId};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
+#[derive(Default)]
 pub struct ProjectileManager {
-    pub projectiles: HashMap<u64, Projectile>,
+    pub projectiles: BTreeMap<u64, Projectile>,
     next_projectile_id: u64,
 }
 
 impl ProjectileManager {
     pub fn new() -> Self {
         ProjectileManager {
-            projectiles: HashMap::new(),
+            projectiles: BTreeMap::new(),
             next_projectile_id: 0,
         }
     }
 
+    /// Spells are cast by champions, who get the credit for what their projectiles hit.
     pub fn create_from_blueprint(&mut self, blueprint: ProjectileBlueprint) {
-        match blueprint.projectile_type {
-            ProjectileType::LockOn => {
-                if let Some(target_id) = blueprint.target_id {
-                    self.create_homing_projectile(
-                        blueprint.owner_id,
-                        blueprint.team_id,
-                        target_id,
-                        blueprint.start_pos,
-                        blueprint.speed,
-                        blueprint.payloads,
-                        blueprint.visual_cell_type,
-                    );
-                }
-            }
-            ProjectileType::SkillShot => {
-                self.create_skillshot_projectile(
+        let attacker = blueprint.owner_id as PlayerId;
+        let id = match blueprint.projectile_type {
+            ProjectileType::LockOn => blueprint.target_id.map(|target_id| {
+                self.create_homing_projectile(
                     blueprint.owner_id,
                     blueprint.team_id,
+                    target_id,
                     blueprint.start_pos,
-                    blueprint.end_pos,
                     blueprint.speed,
                     blueprint.payloads,
                     blueprint.visual_cell_type,
-                );
-            }
+                )
+            }),
+            ProjectileType::SkillShot => Some(self.create_skillshot_projectile(
+                blueprint.owner_id,
+                blueprint.team_id,
+                blueprint.start_pos,
+                blueprint.end_pos,
+                blueprint.speed,
+                blueprint.payloads,
+                blueprint.visual_cell_type,
+            )),
+        };
+        if let Some(projectile) = id.and_then(|id| self.projectiles.get_mut(&id)) {
+            projectile.attacker = Some(attacker);
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_skillshot_projectile(
         &mut self,
         owner_id: u64,
@@ -62,7 +65,7 @@ impl ProjectileManager {
         speed: u32,
         payloads: Vec<GameplayEffect>,
         visual_cell_type: CellAnimation,
-    ) {
+    ) -> u64 {
         let id = self.next_projectile_id;
         self.next_projectile_id += 1;
         let projectile = Projectile::from_skillshot(
@@ -76,8 +79,10 @@ impl ProjectileManager {
             visual_cell_type,
         );
         self.projectiles.insert(id, projectile);
+        id
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_homing_projectile(
         &mut self,
         owner_id: u64,
@@ -87,7 +92,7 @@ impl ProjectileManager {
         speed: u32,
         payloads: Vec<GameplayEffect>,
         visual_cell_type: CellAnimation,
-    ) {
+    ) -> u64 {
         let id = self.next_projectile_id;
         self.next_projectile_id += 1;
         let projectile = Projectile::from_homing_shot(
@@ -101,61 +106,65 @@ impl ProjectileManager {
             visual_cell_type,
         );
         self.projectiles.insert(id, projectile);
+        id
     }
 
+    #[allow(clippy::type_complexity)]
     pub fn update_and_check_collisions(
         &mut self,
         board: &Board,
-        champions: &HashMap<PlayerId, Champion>,
-        minions: &HashMap<MinionId, Minion>,
-        towers: &HashMap<TowerId, Tower>,
-        monsters: &HashMap<MonsterId, Monster>,
+        champions: &BTreeMap<PlayerId, Champion>,
+        minions: &BTreeMap<MinionId, Minion>,
+        towers: &BTreeMap<TowerId, Tower>,
+        monsters: &BTreeMap<MonsterId, Monster>,
     ) -> (
-        Vec<(usize, Target, Vec<GameplayEffect>)>,
+        Vec<(Option<PlayerId>, Target, Vec<GameplayEffect>)>,
         Vec<AnimationCommand>,
     ) {
         let mut projectiles_to_remove: Vec<u64> = Vec::new();
-        let mut pending_effects: Vec<(usize, Target, Vec<GameplayEffect>)> = Vec::new();
+        let mut pending_effects: Vec<(Option<PlayerId>, Target, Vec<GameplayEffect>)> = Vec::new();
         let mut animation_commands_executable: Vec<AnimationCommand> = Vec::new();
 
         for (id, projectile) in self.projectiles.iter_mut() {
             let (target_row, target_col) = match &projectile.pathing {
                 PathingLogic::Straight { .. } => (0, 0),
                 PathingLogic::LockOn { target_id } => match target_id {
-                    Target::Champion(id) => {
-                        if let Some(champion) = champions.get(id) {
+                    Target::Champion(target) => {
+                        if let Some(champion) =
+                            champions.get(target).filter(|champion| !champion.is_dead())
+                        {
                             (champion.row, champion.col)
                         } else {
-                            projectiles_to_remove.push(*id as u64);
+                            projectiles_to_remove.push(*id);
                             continue;
                         }
                     }
-                    Target::Minion(id) => {
-                        if let Some(minion) = minions.get(id) {
+                    Target::Minion(target) => {
+                        if let Some(minion) = minions.get(target) {
                             (minion.row, minion.col)
                         } else {
-                            projectiles_to_remove.push(*id as u64);
+                            projectiles_to_remove.push(*id);
                             continue;
                         }
                     }
-                    Target::Tower(id) => {
-                        if let Some(tower) = towers.get(id) {
+                    Target::Tower(target) => {
+                        if let Some(tower) = towers.get(target) {
                             (tower.row, tower.col)
                         } else {
-                            projectiles_to_remove.push(*id as u64);
+                            projectiles_to_remove.push(*id);
                             continue;
                         }
                     }
-                    Target::Monster(id) => {
-                        if let Some(monster) = monsters.get(id) {
+                    Target::Monster(target) => {
+                        if let Some(monster) = monsters.get(target) {
                             (monster.row, monster.col)
                         } else {
-                            projectiles_to_remove.push(*id as u64);
+                            projectiles_to_remove.push(*id);
                             continue;
                         }
                     }
                     _ => {
-                        projectiles_to_remove.push(*id as u64);
+                        projectiles_to_remove.push(*id);
                         continue;
                     }
                 },
@@ -184,7 +193,7 @@ impl ProjectileManager {
                             Some(CellContent::Champion(target_id, target_team)) => {
                                 hit_target = add_effects(
                                     &mut pending_effects,
-                                    projectile.owner_id as usize,
+                                    projectile.attacker,
                                     Target::Champion(target_id),
                                     projectile.payloads.clone(),
                                     projectile.team_id,
@@ -194,7 +203,7 @@ impl ProjectileManager {
                             Some(CellContent::Minion(target_id, target_team)) => {
                                 hit_target = add_effects(
                                     &mut pending_effects,
-                                    projectile.owner_id as usize,
+                                    projectile.attacker,
                                     Target::Minion(target_id),
                                     projectile.payloads.clone(),
                                     projectile.team_id,
@@ -204,7 +213,7 @@ impl ProjectileManager {
                             Some(CellContent::Monster(target_id)) => {
                                 hit_target = add_effects(
                                     &mut pending_effects,
-                                    projectile.owner_id as usize,
+                                    projectile.attacker,
                                     Target::Monster(target_id),
                                     projectile.payloads.clone(),
                                     projectile.team_id,
@@ -214,7 +223,7 @@ impl ProjectileManager {
                             Some(CellContent::Tower(target_id, target_team)) => {
                                 hit_target = add_effects(
                                     &mut pending_effects,
-                                    projectile.owner_id as usize,
+                                    projectile.attacker,
                                     Target::Tower(target_id),
                                     projectile.payloads.clone(),
                                     projectile.team_id,
@@ -249,8 +258,8 @@ impl ProjectileManager {
 }
 
 fn add_effects(
-    pending_effects: &mut Vec<(usize, Target, Vec<GameplayEffect>)>,
-    owner: usize,
+    pending_effects: &mut Vec<(Option<PlayerId>, Target, Vec<GameplayEffect>)>,
+    owner: Option<PlayerId>,
     target: Target,
     payloads: Vec<GameplayEffect>,
     projectile_team: Team,
@@ -291,11 +300,13 @@ mod tests {
     use super::*;
     use crate::config::{ChampionStats, MonsterStats, TowerStats};
     use crate::game::cell::{CellAnimation, MonsterId, Team};
+    use crate::game::clock::GameInstant;
     use crate::game::entities::champion::Champion;
     use crate::game::entities::monster::Monster;
     use crate::game::entities::projectile::PathingLogic;
     use crate::game::entities::tower::Tower;
     use crate::game::{PlayerId, TowerId};
+    use std::collections::HashMap;
 
     fn create_dummy_board(rows: usize, cols: usize) -> Board {
         Board::new(rows, cols)
@@ -312,6 +323,10 @@ mod tests {
             level_up_health_increase: 50,
             level_up_attack_damage_increase: 5,
             level_up_armor_increase: 2,
+            health_regen: 2,
+            mana_regen: 3,
+            level_up_health_regen_increase: 1,
+            level_up_mana_regen_increase: 1,
             attack_range_row: 3,
             attack_range_col: 3,
         }
@@ -426,10 +441,10 @@ mod tests {
     fn test_update_skillshot_misses_and_finishes() {
         let mut manager = ProjectileManager::new();
         let board = create_dummy_board(20, 20);
-        let champions = HashMap::<PlayerId, Champion>::new();
-        let minions = HashMap::new();
-        let towers = HashMap::<TowerId, Tower>::new();
-        let monsters = HashMap::<MonsterId, Monster>::new();
+        let champions = BTreeMap::<PlayerId, Champion>::new();
+        let minions = BTreeMap::new();
+        let towers = BTreeMap::<TowerId, Tower>::new();
+        let monsters = BTreeMap::<MonsterId, Monster>::new();
 
         manager.create_skillshot_projectile(
             101,
@@ -458,10 +473,10 @@ mod tests {
     fn test_update_projectile_hits_champion() {
         let mut manager = ProjectileManager::new();
         let mut board = create_dummy_board(20, 20);
-        let mut champions = HashMap::new();
-        let minions = HashMap::new();
-        let towers = HashMap::new();
-        let monsters = HashMap::new();
+        let mut champions = BTreeMap::new();
+        let minions = BTreeMap::new();
+        let towers = BTreeMap::new();
+        let monsters = BTreeMap::new();
 
         let target_id = 202;
         let target_pos = (10, 12);
@@ -507,10 +522,10 @@ mod tests {
     fn test_update_projectile_hits_tower() {
         let mut manager = ProjectileManager::new();
         let mut board = create_dummy_board(20, 20);
-        let champions = HashMap::new();
-        let minions = HashMap::new();
-        let mut towers = HashMap::new();
-        let monsters = HashMap::new();
+        let champions = BTreeMap::new();
+        let minions = BTreeMap::new();
+        let mut towers = BTreeMap::new();
+        let monsters = BTreeMap::new();
 
         let target_id = 303 as TowerId;
         let target_pos = (0, 5);
@@ -555,10 +570,10 @@ mod tests {
     fn test_update_projectile_hits_monster() {
         let mut manager = ProjectileManager::new();
         let mut board = create_dummy_board(20, 20);
-        let champions = HashMap::new();
-        let minions = HashMap::new();
-        let towers = HashMap::new();
-        let mut monsters = HashMap::new();
+        let champions = BTreeMap::new();
+        let minions = BTreeMap::new();
+        let towers = BTreeMap::new();
+        let mut monsters = BTreeMap::new();
 
         let target_id = 101 as MonsterId;
         let target_pos = (10, 12);
@@ -599,10 +614,10 @@ mod tests {
     fn test_update_homing_projectile_tracks_target() {
         let mut manager = ProjectileManager::new();
         let board = create_dummy_board(20, 20);
-        let mut champions = HashMap::new();
-        let minions = HashMap::new();
-        let towers = HashMap::new();
-        let monsters = HashMap::new();
+        let mut champions = BTreeMap::new();
+        let minions = BTreeMap::new();
+        let towers = BTreeMap::new();
+        let monsters = BTreeMap::new();
 
         let target_id = 202;
         let target_champion = Champion::new(
@@ -639,4 +654,55 @@ mod tests {
         let proj2 = manager.projectiles.get(&0).unwrap();
         assert_eq!(proj2.current_position, (11, 12)); // Moves diagonally
     }
+
+    #[test]
+    fn test_homing_projectile_is_removed_when_its_target_dies() {
+        let mut manager = ProjectileManager::new();
+        let mut board = create_dummy_board(20, 20);
+        let mut champions = BTreeMap::new();
+        let minions = BTreeMap::new();
+        let towers = BTreeMap::new();
+        let monsters = BTreeMap::new();
+        for (player_id, col) in [(1, 13), (5, 19)] {
+            let champion = Champion::new(
+                player_id,
+                Team::Red,
+                10,
+                col,
+                mock_champion_stats(),
+                HashMap::new(),
+            );
+            champions.insert(player_id, champion);
+        }
+
+        let orphaned = manager.create_homing_projectile(
+            102,
+            Team::Blue,
+            Target::Champion(1),
+            (10, 10),
+            1,
+            vec![GameplayEffect::Damage(30)],
+            CellAnimation::Projectile,
+        );
+        // Its id matches the player id of the dying champion
+        let unrelated = manager.create_homing_projectile(
+            102,
+            Team::Blue,
+            Target::Champion(5),
+            (5, 10),
+            1,
+            vec![GameplayEffect::Damage(30)],
+            CellAnimation::Projectile,
+        );
+        assert_eq!(unrelated, 1);
+
+        champions
+            .get_mut(&1)
+            .unwrap()
+            .die(&mut board, GameInstant::from_millis(10_000));
+        manager.update_and_check_collisions(&board, &champions, &minions, &towers, &monsters);
+
+        assert!(!manager.projectiles.contains_key(&orphaned));
+        assert!(manager.projectiles.contains_key(&unrelated));
+    }
 }
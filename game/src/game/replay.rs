@@ -0,0 +1,428 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, LineWriter, Write};
+
+use serde::{Deserialize, Serialize};
+
+use super::cell::PlayerId;
+use super::entities::champion::Action;
+use super::roster::{Roster, RosterEntry};
+use super::{Board, ClientMessage, GameManager};
+use crate::config::{ConfigFiles, ConfigSources, GameConfig};
+use crate::errors::ReplayError;
+
+/// Format of the replay files written by this server.
+pub const REPLAY_VERSION: u8 = 1;
+
+/// First line of a replay, everything needed to rebuild the match before its first tick.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    pub version: u8,
+    pub config: ConfigFiles,
+    /// Contents of the map file
+    pub map: String,
+    pub max_players: u8,
+    pub roster: Option<Vec<RosterEntry>>,
+}
+
+/// Input that changed the match between two ticks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReplayEvent {
+    Join {
+        spell1: u8,
+        spell2: u8,
+        username: Option<String>,
+    },
+    Reconnect {
+        player_id: PlayerId,
+    },
+    Leave {
+        player_id: PlayerId,
+    },
+    Purchase {
+        player_id: PlayerId,
+        item_id: u32,
+    },
+    Pause {
+        player_id: PlayerId,
+    },
+    Resume {
+        player_id: PlayerId,
+    },
+    ConfigReload {
+        config: ConfigFiles,
+        rescale_live: bool,
+    },
+}
+
+/// Inputs of one tick, the events applied since the previous tick and the actions it played.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayTick {
+    pub tick: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<ReplayEvent>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub actions: BTreeMap<PlayerId, Action>,
+}
+
+/// Writes a replay as JSON lines: the header, then one line per tick that had inputs.
+/// Lines are written before their tick runs, so a tick that crashes the server is kept.
+pub struct ReplayRecorder {
+    writer: Box<dyn Write + Send>,
+    tick: u64,
+    last_written: u64,
+    events: Vec<ReplayEvent>,
+}
+
+impl ReplayRecorder {
+    pub fn create(path: &str, header: &ReplayHeader) -> Result<Self, ReplayError> {
+        let file = File::create(path)?;
+        Self::new(Box::new(LineWriter::new(file)), header)
+    }
+
+    pub fn new(
+        mut writer: Box<dyn Write + Send>,
+        header: &ReplayHeader,
+    ) -> Result<Self, ReplayError> {
+        write_line(&mut writer, header)?;
+        Ok(ReplayRecorder {
+            writer,
+            tick: 0,
+            last_written: 0,
+            events: Vec::new(),
+        })
+    }
+
+    pub fn record(&mut self, event: ReplayEvent) {
+        self.events.push(event);
+    }
+
+    /// Starts the next tick with the actions the players chose for it.
+    pub fn begin_tick(&mut self, actions: &BTreeMap<PlayerId, Action>) -> Result<(), ReplayError> {
+        self.tick += 1;
+        if self.events.is_empty() && actions.is_empty() {
+            return Ok(());
+        }
+        let line = ReplayTick {
+            tick: self.tick,
+            events: std::mem::take(&mut self.events),
+            actions: actions.iter().map(|(id, action)| (*id, *action)).collect(),
+        };
+        self.last_written = self.tick;
+        write_line(&mut self.writer, &line)
+    }
+
+    /// Marks the current tick as the last one, so a playback runs up to it.
+    pub fn finish(&mut self) -> Result<(), ReplayError> {
+        if self.last_written == self.tick {
+            return Ok(());
+        }
+        self.last_written = self.tick;
+        write_line(
+            &mut self.writer,
+            &ReplayTick {
+                tick: self.tick,
+                ..ReplayTick::default()
+            },
+        )
+    }
+}
+
+fn write_line(
+    writer: &mut Box<dyn Write + Send>,
+    value: &impl Serialize,
+) -> Result<(), ReplayError> {
+    serde_json::to_writer(&mut *writer, value).map_err(std::io::Error::from)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// A recorded match, read back for playback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replay {
+    pub header: ReplayHeader,
+    /// Ticks with inputs, in order
+    pub ticks: Vec<ReplayTick>,
+}
+
+impl Replay {
+    pub fn load(path: &str) -> Result<Self, ReplayError> {
+        Self::parse(BufReader::new(File::open(path)?))
+    }
+
+    pub fn parse(reader: impl BufRead) -> Result<Self, ReplayError> {
+        let mut lines = reader
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.as_ref().is_ok_and(|line| line.trim().is_empty()));
+        let (_, header) = lines.next().ok_or(ReplayError::MissingHeader)?;
+        let header: ReplayHeader = serde_json::from_str(&header?)
+            .map_err(|source| ReplayError::Parse { line: 1, source })?;
+        if header.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion(header.version));
+        }
+
+        let mut ticks: Vec<ReplayTick> = Vec::new();
+        for (index, line) in lines {
+            let tick: ReplayTick =
+                serde_json::from_str(&line?).map_err(|source| ReplayError::Parse {
+                    line: index + 1,
+                    source,
+                })?;
+            if let Some(previous) = ticks.last()
+                && tick.tick <= previous.tick
+            {
+                return Err(ReplayError::OutOfOrder {
+                    tick: tick.tick,
+                    previous: previous.tick,
+                });
+            }
+            ticks.push(tick);
+        }
+        Ok(Replay { header, ticks })
+    }
+
+    /// Last tick the recorded match played.
+    pub fn last_tick(&self) -> u64 {
+        self.ticks.last().map(|tick| tick.tick).unwrap_or(0)
+    }
+
+    /// Rebuilds the match as it was before its first tick.
+    pub fn start_match(&self) -> Result<GameManager, ReplayError> {
+        let sources = ConfigSources {
+            stats: "replay stats".to_string(),
+            spells: "replay spells".to_string(),
+            items: "replay items".to_string(),
+            rules: "replay rules".to_string(),
+        };
+        let config = GameConfig::from_files(self.header.config.clone(), sources)
+            .map_err(|e| ReplayError::Setup(format!("invalid configuration: {}", e)))?;
+        let board = Board::from_json_str(&self.header.map, "replay map")
+            .map_err(|e| ReplayError::Setup(format!("invalid map: {}", e)))?;
+        let mut manager = GameManager::new(config, board, self.header.max_players);
+        if let Some(players) = &self.header.roster {
+            manager.set_roster(Roster::new(players.clone(), "replay roster"));
+        }
+        Ok(manager)
+    }
+}
+
+/// Steps a rebuilt match through the recorded ticks.
+#[derive(Debug)]
+pub struct Playback {
+    replay: Replay,
+    tick: u64,
+    next: usize,
+}
+
+impl Playback {
+    pub fn new(replay: Replay) -> Self {
+        Playback {
+            replay,
+            tick: 0,
+            next: 0,
+        }
+    }
+
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.tick >= self.replay.last_tick()
+    }
+
+    /// Plays the next tick with its recorded inputs, `None` once the recording is over.
+    pub fn step(
+        &mut self,
+        manager: &mut GameManager,
+    ) -> Option<HashMap<PlayerId, Vec<ClientMessage>>> {
+        if self.is_done() {
+            return None;
+        }
+        self.tick += 1;
+        if let Some(inputs) = self.replay.ticks.get(self.next)
+            && inputs.tick == self.tick
+        {
+            manager.apply_replay_tick(inputs);
+            self.next += 1;
+        }
+        let updates = manager.game_tick();
+        manager.clear_action();
+        Some(updates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map_generator::{GeneratorParams, generate};
+    use crate::game::phase::MatchPhase;
+    use crate::packet::codec::PacketCodec;
+    use bytes::BytesMut;
+    use std::sync::{Arc, Mutex};
+    use tokio_util::codec::Encoder;
+
+    // Lets a test read back what the recorder wrote
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn header() -> ReplayHeader {
+        ReplayHeader {
+            version: REPLAY_VERSION,
+            config: ConfigFiles::default(),
+            map: "{}".to_string(),
+            max_players: 2,
+            roster: None,
+        }
+    }
+
+    #[test]
+    fn test_recorder_skips_idle_ticks() {
+        let buffer = SharedBuffer::default();
+        let mut recorder = ReplayRecorder::new(Box::new(buffer.clone()), &header()).unwrap();
+        recorder.record(ReplayEvent::Join {
+            spell1: 1,
+            spell2: 2,
+            username: None,
+        });
+        recorder.begin_tick(&BTreeMap::new()).unwrap();
+        recorder.begin_tick(&BTreeMap::new()).unwrap();
+        recorder
+            .begin_tick(&BTreeMap::from([(1, Action::MoveUp)]))
+            .unwrap();
+        recorder.begin_tick(&BTreeMap::new()).unwrap();
+        recorder.finish().unwrap();
+
+        let bytes = buffer.0.lock().unwrap().clone();
+        let replay = Replay::parse(bytes.as_slice()).unwrap();
+        assert_eq!(replay.header, header());
+        let ticks: Vec<u64> = replay.ticks.iter().map(|tick| tick.tick).collect();
+        assert_eq!(ticks, vec![1, 3, 4]);
+        assert_eq!(replay.ticks[0].events.len(), 1);
+        assert_eq!(replay.ticks[1].actions[&1], Action::MoveUp);
+        assert_eq!(replay.last_tick(), 4);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_files() {
+        assert!(matches!(
+            Replay::parse("".as_bytes()),
+            Err(ReplayError::MissingHeader)
+        ));
+
+        let mut old = header();
+        old.version = REPLAY_VERSION + 1;
+        let file = serde_json::to_string(&old).unwrap();
+        assert!(matches!(
+            Replay::parse(file.as_bytes()),
+            Err(ReplayError::UnsupportedVersion(_))
+        ));
+
+        let file = format!(
+            "{}\n{{\"tick\":5}}\n{{\"tick\":3}}\n",
+            serde_json::to_string(&header()).unwrap()
+        );
+        assert!(matches!(
+            Replay::parse(file.as_bytes()),
+            Err(ReplayError::OutOfOrder {
+                tick: 3,
+                previous: 5
+            })
+        ));
+    }
+
+    // Every packet of a tick, in player order, as sent on the wire
+    fn tick_bytes(updates: HashMap<PlayerId, Vec<ClientMessage>>) -> Vec<(PlayerId, BytesMut)> {
+        let mut codec = PacketCodec::new();
+        let mut updates: Vec<_> = updates.into_iter().collect();
+        updates.sort_by_key(|(player_id, _)| *player_id);
+        updates
+            .into_iter()
+            .map(|(player_id, packets)| {
+                let mut bytes = BytesMut::new();
+                for packet in packets {
+                    codec.encode(packet, &mut bytes).unwrap();
+                }
+                (player_id, bytes)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_playback_reproduces_the_match() {
+        let config =
+            GameConfig::load("stats.toml", "spells.toml", "items.toml", "rules.toml").unwrap();
+        let layout = generate(&GeneratorParams {
+            seed: 3,
+            size: 60,
+            lane_width: 4,
+            bush_density: 0.1,
+            wall_complexity: 0.3,
+            camps: Vec::new(),
+        })
+        .unwrap();
+        let header = ReplayHeader {
+            version: REPLAY_VERSION,
+            config: config.files.clone(),
+            map: serde_json::to_string(&layout).unwrap(),
+            max_players: 2,
+            roster: None,
+        };
+        let recording = Replay {
+            header: header.clone(),
+            ticks: Vec::new(),
+        };
+
+        let buffer = SharedBuffer::default();
+        let mut manager = recording.start_match().unwrap();
+        manager.record_replay(ReplayRecorder::new(Box::new(buffer.clone()), &header).unwrap());
+        manager.add_player(1, 2, None);
+        manager.add_player(2, 1, None);
+        let mut played = Vec::new();
+        // Long enough for the first minion wave to spawn and walk the lanes
+        for tick in 0..1600u32 {
+            if manager.phase() == &MatchPhase::InProgress {
+                manager.store_player_action(1, [1, 4, 7, 5][tick as usize % 4]);
+                manager.store_player_action(2, [2, 3, 7, 6][tick as usize % 4]);
+            }
+            match tick {
+                150 => manager.request_pause(1),
+                160 => {
+                    manager.vote_resume(1);
+                    manager.vote_resume(2);
+                }
+                _ => {}
+            }
+            played.push(tick_bytes(manager.game_tick()));
+            manager.clear_action();
+        }
+        assert!(manager.phase() == &MatchPhase::InProgress);
+        assert!(!manager.minion_manager.minions.is_empty());
+
+        let bytes = buffer.0.lock().unwrap().clone();
+        let replay = Replay::parse(bytes.as_slice()).unwrap();
+        let mut replayed_manager = replay.start_match().unwrap();
+        let mut playback = Playback::new(replay);
+        let mut replayed = Vec::new();
+        while let Some(updates) = playback.step(&mut replayed_manager) {
+            replayed.push(tick_bytes(updates));
+        }
+        assert_eq!(replayed.len(), played.len());
+        for (tick, (played, replayed)) in played.iter().zip(&replayed).enumerate() {
+            assert_eq!(played, replayed, "tick {} differs", tick + 1);
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+
+use super::Board;
+use super::cell::{PlayerId, Team};
+use crate::errors::{ConfigIssue, GameError};
+
+/// An account expected in the match, with its team and spawn point.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct RosterEntry {
+    pub username: String,
+    pub team: Team,
+    /// Index into the team's spawn points on the map.
+    pub slot: u8,
+}
+
+impl RosterEntry {
+    /// Parses `USERNAME:TEAM:SLOT`, as given on the command line.
+    pub fn parse(value: &str) -> Result<Self, GameError> {
+        let invalid = || {
+            GameError::InvalidInput(format!(
+                "invalid roster entry {:?}, expected USERNAME:TEAM:SLOT",
+                value
+            ))
+        };
+        let mut fields = value.rsplitn(3, ':');
+        let (Some(slot), Some(team), Some(username)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(invalid());
+        };
+        let team = match team.to_ascii_lowercase().as_str() {
+            "blue" => Team::Blue,
+            "red" => Team::Red,
+            _ => return Err(invalid()),
+        };
+        Ok(RosterEntry {
+            username: username.to_string(),
+            team,
+            slot: slot.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Players the room manager expects, given at launch instead of filling slots in join order.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct Roster {
+    #[serde(skip)]
+    pub source: String,
+    pub players: Vec<RosterEntry>,
+}
+
+impl Roster {
+    pub fn new(players: Vec<RosterEntry>, source: &str) -> Self {
+        Roster {
+            source: source.to_string(),
+            players,
+        }
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(path)?;
+        let mut roster: Roster = serde_json::from_str(&content)?;
+        roster.source = path.to_string();
+        Ok(roster)
+    }
+
+    pub fn len(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+
+    /// Player id reserved for an account, ids follow the roster order.
+    pub fn entry_of(&self, username: &str) -> Option<(PlayerId, &RosterEntry)> {
+        self.players
+            .iter()
+            .enumerate()
+            .find(|(_, entry)| entry.username == username)
+            .map(|(i, entry)| (i + 1, entry))
+    }
+
+    /// Checks that every account appears once and gets its own spawn point on the board.
+    pub fn validate(&self, board: &Board) -> Result<(), Vec<ConfigIssue>> {
+        let mut issues = Vec::new();
+        let mut report = |section: String, field: &str, message: String| {
+            issues.push(ConfigIssue {
+                file: self.source.clone(),
+                section,
+                field: field.to_string(),
+                message,
+            });
+        };
+
+        if self.players.is_empty() {
+            report(
+                "players".to_string(),
+                "username",
+                "must list at least one player".to_string(),
+            );
+        }
+        for (i, entry) in self.players.iter().enumerate() {
+            let section = format!("players.{} ({})", i, entry.username);
+            if entry.username.is_empty() {
+                report(section.clone(), "username", "must not be empty".to_string());
+            }
+            let earlier = &self.players[..i];
+            if earlier.iter().any(|other| other.username == entry.username) {
+                report(
+                    section.clone(),
+                    "username",
+                    "is listed more than once".to_string(),
+                );
+            }
+            if earlier
+                .iter()
+                .any(|other| other.team == entry.team && other.slot == entry.slot)
+            {
+                report(
+                    section.clone(),
+                    "slot",
+                    format!("{:?} slot {} is already taken", entry.team, entry.slot),
+                );
+            }
+            let spawns = board.map_layout.spawns_of(entry.team).len();
+            if entry.slot as usize >= spawns {
+                report(
+                    section,
+                    "slot",
+                    format!("the map only has {} {:?} spawn(s)", spawns, entry.team),
+                );
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map_layout::PlayerSpawn;
+
+    fn create_board() -> Board {
+        let mut board = Board::new(20, 20);
+        for (team, row, col) in [(Team::Blue, 18, 1), (Team::Blue, 17, 1), (Team::Red, 1, 18)] {
+            board.map_layout.spawns.push(PlayerSpawn { team, row, col });
+        }
+        board
+    }
+
+    #[test]
+    fn test_parse_roster_file() {
+        let content = r#"{"players": [
+            {"username": "alice", "team": "blue", "slot": 1},
+            {"username": "bob", "team": "red", "slot": 0}
+        ]}"#;
+        let roster: Roster = serde_json::from_str(content).unwrap();
+        let (player_id, entry) = roster.entry_of("bob").unwrap();
+        assert_eq!(player_id, 2);
+        assert_eq!(entry.team, Team::Red);
+        assert!(roster.entry_of("carol").is_none());
+        assert_eq!(roster.validate(&create_board()), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_cli_entry() {
+        assert_eq!(
+            RosterEntry::parse("team:lead:Blue:1").unwrap(),
+            RosterEntry {
+                username: "team:lead".to_string(),
+                team: Team::Blue,
+                slot: 1
+            }
+        );
+        assert!(RosterEntry::parse("alice:green:0").is_err());
+        assert!(RosterEntry::parse("alice:red").is_err());
+    }
+
+    #[test]
+    fn test_validate_reports_conflicts() {
+        let roster = Roster::new(
+            vec![
+                RosterEntry::parse("alice:blue:0").unwrap(),
+                RosterEntry::parse("alice:red:0").unwrap(),
+                RosterEntry::parse("bob:blue:0").unwrap(),
+                RosterEntry::parse("carol:red:3").unwrap(),
+            ],
+            "roster.json",
+        );
+        let issues = roster.validate(&create_board()).unwrap_err();
+        let located: Vec<(&str, &str)> = issues
+            .iter()
+            .map(|issue| (issue.section.as_str(), issue.field.as_str()))
+            .collect();
+        assert_eq!(
+            located,
+            vec![
+                ("players.1 (alice)", "username"),
+                ("players.2 (bob)", "slot"),
+                ("players.3 (carol)", "slot"),
+            ]
+        );
+    }
+}
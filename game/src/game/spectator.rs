@@ -0,0 +1,73 @@
+use super::cell::{PlayerId, Team};
+
+/// Spectators share the id space of the client channels, above any player id.
+pub const FIRST_SPECTATOR_ID: PlayerId = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpectatorVision {
+    /// Sees everything, only allowed when the server enables it
+    Full,
+    /// Sees what this team sees
+    Team(Team),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Camera {
+    /// Centered on a fixed cell
+    Free { row: u16, col: u16 },
+    /// Centered on a champion, staying where it was if the champion is gone
+    Follow(PlayerId),
+}
+
+/// A connection watching the match without a champion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spectator {
+    pub vision: SpectatorVision,
+    pub camera: Camera,
+    // Last cell the camera was centered on
+    pub row: u16,
+    pub col: u16,
+}
+
+impl Spectator {
+    pub fn new(vision: SpectatorVision, row: u16, col: u16) -> Self {
+        Spectator {
+            vision,
+            camera: Camera::Free { row, col },
+            row,
+            col,
+        }
+    }
+
+    /// Moves the camera center, `champion_position` gives where a champion currently is.
+    pub fn update_position(&mut self, champion_position: impl Fn(PlayerId) -> Option<(u16, u16)>) {
+        let position = match self.camera {
+            Camera::Free { row, col } => Some((row, col)),
+            Camera::Follow(player_id) => champion_position(player_id),
+        };
+        if let Some((row, col)) = position {
+            self.row = row;
+            self.col = col;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_camera_keeps_last_position() {
+        let mut spectator = Spectator::new(SpectatorVision::Full, 5, 5);
+        spectator.camera = Camera::Follow(2);
+        spectator.update_position(|id| (id == 2).then_some((10, 12)));
+        assert_eq!((spectator.row, spectator.col), (10, 12));
+
+        spectator.update_position(|_| None);
+        assert_eq!((spectator.row, spectator.col), (10, 12));
+
+        spectator.camera = Camera::Free { row: 3, col: 4 };
+        spectator.update_position(|_| None);
+        assert_eq!((spectator.row, spectator.col), (3, 4));
+    }
+}
@@ -1,6 +1,7 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use crate::config::SpellStats;
+use crate::game::clock::GameInstant;
 use crate::game::projectile_manager::ProjectileManager;
 use crate::game::{
     Champion,
@@ -12,7 +13,7 @@ use super::{ProjectileBlueprint, ProjectileType, Spell};
 
 #[derive(Debug, Clone)]
 pub struct FireballSpell {
-    last_casted: Option<Instant>,
+    last_casted: Option<GameInstant>,
     stats: SpellStats,
 }
 
@@ -34,6 +35,10 @@ impl Spell for FireballSpell {
         &self.stats.mana_cost
     }
 
+    fn set_stats(&mut self, stats: SpellStats) {
+        self.stats = stats;
+    }
+
     fn clone_box(&self) -> Box<dyn Spell> {
         Box::new(self.clone())
     }
@@ -43,21 +48,23 @@ impl Spell for FireballSpell {
         caster: &mut Champion,
         caster_damage: u16,
         projectile_manager: &mut ProjectileManager,
+        now: GameInstant,
     ) {
         // Cooldown check
-        if let Some(last_casted) = self.last_casted {
-            if last_casted.elapsed() < Duration::from_secs(self.stats.cooldown_secs as u64) {
-                return ();
-            }
+        if let Some(last_casted) = self.last_casted
+            && now.duration_since(last_casted)
+                < Duration::from_secs(self.stats.cooldown_secs as u64)
+        {
+            return;
         }
         // Mana check
         if caster.stats.mana < self.stats.mana_cost {
-            return ();
+            return;
         } else {
             caster.stats.mana -= self.stats.mana_cost;
         }
 
-        self.last_casted = Some(Instant::now());
+        self.last_casted = Some(now);
 
         let spell_damage =
             (caster_damage as f32 * self.stats.damage_ratio + self.stats.base_damage as f32) as u16;
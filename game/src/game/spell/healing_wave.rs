@@ -1,6 +1,7 @@
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
 use crate::config::SpellStats;
+use crate::game::clock::GameInstant;
 use crate::game::projectile_manager::ProjectileManager;
 use crate::game::{
     Champion,
@@ -12,7 +13,7 @@ use super::{ProjectileBlueprint, ProjectileType, Spell};
 
 #[derive(Debug, Clone)]
 pub struct HealingWaveSpell {
-    last_casted: Option<Instant>,
+    last_casted: Option<GameInstant>,
     stats: SpellStats,
 }
 
@@ -34,6 +35,10 @@ impl Spell for HealingWaveSpell {
         &self.stats.mana_cost
     }
 
+    fn set_stats(&mut self, stats: SpellStats) {
+        self.stats = stats;
+    }
+
     fn clone_box(&self) -> Box<dyn Spell> {
         Box::new(self.clone())
     }
@@ -43,11 +48,13 @@ impl Spell for HealingWaveSpell {
         caster: &mut Champion,
         caster_damage: u16,
         projectile_manager: &mut ProjectileManager,
+        now: GameInstant,
     ) {
-        if let Some(last_casted) = self.last_casted {
-            if last_casted.elapsed() < Duration::from_secs(self.stats.cooldown_secs as u64) {
-                return;
-            }
+        if let Some(last_casted) = self.last_casted
+            && now.duration_since(last_casted)
+                < Duration::from_secs(self.stats.cooldown_secs as u64)
+        {
+            return;
         }
         if caster.stats.mana < self.stats.mana_cost {
             return;
@@ -55,7 +62,7 @@ impl Spell for HealingWaveSpell {
             caster.stats.mana -= self.stats.mana_cost;
         }
 
-        self.last_casted = Some(Instant::now());
+        self.last_casted = Some(now);
 
         let heal_amount =
             (caster_damage as f32 * self.stats.damage_ratio + self.stats.base_damage as f32) as u16;
@@ -111,6 +118,10 @@ mod tests {
             level_up_health_increase: 20,
             level_up_attack_damage_increase: 5,
             level_up_armor_increase: 2,
+            health_regen: 2,
+            mana_regen: 3,
+            level_up_health_regen_increase: 1,
+            level_up_mana_regen_increase: 1,
             attack_range_row: 3,
             attack_range_col: 3,
         }
@@ -139,7 +150,7 @@ mod tests {
 
         let mut spell = HealingWaveSpell::new(spell_stats);
 
-        spell.cast(&mut champion, 0, &mut projectile_manager);
+        spell.cast(&mut champion, 0, &mut projectile_manager, GameInstant::ZERO);
 
         assert_eq!(projectile_manager.projectiles.len(), 1);
         let projectile = &projectile_manager.projectiles[&0];
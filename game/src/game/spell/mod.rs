@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use super::{
     Champion,
     cell::{CellAnimation, Team},
+    clock::GameInstant,
     entities::{Target, projectile::GameplayEffect},
     projectile_manager::ProjectileManager,
 };
@@ -32,21 +33,24 @@ pub enum ProjectileType {
 pub trait Spell: Send + Sync + Debug + 'static {
     fn id(&self) -> u8;
     fn mana_cost(&self) -> &u16;
+    fn set_stats(&mut self, stats: SpellStats);
     fn cast(
         &mut self,
         caster: &mut Champion,
         caster_damage: u16,
         projectile_manager: &mut ProjectileManager,
+        now: GameInstant,
     );
     fn clone_box(&self) -> Box<dyn Spell>;
 }
 
-pub fn create_spell_from_id(id: u8, stats: SpellStats) -> Box<dyn Spell> {
+/// The spell with this id, `None` when no spell has it.
+pub fn create_spell_from_id(id: u8, stats: SpellStats) -> Option<Box<dyn Spell>> {
     match id {
-        0 => Box::new(freeze_wall::FreezeWallSpell::new(stats)),
-        1 => Box::new(fireball::FireballSpell::new(stats)),
-        2 => Box::new(healing_wave::HealingWaveSpell::new(stats)),
-        _ => panic!("Unknown spell ID: {}", id),
+        0 => Some(Box::new(freeze_wall::FreezeWallSpell::new(stats))),
+        1 => Some(Box::new(fireball::FireballSpell::new(stats))),
+        2 => Some(Box::new(healing_wave::HealingWaveSpell::new(stats))),
+        _ => None,
     }
 }
 
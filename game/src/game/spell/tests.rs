@@ -5,12 +5,15 @@ use crate::{
     config::{ChampionStats, SpellStats},
     game::{
         cell::Team,
+        clock::GameInstant,
         entities::{
             champion::{Champion, Direction},
             projectile::GameplayEffect,
         },
         projectile_manager::ProjectileManager,
-        spell::{Spell, fireball::FireballSpell, freeze_wall::FreezeWallSpell},
+        spell::{
+            Spell, create_spell_from_id, fireball::FireballSpell, freeze_wall::FreezeWallSpell,
+        },
     },
 };
 
@@ -25,6 +28,10 @@ fn mock_champion_stats() -> ChampionStats {
         level_up_health_increase: 50,
         level_up_attack_damage_increase: 5,
         level_up_armor_increase: 2,
+        health_regen: 2,
+        mana_regen: 3,
+        level_up_health_regen_increase: 1,
+        level_up_mana_regen_increase: 1,
         attack_range_row: 3,
         attack_range_col: 3,
     }
@@ -67,7 +74,12 @@ fn test_fireball_cast_creates_projectile() {
     let mut fireball_spell = FireballSpell::new(mock_fireball_spell_stats());
     let mut projectile_manager = ProjectileManager::new();
 
-    fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+    fireball_spell.cast(
+        &mut champion,
+        50,
+        &mut projectile_manager,
+        GameInstant::ZERO,
+    );
 
     assert_eq!(projectile_manager.projectiles.len(), 1);
     let projectile = projectile_manager.projectiles.values().next().unwrap();
@@ -101,12 +113,27 @@ fn test_fireball_cast_respects_cooldown() {
     let mut projectile_manager = ProjectileManager::new();
 
     // First cast
-    fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+    fireball_spell.cast(
+        &mut champion,
+        50,
+        &mut projectile_manager,
+        GameInstant::ZERO,
+    );
     assert_eq!(projectile_manager.projectiles.len(), 1);
 
     // Second cast, should be on cooldown
-    fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+    fireball_spell.cast(
+        &mut champion,
+        50,
+        &mut projectile_manager,
+        GameInstant::ZERO,
+    );
     assert_eq!(projectile_manager.projectiles.len(), 1);
+
+    // Once the cooldown elapsed the spell can be cast again
+    let after_cooldown = GameInstant::from_millis(10_000);
+    fireball_spell.cast(&mut champion, 50, &mut projectile_manager, after_cooldown);
+    assert_eq!(projectile_manager.projectiles.len(), 2);
 }
 
 #[test]
@@ -116,7 +143,12 @@ fn test_fireball_cast_checks_mana() {
     let mut fireball_spell = FireballSpell::new(mock_fireball_spell_stats());
     let mut projectile_manager = ProjectileManager::new();
 
-    fireball_spell.cast(&mut champion, 50, &mut projectile_manager);
+    fireball_spell.cast(
+        &mut champion,
+        50,
+        &mut projectile_manager,
+        GameInstant::ZERO,
+    );
 
     assert_eq!(projectile_manager.projectiles.len(), 0);
 }
@@ -128,7 +160,12 @@ fn test_freezewall_cast_creates_multiple_projectiles() {
     let mut freezewall_spell = FreezeWallSpell::new(mock_freezewall_spell_stats());
     let mut projectile_manager = ProjectileManager::new();
 
-    freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+    freezewall_spell.cast(
+        &mut champion,
+        50,
+        &mut projectile_manager,
+        GameInstant::ZERO,
+    );
 
     assert_eq!(projectile_manager.projectiles.len(), 3);
     let mut projectiles: Vec<_> = projectile_manager.projectiles.values().collect();
@@ -188,11 +225,21 @@ fn test_freezewall_cast_respects_cooldown() {
     let mut projectile_manager = ProjectileManager::new();
 
     // First cast
-    freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+    freezewall_spell.cast(
+        &mut champion,
+        50,
+        &mut projectile_manager,
+        GameInstant::ZERO,
+    );
     assert_eq!(projectile_manager.projectiles.len(), 3);
 
     // Second cast, should be on cooldown
-    freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+    freezewall_spell.cast(
+        &mut champion,
+        50,
+        &mut projectile_manager,
+        GameInstant::ZERO,
+    );
     assert_eq!(projectile_manager.projectiles.len(), 3);
 }
 
@@ -203,7 +250,18 @@ fn test_freezewall_cast_checks_mana() {
     let mut freezewall_spell = FreezeWallSpell::new(mock_freezewall_spell_stats());
     let mut projectile_manager = ProjectileManager::new();
 
-    freezewall_spell.cast(&mut champion, 50, &mut projectile_manager);
+    freezewall_spell.cast(
+        &mut champion,
+        50,
+        &mut projectile_manager,
+        GameInstant::ZERO,
+    );
 
     assert_eq!(projectile_manager.projectiles.len(), 0);
 }
+
+#[test]
+fn test_create_spell_from_unknown_id() {
+    assert!(create_spell_from_id(1, mock_fireball_spell_stats()).is_some());
+    assert!(create_spell_from_id(3, mock_fireball_spell_stats()).is_none());
+}
@@ -0,0 +1,234 @@
+use super::algorithms::bresenham::Bresenham;
+use super::board::Board;
+use super::cell::{BaseTerrain, Cell, CellContent, Team};
+use std::collections::HashSet;
+
+/// Something that reveals the cells around it for its team.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SightSource {
+    pub row: u16,
+    pub col: u16,
+    pub radius: u16,
+}
+
+/// Connected bush patches of a board, computed once since bushes never change.
+#[derive(Debug, Default)]
+pub struct BushRegions {
+    cols: usize,
+    regions: Vec<Option<u16>>,
+}
+
+impl BushRegions {
+    pub fn new(board: &Board) -> Self {
+        let mut regions = vec![None; board.rows * board.cols];
+        let is_bush = |row: usize, col: usize| {
+            board
+                .get_cell(row, col)
+                .is_some_and(|cell| cell.base == BaseTerrain::Bush)
+        };
+        let mut next_region = 0;
+        for row in 0..board.rows {
+            for col in 0..board.cols {
+                if !is_bush(row, col) || regions[row * board.cols + col].is_some() {
+                    continue;
+                }
+                let mut stack = vec![(row, col)];
+                regions[row * board.cols + col] = Some(next_region);
+                while let Some((r, c)) = stack.pop() {
+                    let neighbours = [
+                        (r.wrapping_sub(1), c),
+                        (r + 1, c),
+                        (r, c.wrapping_sub(1)),
+                        (r, c + 1),
+                    ];
+                    for (nr, nc) in neighbours {
+                        if nr < board.rows
+                            && nc < board.cols
+                            && is_bush(nr, nc)
+                            && regions[nr * board.cols + nc].is_none()
+                        {
+                            regions[nr * board.cols + nc] = Some(next_region);
+                            stack.push((nr, nc));
+                        }
+                    }
+                }
+                next_region += 1;
+            }
+        }
+        BushRegions {
+            cols: board.cols,
+            regions,
+        }
+    }
+
+    pub fn region_of(&self, row: u16, col: u16) -> Option<u16> {
+        self.regions
+            .get(row as usize * self.cols + col as usize)
+            .copied()
+            .flatten()
+    }
+
+    /// Cells one team can see this tick, from the sight sources of its units and structures.
+    pub fn team_vision(
+        &self,
+        board: &Board,
+        team: Team,
+        sources: impl IntoIterator<Item = SightSource>,
+    ) -> TeamVision {
+        let mut visible = vec![false; board.rows * board.cols];
+        let mut occupied_bushes = HashSet::new();
+        for source in sources {
+            if let Some(region) = self.region_of(source.row, source.col) {
+                occupied_bushes.insert(region);
+            }
+            reveal(board, source, &mut visible);
+        }
+        // Units in a bush only show to a team that has a unit in the same bush
+        let exposed = visible
+            .iter()
+            .zip(&self.regions)
+            .map(|(seen, region)| {
+                *seen && region.is_none_or(|region| occupied_bushes.contains(&region))
+            })
+            .collect();
+        TeamVision {
+            team,
+            cols: board.cols,
+            visible,
+            exposed,
+        }
+    }
+}
+
+// Marks every cell within the radius whose line of sight is not cut by a wall
+fn reveal(board: &Board, source: SightSource, visible: &mut [bool]) {
+    let radius = source.radius as i32;
+    for drow in -radius..=radius {
+        for dcol in -radius..=radius {
+            if drow * drow + dcol * dcol > radius * radius {
+                continue;
+            }
+            let row = source.row as i32 + drow;
+            let col = source.col as i32 + dcol;
+            if row < 0 || col < 0 || row as usize >= board.rows || col as usize >= board.cols {
+                continue;
+            }
+            let target = (row as u16, col as u16);
+            // The wall itself is seen, what lies behind it is not
+            let blocked = Bresenham::new((source.row, source.col), target)
+                .filter(|&cell| cell != target)
+                .any(|(r, c)| {
+                    board
+                        .get_cell(r as usize, c as usize)
+                        .is_some_and(|cell| cell.base == BaseTerrain::Wall)
+                });
+            if !blocked {
+                visible[row as usize * board.cols + col as usize] = true;
+            }
+        }
+    }
+}
+
+/// What one team can see during a tick.
+#[derive(Debug)]
+pub struct TeamVision {
+    team: Team,
+    cols: usize,
+    visible: Vec<bool>,
+    // Visible cells where other teams' units show, bushes can hide them
+    exposed: Vec<bool>,
+}
+
+impl TeamVision {
+    pub fn team(&self) -> Team {
+        self.team
+    }
+
+    pub fn can_see(&self, row: u16, col: u16) -> bool {
+        self.visible
+            .get(row as usize * self.cols + col as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    fn exposes(&self, row: u16, col: u16) -> bool {
+        self.exposed
+            .get(row as usize * self.cols + col as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Whether the content and animation of a cell show in the team's view. Structures are
+    /// always known, other units need sight and hide in bushes the team has no unit in.
+    pub fn reveals(&self, cell: &Cell) -> bool {
+        let (row, col) = cell.position;
+        match &cell.content {
+            Some(CellContent::Tower(..) | CellContent::Base(_)) => true,
+            Some(
+                CellContent::Champion(_, team)
+                | CellContent::Minion(_, team)
+                | CellContent::Flag(_, team),
+            ) if *team == self.team => true,
+            Some(_) => self.exposes(row, col),
+            None => self.can_see(row, col),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(row: u16, col: u16, radius: u16) -> SightSource {
+        SightSource { row, col, radius }
+    }
+
+    #[test]
+    fn test_walls_block_sight() {
+        let mut board = Board::new(5, 9);
+        for row in 0..5 {
+            board.change_base(BaseTerrain::Wall, row, 4);
+        }
+        let regions = BushRegions::new(&board);
+        let vision = regions.team_vision(&board, Team::Blue, [source(2, 2, 4)]);
+
+        assert!(vision.can_see(2, 2));
+        assert!(vision.can_see(2, 4), "the wall itself is visible");
+        assert!(!vision.can_see(2, 5), "cells behind the wall are hidden");
+        assert!(!vision.can_see(2, 7), "cells out of range are hidden");
+    }
+
+    #[test]
+    fn test_bush_hides_enemies_until_entered() {
+        let mut board = Board::new(5, 5);
+        board.change_base(BaseTerrain::Bush, 2, 3);
+        board.change_base(BaseTerrain::Bush, 2, 4);
+        board.place_cell(CellContent::Champion(2, Team::Red), 2, 4);
+        let regions = BushRegions::new(&board);
+        let hidden_cell = board.get_cell(2, 4).unwrap();
+
+        let outside = regions.team_vision(&board, Team::Blue, [source(2, 1, 4)]);
+        assert!(outside.can_see(2, 4));
+        assert!(!outside.reveals(hidden_cell));
+
+        let inside = regions.team_vision(&board, Team::Blue, [source(2, 3, 4)]);
+        assert!(inside.reveals(hidden_cell));
+
+        let allies = regions.team_vision(&board, Team::Red, []);
+        assert!(allies.reveals(hidden_cell));
+    }
+
+    #[test]
+    fn test_bush_regions_are_connected_patches() {
+        let mut board = Board::new(3, 5);
+        board.change_base(BaseTerrain::Bush, 0, 0);
+        board.change_base(BaseTerrain::Bush, 1, 0);
+        board.change_base(BaseTerrain::Bush, 1, 3);
+        let regions = BushRegions::new(&board);
+
+        assert!(regions.region_of(0, 0).is_some());
+        assert_eq!(regions.region_of(0, 0), regions.region_of(1, 0));
+        assert_ne!(regions.region_of(0, 0), regions.region_of(1, 3));
+        assert_eq!(regions.region_of(2, 2), None);
+    }
+}
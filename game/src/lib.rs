@@ -0,0 +1,5 @@
+pub mod config;
+pub mod errors;
+pub mod game;
+pub mod packet;
+pub mod session;
@@ -1,96 +1,311 @@
-use crate::game::{ClientMessage, GameManager, PlayerId};
+use bytes::BytesMut;
 use clap::Parser;
-use packet::shop_packet::{PurchaseItemPacket, ShopResponsePacket};
-use packet::start_packet::StartPacket;
+use game::config;
+use game::errors::ProtocolError;
+use game::game::map_registry::{DEFAULT_MAP_ID, MapRegistry};
+use game::game::match_result::MatchResult;
+use game::game::phase::MatchPhase;
+use game::game::replay::{REPLAY_VERSION, ReplayHeader, ReplayRecorder};
+use game::game::roster::{Roster, RosterEntry};
+use game::game::spectator::SpectatorVision;
+use game::game::{Board, ClientMessage, GameManager, PlayerId, ReconnectToken};
+use game::packet::codec::{
+    ClientPacket, FRAMED_VERSION, LEGACY_VERSION, PacketCodec, PacketReader, ServerPacket,
+};
+use game::packet::phase_packet::PauseRequest;
+use game::packet::reconnect_packet::ReconnectTokenPacket;
+use game::packet::shop_packet::ShopResponsePacket;
+use game::packet::start_packet::StartPacket;
+use game::packet::viewport_packet::ViewportPacket;
+use game::session::SessionKey;
 use std::collections::HashMap;
+use std::fs;
+use std::mem::take;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader, split};
+use std::time::SystemTime;
+use tokio::io::{AsyncWriteExt, split};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::spawn;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
-use tokio::time::{Duration, sleep};
+use tokio::task::JoinHandle;
+use tokio::time::{Duration, sleep, timeout};
+use tokio_util::codec::Encoder;
 
-mod config;
-mod errors;
-mod game;
-mod packet;
+// How long the writer tasks get to deliver the last packets once the match ends
+const FINAL_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+type WriterTasks = Arc<Mutex<Vec<JoinHandle<()>>>>;
+
+/// First packet of a connection.
+enum Handshake {
+    /// Spells, and the account name once its session token is verified
+    Join(u8, u8, Option<String>),
+    Reconnect(ReconnectToken),
+    Spectate(SpectatorVision),
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
 
 // Cli Parser
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct CliArgs {
-    #[arg(long = "port", value_name = "PORT", value_parser = clap::value_parser!(u16))]
-    port: u16,
+    #[arg(long = "port", value_name = "PORT", value_parser = clap::value_parser!(u16), required_unless_present = "check_config")]
+    port: Option<u16>,
 
-    #[arg(long = "map", value_name = "MAP_ID", value_parser = clap::value_parser!(u8))]
+    #[arg(long = "map", value_name = "MAP_ID", value_parser = clap::value_parser!(u8), conflicts_with = "map_file")]
     map_id: Option<u8>,
 
+    /// Load the board from this file instead of looking it up in the map registry
+    #[arg(long = "map-file", value_name = "PATH")]
+    map_file: Option<String>,
+
+    #[arg(long = "maps", value_name = "PATH", default_value = "game/maps.toml")]
+    map_registry: String,
+
+    #[arg(long = "stats", value_name = "PATH", default_value = "game/stats.toml")]
+    stats_path: String,
+
+    #[arg(
+        long = "spells",
+        value_name = "PATH",
+        default_value = "game/spells.toml"
+    )]
+    spells_path: String,
+
+    #[arg(long = "items", value_name = "PATH", default_value = "game/items.toml")]
+    items_path: String,
+
+    #[arg(long = "rules", value_name = "PATH", default_value = "game/rules.toml")]
+    rules_path: String,
+
     #[arg(long = "max-players", value_name = "MAX_PLAYERS", value_parser = clap::value_parser!(u8), default_value_t = 1)]
     max_players: u8,
+
+    /// Validate the configuration files against the map and exit
+    #[arg(long = "check-config")]
+    check_config: bool,
+
+    /// Reload stats, spells and items whenever the files change on disk
+    #[arg(long = "watch-config")]
+    watch_config: bool,
+
+    /// Also apply reloaded stats to entities already in play
+    #[arg(long = "rescale-live", requires = "watch_config")]
+    rescale_live: bool,
+
+    /// Key shared with the auth service, players must then join with a valid session token
+    #[arg(long = "session-key-file", value_name = "PATH")]
+    session_key_file: Option<String>,
+
+    /// JSON roster of the expected accounts with their team and spawn slot
+    #[arg(long = "roster", value_name = "PATH", conflicts_with_all = ["max_players", "roster_players"])]
+    roster_path: Option<String>,
+
+    /// Expected account, repeat for each player instead of giving a roster file
+    #[arg(long = "player", value_name = "USERNAME:TEAM:SLOT", conflicts_with = "max_players", value_parser = parse_roster_entry)]
+    roster_players: Vec<RosterEntry>,
+
+    /// Connections allowed to watch the match on top of the players
+    #[arg(long = "max-spectators", value_name = "COUNT", default_value_t = 0)]
+    max_spectators: usize,
+
+    /// Let spectators see the whole board instead of one team's vision
+    #[arg(long = "spectator-full-vision")]
+    spectator_full_vision: bool,
+
+    /// Write the match result as JSON to this file when the match ends
+    #[arg(long = "results", value_name = "PATH")]
+    results_path: Option<String>,
+
+    /// Record the configuration, the map and every player input to this replay file
+    #[arg(long = "record", value_name = "PATH")]
+    replay_path: Option<String>,
 }
 
-async fn handle_client(stream: TcpStream, addr: SocketAddr, game_manager: Arc<Mutex<GameManager>>) {
-    println!("Handler task started for connection from: {:?}", addr);
+fn parse_roster_entry(value: &str) -> Result<RosterEntry, String> {
+    RosterEntry::parse(value).map_err(|e| e.to_string())
+}
 
-    let (reader, mut writer) = split(stream);
-    let mut buf_reader = BufReader::new(reader);
-
-    // --- Initial Packet: Spell Selection ---
-    let mut initial_packet_header = [0; 2]; // Read version and code
-    if buf_reader
-        .read_exact(&mut initial_packet_header)
-        .await
-        .is_err()
-    {
-        eprintln!("Error reading initial packet header from {:?}", addr);
-        if let Err(e) = writer.shutdown().await {
-            eprintln!("Error shutting down stream for {:?}: {}", addr, e);
+/// Resolves the map file, either given directly or looked up by id in the registry.
+fn resolve_map_path(args: &CliArgs) -> Result<String, String> {
+    if let Some(path) = &args.map_file {
+        return Ok(path.clone());
+    }
+    let registry = MapRegistry::load(&args.map_registry)
+        .map_err(|e| format!("Failed to load map registry {}: {}", args.map_registry, e))?;
+    let map_id = args.map_id.unwrap_or(DEFAULT_MAP_ID);
+    let entry = registry
+        .get(map_id)
+        .map_err(|e| format!("Failed to select map: {}", e))?;
+    println!("Selected map {} ({})", entry.id, entry.name);
+    Ok(entry.path.clone())
+}
+
+/// Loads the configuration, the board and the roster, then checks them against each other.
+/// The map file contents are returned along with the board for replays.
+fn load_and_validate(
+    args: &CliArgs,
+) -> Result<(config::GameConfig, Board, String, Option<Roster>), String> {
+    let config = config::GameConfig::load(
+        &args.stats_path,
+        &args.spells_path,
+        &args.items_path,
+        &args.rules_path,
+    )
+    .map_err(|e| format!("Failed to load game configuration: {}", e))?;
+    let map_path = resolve_map_path(args)?;
+    let map = fs::read_to_string(&map_path)
+        .map_err(|e| format!("Failed to read the map {}: {}", map_path, e))?;
+    let board = Board::from_json_str(&map, &map_path)
+        .map_err(|e| format!("Failed to initialize the board from {}: {}", map_path, e))?;
+
+    let roster = match &args.roster_path {
+        Some(path) => {
+            Some(Roster::load(path).map_err(|e| format!("Failed to load roster {}: {}", path, e))?)
+        }
+        None if !args.roster_players.is_empty() => {
+            Some(Roster::new(args.roster_players.clone(), "--player"))
+        }
+        None => None,
+    };
+
+    let mut issues = config.validate(&board).err().unwrap_or_default();
+    let player_issues = match &roster {
+        Some(roster) => roster.validate(&board),
+        None => config::validate_spawn_count(&board, args.max_players),
+    };
+    if let Err(player_issues) = player_issues {
+        issues.extend(player_issues);
+    }
+    if !issues.is_empty() {
+        let mut message = format!("Found {} configuration issue(s):", issues.len());
+        for issue in issues {
+            message.push_str(&format!("\n  {}", issue));
         }
-        return;
+        return Err(message);
     }
+    Ok((config, board, map, roster))
+}
 
-    let version = initial_packet_header[0];
-    let code = initial_packet_header[1];
+async fn handle_client(
+    stream: TcpStream,
+    addr: SocketAddr,
+    game_manager: Arc<Mutex<GameManager>>,
+    writer_tasks: WriterTasks,
+    session_key: Option<Arc<SessionKey>>,
+) {
+    println!("Handler task started for connection from: {:?}", addr);
 
-    let (spell1, spell2) = if version == 1 && code == 13 {
-        // Code for SpellSelectionPacket
-        let mut spell_payload = [0; 2]; // Read spell1 and spell2
-        if buf_reader.read_exact(&mut spell_payload).await.is_err() {
-            eprintln!("Error reading spell payload from {:?}", addr);
+    let (reader, mut writer) = split(stream);
+    let mut packets = PacketReader::new(reader);
+
+    // --- Initial Packet: Spell Selection, Join or Reconnect ---
+    let handshake = match packets.next().await {
+        Ok(Some(ClientPacket::SpellSelection { .. })) if session_key.is_some() => {
+            Err("Session token required".to_string())
+        }
+        Ok(Some(ClientPacket::SpellSelection { spell1, spell2 })) => {
+            Ok(Handshake::Join(spell1, spell2, None))
+        }
+        Ok(Some(ClientPacket::Join(packet))) => match &session_key {
+            Some(key) => key
+                .verify(&packet.session_token, unix_now())
+                .map(|username| Handshake::Join(packet.spell1, packet.spell2, Some(username)))
+                .map_err(|e| format!("Invalid session token: {}", e)),
+            None => Ok(Handshake::Join(packet.spell1, packet.spell2, None)),
+        },
+        Ok(Some(ClientPacket::Reconnect(packet))) => Ok(Handshake::Reconnect(packet.token)),
+        Ok(Some(ClientPacket::Spectate(packet))) => Ok(Handshake::Spectate(packet.vision)),
+        Ok(Some(packet)) => Err(format!("Invalid initial packet {:?}", packet)),
+        Ok(None) => Err("Connection closed before the initial packet".to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    let handshake = match handshake {
+        Ok(handshake) => handshake,
+        Err(e) => {
+            eprintln!("Rejecting handshake from {:?}: {}", addr, e);
             if let Err(e) = writer.shutdown().await {
                 eprintln!("Error shutting down stream for {:?}: {}", addr, e);
             }
             return;
         }
-        (spell_payload[0], spell_payload[1])
-    } else {
-        eprintln!(
-            "Invalid initial packet from {:?}: Version={}, Code={}",
-            addr, version, code
-        );
-        if let Err(e) = writer.shutdown().await {
-            eprintln!("Error shutting down stream for {:?}: {}", addr, e);
-        }
-        return;
     };
+    let version = packets.version().unwrap_or(LEGACY_VERSION);
 
     let player_id: PlayerId;
     let (tx, mut rx) = mpsc::channel::<ClientMessage>(32);
+    // Lets the cleanup tell whether a reconnect replaced this connection
+    let own_channel = tx.downgrade();
+    let started_countdown: bool;
+    let is_spectator = matches!(handshake, Handshake::Spectate(_));
 
     {
         let mut manager = game_manager.lock().await;
-        if let Some(id) = manager.add_player(spell1, spell2) {
+        let was_lobby = manager.phase() == &MatchPhase::Lobby;
+        let joined = match handshake {
+            Handshake::Join(spell1, spell2, ref username) => {
+                let joined = manager.add_player(spell1, spell2, username.clone());
+                if let Some(id) = joined {
+                    println!(
+                        "Player {} {} ({:?}) joined with spells {} and {}",
+                        id,
+                        manager.username_of(id),
+                        addr,
+                        spell1,
+                        spell2
+                    );
+                }
+                joined
+            }
+            Handshake::Reconnect(token) => {
+                let joined = manager.reconnect_player(token);
+                if let Some(id) = joined {
+                    println!(
+                        "Player {} {} ({:?}) is back",
+                        id,
+                        manager.username_of(id),
+                        addr
+                    );
+                }
+                joined
+            }
+            Handshake::Spectate(vision) => {
+                let joined = manager.add_spectator(vision);
+                if let Some(id) = joined {
+                    println!("Spectator {} ({:?}) is watching", id, addr);
+                }
+                joined
+            }
+        };
+        if let Some(id) = joined {
             player_id = id;
+            started_countdown =
+                was_lobby && matches!(manager.phase(), MatchPhase::Countdown { .. });
             manager.client_channel.insert(id, tx);
+            manager.track_frames(id, version >= FRAMED_VERSION);
+            if let Some(token) = manager.reconnect_token_of(id) {
+                let packet = ReconnectTokenPacket::new(id as u8, token);
+                manager.send_to_player(id, ServerPacket::ReconnectToken(packet));
+            }
+        } else {
+            let rejection_msg = match handshake {
+                Handshake::Join(..) => "Server is full or account already joined.\n",
+                Handshake::Reconnect(_) => "Unknown or expired reconnect token.\n",
+                Handshake::Spectate(_) => "No spectator slot or vision not allowed.\n",
+            };
             println!(
-                "Player {} ({:?}) joined with spells {} and {}",
-                id, addr, spell1, spell2
+                "Rejecting connection from {:?}: {}",
+                addr,
+                rejection_msg.trim()
             );
-        } else {
-            println!("Rejecting connection from {:?}: Server is full.", addr);
-            let rejection_msg = "Server is full. Try again later.\n";
             if let Err(e) = writer.write_all(rejection_msg.as_bytes()).await {
                 eprintln!("Error sending rejection message to {:?}: {}", addr, e);
             }
@@ -104,9 +319,16 @@ async fn handle_client(stream: TcpStream, addr: SocketAddr, game_manager: Arc<Mu
     // -- Split Stream and Spawn Writer Task --
     // The reader and writer are already split from the initial read
     // Spawn a separate task that owns the 'writer' and listens on 'rx'
-    let _ = spawn(async move {
+    let writer_task = spawn(async move {
+        let mut codec = PacketCodec::with_version(version);
+        let mut buffer = BytesMut::new();
         while let Some(message) = rx.recv().await {
-            if writer.write_all(&message).await.is_err() {
+            buffer.clear();
+            if let Err(e) = codec.encode(message, &mut buffer) {
+                eprintln!("Error encoding message to client {}: {}", player_id, e);
+                continue;
+            }
+            if writer.write_all(&buffer).await.is_err() {
                 eprintln!(
                     "Error writting message to client {}, connection likely closed.",
                     player_id
@@ -120,95 +342,107 @@ async fn handle_client(stream: TcpStream, addr: SocketAddr, game_manager: Arc<Mu
             eprintln!("Error shutting down writer for player {}: {}", player_id, e);
         }
     });
+    writer_tasks.lock().await.push(writer_task);
 
     // -- Verify if we can start game --
     // Scope to release the lock
     {
         let manager = game_manager.lock().await;
-        if manager.game_started {
+        if started_countdown {
             println!("Sending StartPacket to all client");
             for player_id in manager.client_channel.keys() {
-                let message = StartPacket::new(0).serialize();
-                manager.send_to_player(*player_id, message).await;
+                let message = ServerPacket::Start(StartPacket::new(0));
+                manager.send_to_player(*player_id, message);
             }
+        } else if manager.phase().shows_board() {
+            // A returning player goes straight back to the game view
+            manager.send_to_player(player_id, ServerPacket::Start(StartPacket::new(0)));
         }
     }
 
     // -- Read Client Action loop --
     println!("Listening for Player {} ({:?}) actions...", player_id, addr);
     loop {
-        let mut packet_header = [0; 2]; // Read version and code
-        if buf_reader.read_exact(&mut packet_header).await.is_err() {
-            eprintln!("Error reading packet header from {:?}", addr);
-            break;
-        }
-        println!("Packet header: {:?}", packet_header);
-
-        let version = packet_header[0];
-        let code = packet_header[1];
-
-        if version != 1 {
-            eprintln!("Invalid packet version from {:?}: {}", addr, version);
-            break;
-        }
+        let packet = match packets.next().await {
+            Ok(Some(packet)) => packet,
+            Ok(None) => {
+                println!("Player {} ({:?}) closed the connection", player_id, addr);
+                break;
+            }
+            Err(e @ ProtocolError::Malformed { .. }) => {
+                eprintln!("Ignoring packet from {:?}: {}", addr, e);
+                continue;
+            }
+            Err(e) => {
+                eprintln!("Error reading packet from {:?}: {}", addr, e);
+                break;
+            }
+        };
 
-        match code {
-            8 => {
-                // Action Packet
-                let mut action_payload = [0; 1];
-                if buf_reader.read_exact(&mut action_payload).await.is_err() {
-                    eprintln!("Error reading action payload from {:?}", addr);
-                    break;
-                }
+        match packet {
+            ClientPacket::Camera(request) if is_spectator => {
                 let mut manager = game_manager.lock().await;
-                manager.store_player_action(player_id, action_payload[0]);
+                manager.set_camera(player_id, request.camera);
+            }
+            ClientPacket::Action(_)
+            | ClientPacket::ShopRequest
+            | ClientPacket::PurchaseItem(_)
+            | ClientPacket::Pause(_)
+                if is_spectator =>
+            {
+                eprintln!("Ignoring player packet from spectator {}", player_id);
             }
-            14 => {
-                // Shop Request Packet
+            ClientPacket::Action(action) => {
+                let mut manager = game_manager.lock().await;
+                manager.store_player_action(player_id, action);
+            }
+            ClientPacket::ShopRequest => {
                 println!("Got a requests shop packet");
                 let manager = game_manager.lock().await;
                 if let Some(champion) = manager.get_champion(&player_id) {
                     let message =
-                        ShopResponsePacket::new(champion.stats(), champion.get_inventory())
-                            .serialize();
-                    manager.send_to_player(player_id, message).await;
+                        ShopResponsePacket::new(champion.stats(), champion.get_inventory());
+                    manager.send_to_player(player_id, ServerPacket::ShopResponse(message));
                 } else {
                     println!("Player: {} champion not found", player_id);
                 }
             }
-            16 => {
-                // Purchase Item Packet
-                let mut purchase_payload = [0; 2];
-                if buf_reader.read_exact(&mut purchase_payload).await.is_err() {
-                    eprintln!("Error reading purchase payload from {:?}", addr);
-                    break;
-                }
-                if let Ok(packet) = PurchaseItemPacket::deserialize(&purchase_payload) {
-                    let mut manager = game_manager.lock().await;
-                    if let Some(item) = manager
-                        .get_config()
-                        .items
-                        .get(&packet.item_id.into())
-                        .cloned()
-                    {
-                        if let Some(champion) = manager.get_mut_champion(&player_id) {
-                            if let Err(e) = champion.add_item(item) {
-                                eprintln!("Player {} failed to buy item: {}", player_id, e);
-                            } else {
-                                // Send back the updated champion stats
-                                let message = ShopResponsePacket::new(
-                                    champion.stats(),
-                                    champion.get_inventory(),
-                                )
-                                .serialize();
-                                manager.send_to_player(player_id, message).await;
-                            }
-                        }
+            ClientPacket::PurchaseItem(item_id) => {
+                let mut manager = game_manager.lock().await;
+                match manager.purchase_item(player_id, item_id.into()) {
+                    Ok(champion) => {
+                        // Send back the updated champion stats
+                        let message =
+                            ShopResponsePacket::new(champion.stats(), champion.get_inventory());
+                        manager.send_to_player(player_id, ServerPacket::ShopResponse(message));
                     }
+                    Err(e) => eprintln!("Player {} failed to buy item: {}", player_id, e),
                 }
             }
-            _ => {
-                eprintln!("Invalid packet code from {:?}: {}", addr, code);
+            ClientPacket::Pause(request) => {
+                let mut manager = game_manager.lock().await;
+                match request {
+                    PauseRequest::Pause => manager.request_pause(player_id),
+                    PauseRequest::Resume => manager.vote_resume(player_id),
+                }
+            }
+            ClientPacket::Viewport(request) => {
+                let mut manager = game_manager.lock().await;
+                let viewport = manager.set_viewport(player_id, request.rows, request.cols);
+                manager.send_to_player(
+                    player_id,
+                    ServerPacket::Viewport(ViewportPacket::new(viewport)),
+                );
+            }
+            ClientPacket::FrameAck(tick) => {
+                let mut manager = game_manager.lock().await;
+                manager.ack_frame(player_id, tick);
+            }
+            ClientPacket::Unknown { code } => {
+                eprintln!("Skipping unknown packet {} from {:?}", code, addr);
+            }
+            packet => {
+                eprintln!("Unexpected packet from {:?}: {:?}", addr, packet);
                 break;
             }
         }
@@ -218,7 +452,23 @@ async fn handle_client(stream: TcpStream, addr: SocketAddr, game_manager: Arc<Mu
     // -- CLeanup --
     {
         let mut manager = game_manager.lock().await;
-        manager.remove_player(&player_id);
+        let replaced = match (
+            own_channel.upgrade(),
+            manager.client_channel.get(&player_id),
+        ) {
+            (Some(own), Some(current)) => !own.same_channel(current),
+            _ => true,
+        };
+        if is_spectator {
+            manager.remove_spectator(player_id);
+        } else if replaced {
+            println!(
+                "Player {} connection was replaced by a reconnect",
+                player_id
+            );
+        } else {
+            manager.remove_player(&player_id);
+        }
     }
     println!(
         "Handler task for player {} ({:?}) finished cleanup.",
@@ -226,64 +476,229 @@ async fn handle_client(stream: TcpStream, addr: SocketAddr, game_manager: Arc<Mu
     );
 }
 
+/// Last modification time of each balance file, used to detect edits.
+fn balance_files_modified(sources: &config::ConfigSources) -> Vec<Option<SystemTime>> {
+    [&sources.stats, &sources.spells, &sources.items]
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+/// Polls the balance files and swaps in a new configuration between ticks when they change.
+async fn watch_config(game_manager: Arc<Mutex<GameManager>>, rescale_live: bool) {
+    let sources = game_manager.lock().await.get_config().sources.clone();
+    let mut last_modified = balance_files_modified(&sources);
+    loop {
+        sleep(Duration::from_secs(1)).await;
+        let modified = balance_files_modified(&sources);
+        if modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+
+        let config = match config::GameConfig::load(
+            &sources.stats,
+            &sources.spells,
+            &sources.items,
+            &sources.rules,
+        ) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Config reload skipped, failed to load: {}", e);
+                continue;
+            }
+        };
+        let mut manager = game_manager.lock().await;
+        if let Err(issues) = config.validate(manager.get_board()) {
+            eprintln!("Config reload skipped, found {} issue(s):", issues.len());
+            for issue in issues {
+                eprintln!("  {}", issue);
+            }
+            continue;
+        }
+        manager.reload_config(config, rescale_live);
+        println!("Config reloaded (rescale live entities: {})", rescale_live);
+    }
+}
+
+/// Flushes the last packets, waits for the writers to close their sockets and reports the result.
+async fn finish_match(
+    result: &MatchResult,
+    channels: HashMap<PlayerId, mpsc::Sender<ClientMessage>>,
+    mut final_packets: HashMap<PlayerId, Vec<ClientMessage>>,
+    writer_tasks: WriterTasks,
+    results_path: Option<&str>,
+) {
+    for (player_id, sender) in channels {
+        for message in final_packets.remove(&player_id).unwrap_or_default() {
+            if let Err(e) = sender.send(message).await {
+                eprintln!("Error sending final packet to player {}: {}", player_id, e);
+            }
+        }
+    }
+
+    // Every sender is dropped by now, so each writer drains its queue and shuts down
+    let writers = take(&mut *writer_tasks.lock().await);
+    let flushed = timeout(FINAL_FLUSH_TIMEOUT, async {
+        for writer in writers {
+            if let Err(e) = writer.await {
+                eprintln!("Writer task failed: {}", e);
+            }
+        }
+    })
+    .await;
+    if flushed.is_err() {
+        eprintln!("Timed out waiting for clients to receive the final packets");
+    }
+
+    match serde_json::to_string(result) {
+        Ok(json) => {
+            println!("Match result: {}", json);
+            if let Some(path) = results_path
+                && let Err(e) = fs::write(path, json)
+            {
+                eprintln!("Failed to write match result to {}: {}", path, e);
+            }
+        }
+        Err(e) => eprintln!("Failed to serialize match result: {}", e),
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = CliArgs::parse();
-    let address = format!("0.0.0.0:{}", args.port);
+
+    let (config, board, map, roster) = match load_and_validate(&args) {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let session_key = match &args.session_key_file {
+        Some(path) => match SessionKey::load(path) {
+            Ok(key) => Some(Arc::new(key)),
+            Err(e) => {
+                eprintln!("Failed to load session key: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => {
+            println!("No session key given, players join without authentication");
+            None
+        }
+    };
+    // Roster entries are matched against the verified account names
+    if roster.is_some() && session_key.is_none() && !args.check_config {
+        eprintln!("A roster needs --session-key-file to know which account is joining");
+        std::process::exit(1);
+    }
+    if args.check_config {
+        println!("Configuration is valid.");
+        return Ok(());
+    }
+
+    let address = format!("0.0.0.0:{}", args.port.expect("--port is required"));
     let listener = TcpListener::bind(&address).await?;
     println!("Server listening  on {}", address);
 
-    let config = config::GameConfig::load("game/stats.toml", "game/spells.toml", "game/items.toml")
-        .expect("Failed to load game configuration");
-    let game_manager = GameManager::new(config, args.max_players);
+    // A roster sets the number of players, --max-players is only used without one
+    let max_players = roster
+        .as_ref()
+        .map_or(args.max_players, |roster| roster.len() as u8);
+    let header = ReplayHeader {
+        version: REPLAY_VERSION,
+        config: config.files.clone(),
+        map,
+        max_players,
+        roster: roster.as_ref().map(|roster| roster.players.clone()),
+    };
+    let mut game_manager = GameManager::new(config, board, max_players);
+    if let Some(roster) = roster {
+        println!("Expecting {} players from the roster", roster.len());
+        game_manager.set_roster(roster);
+    }
+    if let Some(path) = &args.replay_path {
+        match ReplayRecorder::create(path, &header) {
+            Ok(recorder) => {
+                println!("Recording the match to {}", path);
+                game_manager.record_replay(recorder);
+            }
+            Err(e) => {
+                eprintln!("Failed to create replay {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+    }
+    game_manager.allow_spectators(args.max_spectators, args.spectator_full_vision);
     let arc_gm = Arc::new(Mutex::new(game_manager));
     println!("GameManager created and wrapped.");
 
-    // -- Game Tick Task --
-    let tick_manager = Arc::clone(&arc_gm);
+    if args.watch_config {
+        spawn(watch_config(Arc::clone(&arc_gm), args.rescale_live));
+    }
+
+    let writer_tasks: WriterTasks = Arc::new(Mutex::new(Vec::new()));
+
+    // -- Accept Connections Loop --
+    let accept_manager = Arc::clone(&arc_gm);
+    let accept_writers = Arc::clone(&writer_tasks);
     spawn(async move {
         loop {
-            let game_started: bool;
-            {
-                let manager = tick_manager.lock().await;
-                game_started = manager.game_started;
-            }
-            if game_started {
-                sleep(Duration::from_millis(40)).await;
-
-                let updates: HashMap<PlayerId, ClientMessage>;
-                {
-                    let mut manager = tick_manager.lock().await;
-                    updates = manager.game_tick();
-                    manager.clear_action();
+            match listener.accept().await {
+                Ok((stream, addr)) => {
+                    println!("Accepted connection form {:?}", addr);
+                    let game_manager_for_task = Arc::clone(&accept_manager);
+                    let writers_for_task = Arc::clone(&accept_writers);
+                    let session_key_for_task = session_key.clone();
+                    spawn(async move {
+                        handle_client(
+                            stream,
+                            addr,
+                            game_manager_for_task,
+                            writers_for_task,
+                            session_key_for_task,
+                        )
+                        .await;
+                    });
                 }
-                let manager = tick_manager.lock().await;
-                for (player_id, message) in updates {
-                    println!("Message length to be sent: {:?}", message.len());
-                    manager.send_to_player(player_id, message).await;
+                Err(e) => {
+                    eprintln!("Error accepting connection: {}", e);
+                    sleep(Duration::from_secs(1)).await;
                 }
-                drop(manager);
-            } else {
-                sleep(Duration::from_secs(5)).await;
-                println!("Waiting for all players to connect...");
             }
         }
     });
 
-    // -- Accept Connections Loop --
+    // -- Game Tick Loop --
+    // Runs until a base is destroyed
     loop {
-        match listener.accept().await {
-            Ok((stream, addr)) => {
-                println!("Accepted connection form {:?}", addr);
-                let game_manager_for_task = Arc::clone(&arc_gm);
-                spawn(async move {
-                    handle_client(stream, addr, game_manager_for_task).await;
-                });
-            }
-            Err(e) => {
-                eprintln!("Error accepting connection: {}", e);
-                let _ = sleep(Duration::from_secs(1));
+        let tick_duration = arc_gm.lock().await.tick_duration();
+        sleep(tick_duration).await;
+
+        let mut manager = arc_gm.lock().await;
+        let updates = manager.game_tick();
+        manager.clear_action();
+
+        if let Some(result) = manager.match_result().cloned() {
+            let channels = manager.take_client_channels();
+            drop(manager);
+            finish_match(
+                &result,
+                channels,
+                updates,
+                writer_tasks,
+                args.results_path.as_deref(),
+            )
+            .await;
+            std::process::exit(result.exit_code());
+        }
+
+        for (player_id, messages) in updates {
+            for message in messages {
+                manager.send_to_player(player_id, message);
             }
         }
+        drop(manager);
     }
 }
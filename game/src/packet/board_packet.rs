@@ -13,11 +13,15 @@ pub struct BoardPacket {
     pub level: u8,
     pub xp: u32,
     pub xp_needed: u32,
+    /// Seconds before a dead champion respawns, 0 while alive. Only sent in a `FramePacket`,
+    /// the version 1 layout stays as it was.
+    pub respawn_in: u16,
     pub length: u16,
     pub encoded_board: Vec<u8>,
 }
 
 impl BoardPacket {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         health: u16,
         max_health: u16,
@@ -40,6 +44,7 @@ impl BoardPacket {
             level,
             xp,
             xp_needed,
+            respawn_in: 0,
             length,
             encoded_board,
         }
@@ -101,6 +106,7 @@ mod tests {
         assert_eq!(packet.level, 1);
         assert_eq!(packet.xp, 0);
         assert_eq!(packet.xp_needed, 35);
+        assert_eq!(packet.respawn_in, 0);
         assert_eq!(packet.length, expected_length);
         assert_eq!(packet.encoded_board, encoded_board_data);
     }
@@ -115,7 +121,7 @@ mod tests {
         let level = 1;
         let xp = 0;
         let xp_needed = 35;
-        let packet = BoardPacket::new(
+        let mut packet = BoardPacket::new(
             health,
             max_health,
             mana,
@@ -125,6 +131,7 @@ mod tests {
             xp_needed,
             encoded_board_data.clone(),
         );
+        packet.respawn_in = 7;
 
         let serialized_buffer = packet.serialize();
 
@@ -140,6 +147,7 @@ mod tests {
         expected_buffer.put_u8(packet.level);
         expected_buffer.put_u32(packet.xp);
         expected_buffer.put_u32(packet.xp_needed);
+        // No respawn_in in the version 1 layout
         expected_buffer.put_u16(packet.length); // encoded_board_data.len() as u16 (as BigEndian)
         expected_buffer.extend_from_slice(&packet.encoded_board); // [0, 1, 1, 2, 3, 1, 1]
 
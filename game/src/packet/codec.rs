@@ -0,0 +1,443 @@
+use crate::errors::ProtocolError;
+use crate::packet::board_packet::BoardPacket;
+use crate::packet::end_game_packet::EndGamePacket;
+use crate::packet::frame_packet::FramePacket;
+use crate::packet::join_packet::JoinPacket;
+use crate::packet::kill_packet::KillPacket;
+use crate::packet::phase_packet::{PauseRequest, PhasePacket};
+use crate::packet::reconnect_packet::{ReconnectPacket, ReconnectTokenPacket};
+use crate::packet::shop_packet::{PurchaseItemPacket, ShopResponsePacket};
+use crate::packet::spectator_packet::{CameraRequest, SpectatePacket};
+use crate::packet::start_packet::StartPacket;
+use crate::packet::viewport_packet::{ViewportPacket, ViewportRequest};
+use bytes::{Buf, BufMut, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Original format: `[version][code][payload]`, the payload size is implied by the code.
+pub const LEGACY_VERSION: u8 = 1;
+/// Framed format: `[version][code][payload length u16][payload]`.
+pub const FRAMED_VERSION: u8 = 2;
+pub const MAX_VERSION: u8 = FRAMED_VERSION;
+
+/// Largest payload a client may announce, enough for a join with a full session token.
+pub const MAX_PAYLOAD_LEN: usize = JoinPacket::FIXED_PAYLOAD_LEN + 512;
+
+const ACTION: u8 = 8;
+const SPELL_SELECTION: u8 = 13;
+const SHOP_REQUEST: u8 = 14;
+const PURCHASE_ITEM: u8 = 16;
+const PAUSE: u8 = 18;
+const RECONNECT: u8 = 20;
+const JOIN: u8 = 21;
+const FRAME_ACK: u8 = 22;
+const VIEWPORT: u8 = 23;
+const SPECTATE: u8 = 25;
+const CAMERA: u8 = 26;
+
+/// Every packet a client can send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientPacket {
+    SpellSelection {
+        spell1: u8,
+        spell2: u8,
+    },
+    Join(JoinPacket),
+    Reconnect(ReconnectPacket),
+    Action(u8),
+    ShopRequest,
+    PurchaseItem(u16),
+    Pause(PauseRequest),
+    /// Tick of the last board frame the client applied
+    FrameAck(u32),
+    Viewport(ViewportRequest),
+    Spectate(SpectatePacket),
+    Camera(CameraRequest),
+    /// A framed packet this server does not know, its payload was skipped
+    Unknown {
+        code: u8,
+    },
+}
+
+impl ClientPacket {
+    fn parse(code: u8, payload: &[u8]) -> Result<Self, ProtocolError> {
+        let malformed = |reason| ProtocolError::Malformed { code, reason };
+        match code {
+            ACTION => match payload {
+                [action] => Ok(ClientPacket::Action(*action)),
+                _ => Err(malformed("Action payload must be 1 byte long")),
+            },
+            SPELL_SELECTION => match payload {
+                [spell1, spell2] => Ok(ClientPacket::SpellSelection {
+                    spell1: *spell1,
+                    spell2: *spell2,
+                }),
+                _ => Err(malformed("Spell selection payload must be 2 bytes long")),
+            },
+            SHOP_REQUEST => Ok(ClientPacket::ShopRequest),
+            PURCHASE_ITEM => PurchaseItemPacket::deserialize(payload)
+                .map(|packet| ClientPacket::PurchaseItem(packet.item_id))
+                .map_err(malformed),
+            PAUSE => PauseRequest::deserialize(payload)
+                .map(ClientPacket::Pause)
+                .map_err(malformed),
+            RECONNECT => ReconnectPacket::deserialize(payload)
+                .map(ClientPacket::Reconnect)
+                .map_err(malformed),
+            JOIN => JoinPacket::deserialize(payload)
+                .map(ClientPacket::Join)
+                .map_err(malformed),
+            FRAME_ACK => match payload {
+                [a, b, c, d] => Ok(ClientPacket::FrameAck(u32::from_be_bytes([*a, *b, *c, *d]))),
+                _ => Err(malformed("Frame ack payload must be 4 bytes long")),
+            },
+            VIEWPORT => ViewportRequest::deserialize(payload)
+                .map(ClientPacket::Viewport)
+                .map_err(malformed),
+            SPECTATE => SpectatePacket::deserialize(payload)
+                .map(ClientPacket::Spectate)
+                .map_err(malformed),
+            CAMERA => CameraRequest::deserialize(payload)
+                .map(ClientPacket::Camera)
+                .map_err(malformed),
+            code => Ok(ClientPacket::Unknown { code }),
+        }
+    }
+
+    /// Payload size of a legacy packet, `None` until enough bytes are buffered to tell.
+    fn legacy_payload_len(code: u8, payload: &[u8]) -> Result<Option<usize>, ProtocolError> {
+        let len = match code {
+            ACTION | PAUSE | SPECTATE => 1,
+            CAMERA => 5,
+            SPELL_SELECTION | PURCHASE_ITEM => 2,
+            SHOP_REQUEST => 0,
+            RECONNECT => 8,
+            FRAME_ACK | VIEWPORT => 4,
+            JOIN => match payload.get(..JoinPacket::FIXED_PAYLOAD_LEN) {
+                Some(fixed) => {
+                    let fixed = fixed.try_into().expect("slice has the fixed length");
+                    JoinPacket::FIXED_PAYLOAD_LEN + JoinPacket::token_len(fixed)
+                }
+                None => return Ok(None),
+            },
+            code => return Err(ProtocolError::UnknownLegacyPacket(code)),
+        };
+        Ok(Some(len))
+    }
+}
+
+/// Every packet the server sends.
+#[derive(Debug)]
+pub enum ServerPacket {
+    Start(StartPacket),
+    Board(BoardPacket),
+    Frame(FramePacket),
+    EndGame(EndGamePacket),
+    ShopResponse(ShopResponsePacket),
+    Phase(PhasePacket),
+    ReconnectToken(ReconnectTokenPacket),
+    Viewport(ViewportPacket),
+    Kill(KillPacket),
+}
+
+impl ServerPacket {
+    /// The packet in the legacy `[1][code][payload]` layout.
+    fn serialize(&self) -> BytesMut {
+        match self {
+            ServerPacket::Start(packet) => packet.serialize(),
+            ServerPacket::Board(packet) => packet.serialize(),
+            ServerPacket::Frame(packet) => packet.serialize(),
+            ServerPacket::EndGame(packet) => BytesMut::from(&packet.serialize()[..]),
+            ServerPacket::ShopResponse(packet) => packet.serialize(),
+            ServerPacket::Phase(packet) => packet.serialize(),
+            ServerPacket::ReconnectToken(packet) => packet.serialize(),
+            ServerPacket::Viewport(packet) => packet.serialize(),
+            ServerPacket::Kill(packet) => packet.serialize(),
+        }
+    }
+}
+
+/// Client and server framing for one connection.
+///
+/// The version of the first client packet picks the format used both ways for the rest of
+/// the connection. Framed packets with an unknown code are skipped, while an unknown legacy
+/// packet is fatal since its length cannot be known.
+#[derive(Debug, Default)]
+pub struct PacketCodec {
+    version: Option<u8>,
+}
+
+impl PacketCodec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Codec for a connection whose version was already negotiated.
+    pub fn with_version(version: u8) -> Self {
+        PacketCodec {
+            version: Some(version),
+        }
+    }
+
+    /// Negotiated version, `None` until the first packet is decoded.
+    pub fn version(&self) -> Option<u8> {
+        self.version
+    }
+}
+
+impl Decoder for PacketCodec {
+    type Item = ClientPacket;
+    type Error = ProtocolError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<ClientPacket>, ProtocolError> {
+        let Some(&version) = src.first() else {
+            return Ok(None);
+        };
+        match self.version {
+            None if (LEGACY_VERSION..=MAX_VERSION).contains(&version) => {
+                self.version = Some(version)
+            }
+            None => return Err(ProtocolError::UnsupportedVersion(version)),
+            Some(expected) if expected != version => {
+                return Err(ProtocolError::VersionMismatch {
+                    expected,
+                    found: version,
+                });
+            }
+            Some(_) => {}
+        }
+        let Some(&code) = src.get(1) else {
+            return Ok(None);
+        };
+
+        let (header_len, payload_len) = if version == LEGACY_VERSION {
+            match ClientPacket::legacy_payload_len(code, &src[2..])? {
+                Some(len) => (2, len),
+                None => return Ok(None),
+            }
+        } else {
+            match src.get(2..4) {
+                Some(len) => (4, u16::from_be_bytes([len[0], len[1]]) as usize),
+                None => return Ok(None),
+            }
+        };
+        if payload_len > MAX_PAYLOAD_LEN {
+            return Err(ProtocolError::FrameTooLong {
+                code,
+                len: payload_len,
+            });
+        }
+        if src.len() < header_len + payload_len {
+            src.reserve(header_len + payload_len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(header_len);
+        let payload = src.split_to(payload_len);
+        ClientPacket::parse(code, &payload).map(Some)
+    }
+}
+
+impl Encoder<ServerPacket> for PacketCodec {
+    type Error = ProtocolError;
+
+    fn encode(&mut self, packet: ServerPacket, dst: &mut BytesMut) -> Result<(), ProtocolError> {
+        let legacy = packet.serialize();
+        match self.version.unwrap_or(LEGACY_VERSION) {
+            LEGACY_VERSION => dst.extend_from_slice(&legacy),
+            version => {
+                let payload = &legacy[2..];
+                dst.reserve(4 + payload.len());
+                dst.put_u8(version);
+                dst.put_u8(legacy[1]);
+                dst.put_u16(payload.len() as u16);
+                dst.extend_from_slice(payload);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads client packets from a stream through a [`PacketCodec`].
+pub struct PacketReader<R> {
+    reader: R,
+    buffer: BytesMut,
+    codec: PacketCodec,
+}
+
+impl<R: AsyncRead + Unpin> PacketReader<R> {
+    pub fn new(reader: R) -> Self {
+        PacketReader {
+            reader,
+            buffer: BytesMut::with_capacity(64),
+            codec: PacketCodec::new(),
+        }
+    }
+
+    pub fn version(&self) -> Option<u8> {
+        self.codec.version()
+    }
+
+    /// Next packet, `None` once the client closed the connection. Cancel safe, bytes read
+    /// so far stay buffered for the next call.
+    pub async fn next(&mut self) -> Result<Option<ClientPacket>, ProtocolError> {
+        loop {
+            if let Some(packet) = self.codec.decode(&mut self.buffer)? {
+                return Ok(Some(packet));
+            }
+            if self.reader.read_buf(&mut self.buffer).await? == 0 {
+                return match self.buffer.is_empty() {
+                    true => Ok(None),
+                    false => Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into()),
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(codec: &mut PacketCodec, bytes: &[u8]) -> Vec<ClientPacket> {
+        let mut buffer = BytesMut::from(bytes);
+        let mut packets = Vec::new();
+        while let Some(packet) = codec.decode(&mut buffer).unwrap() {
+            packets.push(packet);
+        }
+        assert!(buffer.is_empty());
+        packets
+    }
+
+    #[test]
+    fn test_legacy_packets_negotiate_version_one() {
+        let mut codec = PacketCodec::new();
+        let packets = decode_all(
+            &mut codec,
+            &[1, 13, 2, 3, 1, 8, 4, 1, 14, 1, 16, 0, 7, 1, 22, 0, 0, 1, 0],
+        );
+        assert_eq!(codec.version(), Some(LEGACY_VERSION));
+        assert_eq!(
+            packets,
+            vec![
+                ClientPacket::SpellSelection {
+                    spell1: 2,
+                    spell2: 3
+                },
+                ClientPacket::Action(4),
+                ClientPacket::ShopRequest,
+                ClientPacket::PurchaseItem(7),
+                ClientPacket::FrameAck(256),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_partial_packet_waits_for_more_bytes() {
+        let mut codec = PacketCodec::new();
+        let mut buffer = BytesMut::from(&[1u8, 21, 0, 1, 0][..]);
+        assert_eq!(codec.decode(&mut buffer).unwrap(), None);
+        buffer.extend_from_slice(&[2, b'o', b'k']);
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap(),
+            Some(ClientPacket::Join(JoinPacket {
+                spell1: 0,
+                spell2: 1,
+                session_token: "ok".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_framed_unknown_packet_is_skipped() {
+        let mut codec = PacketCodec::new();
+        let packets = decode_all(&mut codec, &[2, 99, 0, 3, 9, 9, 9, 2, 8, 0, 1, 5]);
+        assert_eq!(codec.version(), Some(FRAMED_VERSION));
+        assert_eq!(
+            packets,
+            vec![ClientPacket::Unknown { code: 99 }, ClientPacket::Action(5)]
+        );
+    }
+
+    #[test]
+    fn test_legacy_unknown_packet_is_fatal() {
+        let mut codec = PacketCodec::new();
+        let mut buffer = BytesMut::from(&[1u8, 99, 0][..]);
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(ProtocolError::UnknownLegacyPacket(99))
+        ));
+    }
+
+    #[test]
+    fn test_version_is_checked() {
+        let mut codec = PacketCodec::new();
+        let mut buffer = BytesMut::from(&[9u8, 8, 0][..]);
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(ProtocolError::UnsupportedVersion(9))
+        ));
+
+        let mut codec = PacketCodec::with_version(FRAMED_VERSION);
+        let mut buffer = BytesMut::from(&[1u8, 8, 0][..]);
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(ProtocolError::VersionMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_oversized_frame_is_rejected() {
+        let mut codec = PacketCodec::new();
+        let mut buffer = BytesMut::from(&[2u8, 8, 0xff, 0xff][..]);
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(ProtocolError::FrameTooLong { code: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn test_malformed_frame_is_consumed() {
+        let mut codec = PacketCodec::new();
+        let mut buffer = BytesMut::from(&[2u8, 18, 0, 1, 7, 2, 18, 0, 1, 1][..]);
+        assert!(matches!(
+            codec.decode(&mut buffer),
+            Err(ProtocolError::Malformed { code: 18, .. })
+        ));
+        assert_eq!(
+            codec.decode(&mut buffer).unwrap(),
+            Some(ClientPacket::Pause(PauseRequest::Resume))
+        );
+    }
+
+    #[test]
+    fn test_server_packet_encoding_follows_version() {
+        let mut legacy = BytesMut::new();
+        PacketCodec::new()
+            .encode(ServerPacket::Start(StartPacket::new(0)), &mut legacy)
+            .unwrap();
+        assert_eq!(&legacy[..], &[1, 7, 0]);
+
+        let mut framed = BytesMut::new();
+        PacketCodec::with_version(FRAMED_VERSION)
+            .encode(ServerPacket::EndGame(EndGamePacket::new(true)), &mut framed)
+            .unwrap();
+        assert_eq!(&framed[..], &[2, 12, 0, 1, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_reader_reports_closed_connection() {
+        let mut reader = PacketReader::new(&[2u8, 14, 0, 0][..]);
+        assert_eq!(
+            reader.next().await.unwrap(),
+            Some(ClientPacket::ShopRequest)
+        );
+        assert_eq!(reader.next().await.unwrap(), None);
+
+        let mut truncated = PacketReader::new(&[1u8, 16, 0][..]);
+        assert!(truncated.next().await.is_err());
+    }
+}
@@ -1,23 +1,25 @@
-use crate::game::cell::Team;
-
 #[derive(Debug)]
 pub struct EndGamePacket {
-    pub winner: Team,
+    pub win: bool,
 }
 
 impl EndGamePacket {
-    pub fn new(winner: Team) -> Self {
-        EndGamePacket { winner }
+    pub fn new(win: bool) -> Self {
+        EndGamePacket { win }
     }
 
     pub fn serialize(&self) -> Vec<u8> {
-        let mut bytes = Vec::new();
-        bytes.push(1);
-        bytes.push(12);
-        bytes.push(match self.winner {
-            Team::Red => 0,
-            Team::Blue => 1,
-        });
-        bytes
+        vec![1, 12, self.win as u8]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_end_game_packet_serialize() {
+        assert_eq!(EndGamePacket::new(true).serialize(), vec![1, 12, 1]);
+        assert_eq!(EndGamePacket::new(false).serialize(), vec![1, 12, 0]);
     }
 }
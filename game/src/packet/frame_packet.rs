@@ -0,0 +1,100 @@
+use crate::game::frame_sync::CellChange;
+use crate::packet::board_packet::BoardPacket;
+use bytes::{BufMut, BytesMut};
+
+/// Board update for clients that acknowledge frames.
+///
+/// A keyframe (`base_tick` 0) carries the view in the binary run-length encoding of
+/// `packet::rle`, a delta carries `(col, row, value)` triples to apply on top of frame
+/// `base_tick`.
+#[derive(Debug)]
+pub struct FramePacket {
+    pub version: u8,
+    pub code: u8,
+    pub tick: u32,
+    pub base_tick: u32,
+    pub board: BoardPacket,
+    pub changes: Vec<CellChange>,
+}
+
+impl FramePacket {
+    pub fn keyframe(tick: u32, board: BoardPacket) -> Self {
+        FramePacket {
+            version: 1,
+            code: 10,
+            tick,
+            base_tick: 0,
+            board,
+            changes: Vec::new(),
+        }
+    }
+
+    pub fn delta(tick: u32, base_tick: u32, board: BoardPacket, changes: Vec<CellChange>) -> Self {
+        FramePacket {
+            version: 1,
+            code: 10,
+            tick,
+            base_tick,
+            board,
+            changes,
+        }
+    }
+
+    pub fn serialize(&self) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(self.version);
+        buffer.put_u8(self.code);
+        buffer.put_u32(self.tick);
+        buffer.put_u32(self.base_tick);
+        buffer.put_u16(self.board.health);
+        buffer.put_u16(self.board.max_health);
+        buffer.put_u16(self.board.mana);
+        buffer.put_u16(self.board.max_mana);
+        buffer.put_u8(self.board.level);
+        buffer.put_u32(self.board.xp);
+        buffer.put_u32(self.board.xp_needed);
+        buffer.put_u16(self.board.respawn_in);
+        if self.base_tick == 0 {
+            buffer.put_u16(self.board.length);
+            buffer.extend_from_slice(&self.board.encoded_board);
+        } else {
+            buffer.put_u16((self.changes.len() * 3) as u16);
+            for change in &self.changes {
+                buffer.put_u8(change.col);
+                buffer.put_u8(change.row);
+                buffer.put_u8(change.value);
+            }
+        }
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(encoded_board: Vec<u8>) -> BoardPacket {
+        BoardPacket::new(400, 500, 80, 100, 2, 40, 75, encoded_board)
+    }
+
+    #[test]
+    fn test_keyframe_serialize() {
+        let bytes = FramePacket::keyframe(7, board(b"1:3".to_vec())).serialize();
+        assert_eq!(&bytes[..2], &[1, 10]);
+        assert_eq!(&bytes[2..10], &[0, 0, 0, 7, 0, 0, 0, 0]);
+        assert_eq!(bytes.len(), 10 + 19 + 2 + 3);
+        assert_eq!(&bytes[29..], &[0, 3, b'1', b':', b'3']);
+    }
+
+    #[test]
+    fn test_delta_serialize() {
+        let change = CellChange {
+            row: 3,
+            col: 40,
+            value: 4,
+        };
+        let bytes = FramePacket::delta(9, 7, board(Vec::new()), vec![change]).serialize();
+        assert_eq!(&bytes[2..10], &[0, 0, 0, 9, 0, 0, 0, 7]);
+        assert_eq!(&bytes[29..], &[0, 3, 40, 3, 4]);
+    }
+}
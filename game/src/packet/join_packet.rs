@@ -0,0 +1,55 @@
+/// Spell selection carrying the session token issued by the auth service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinPacket {
+    pub spell1: u8,
+    pub spell2: u8,
+    pub session_token: String,
+}
+
+impl JoinPacket {
+    /// Spells and token length, read before the token itself.
+    pub const FIXED_PAYLOAD_LEN: usize = 4;
+
+    /// Length of the token announced by the fixed part of the payload.
+    pub fn token_len(fixed: &[u8; Self::FIXED_PAYLOAD_LEN]) -> usize {
+        u16::from_be_bytes([fixed[2], fixed[3]]) as usize
+    }
+
+    pub fn deserialize(payload: &[u8]) -> Result<Self, &'static str> {
+        if payload.len() < Self::FIXED_PAYLOAD_LEN {
+            return Err("Join payload is too short");
+        }
+        let fixed: [u8; Self::FIXED_PAYLOAD_LEN] = payload[..Self::FIXED_PAYLOAD_LEN]
+            .try_into()
+            .expect("length checked above");
+        let token = &payload[Self::FIXED_PAYLOAD_LEN..];
+        if token.len() != Self::token_len(&fixed) {
+            return Err("Join token length does not match");
+        }
+        let session_token =
+            String::from_utf8(token.to_vec()).map_err(|_| "Join token is not valid UTF-8")?;
+        Ok(JoinPacket {
+            spell1: fixed[0],
+            spell2: fixed[1],
+            session_token,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_packet_deserialize() {
+        let mut payload = vec![3, 4, 0, 5];
+        payload.extend_from_slice(b"token");
+        let packet = JoinPacket::deserialize(&payload).unwrap();
+        assert_eq!(packet.spell1, 3);
+        assert_eq!(packet.spell2, 4);
+        assert_eq!(packet.session_token, "token");
+
+        assert!(JoinPacket::deserialize(&[3, 4, 0, 9, b'x']).is_err());
+        assert!(JoinPacket::deserialize(&[3, 4]).is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use bytes::BufMut;
+use bytes::BytesMut;
+
+/// Broadcast to every client when a champion dies, for the kill feed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KillPacket {
+    pub version: u8,
+    pub code: u8,
+    // 0 when no champion gets the kill
+    pub killer: u8,
+    pub victim: u8,
+    pub bounty: u16,
+    pub assists: Vec<u8>,
+}
+
+impl KillPacket {
+    pub fn new(killer: Option<u8>, victim: u8, bounty: u16, assists: Vec<u8>) -> Self {
+        KillPacket {
+            version: 1,
+            code: 27,
+            killer: killer.unwrap_or(0),
+            victim,
+            bounty,
+            assists,
+        }
+    }
+
+    pub fn serialize(&self) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(self.version);
+        buffer.put_u8(self.code);
+        buffer.put_u8(self.killer);
+        buffer.put_u8(self.victim);
+        buffer.put_u16(self.bounty);
+        buffer.put_u8(self.assists.len() as u8);
+        buffer.put_slice(&self.assists);
+        buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_packet_serialize() {
+        let packet = KillPacket::new(Some(2), 1, 350, vec![4, 6]);
+        assert_eq!(&packet.serialize()[..], &[1, 27, 2, 1, 1, 94, 2, 4, 6]);
+
+        let executed = KillPacket::new(None, 3, 0, Vec::new());
+        assert_eq!(&executed.serialize()[..], &[1, 27, 0, 3, 0, 0, 0]);
+    }
+}
@@ -1,6 +1,15 @@
 pub mod action_packet;
 pub mod board_packet;
+pub mod codec;
 pub mod end_game_packet;
+pub mod frame_packet;
+pub mod join_packet;
+pub mod kill_packet;
+pub mod phase_packet;
+pub mod reconnect_packet;
+pub mod rle;
 pub mod shop_packet;
+pub mod spectator_packet;
 pub mod spell_selection_packet;
 pub mod start_packet;
+pub mod viewport_packet;
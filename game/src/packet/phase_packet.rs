@@ -0,0 +1,74 @@
+use bytes::BufMut;
+use bytes::BytesMut;
+
+/// Sent whenever the match phase, its countdown or the resume votes change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhasePacket {
+    pub version: u8,
+    pub code: u8,
+    pub phase: u8,
+    pub seconds_left: u16,
+    pub resume_votes: u8,
+    pub players: u8,
+}
+
+impl PhasePacket {
+    pub fn new(phase: u8, seconds_left: u16, resume_votes: u8, players: u8) -> Self {
+        PhasePacket {
+            version: 1,
+            code: 17,
+            phase,
+            seconds_left,
+            resume_votes,
+            players,
+        }
+    }
+
+    pub fn serialize(&self) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(self.version);
+        buffer.put_u8(self.code);
+        buffer.put_u8(self.phase);
+        buffer.put_u16(self.seconds_left);
+        buffer.put_u8(self.resume_votes);
+        buffer.put_u8(self.players);
+        buffer
+    }
+}
+
+/// Sent by a client to pause the match or to vote for resuming it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseRequest {
+    Pause,
+    Resume,
+}
+
+impl PauseRequest {
+    pub fn deserialize(payload: &[u8]) -> Result<Self, &'static str> {
+        match payload {
+            [0] => Ok(PauseRequest::Pause),
+            [1] => Ok(PauseRequest::Resume),
+            [_] => Err("Unknown pause request"),
+            _ => Err("Pause request payload must be 1 byte long"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phase_packet_serialize() {
+        let packet = PhasePacket::new(1, 300, 2, 4);
+        assert_eq!(&packet.serialize()[..], &[1, 17, 1, 1, 44, 2, 4]);
+    }
+
+    #[test]
+    fn test_pause_request_deserialize() {
+        assert_eq!(PauseRequest::deserialize(&[0]), Ok(PauseRequest::Pause));
+        assert_eq!(PauseRequest::deserialize(&[1]), Ok(PauseRequest::Resume));
+        assert!(PauseRequest::deserialize(&[2]).is_err());
+        assert!(PauseRequest::deserialize(&[]).is_err());
+    }
+}
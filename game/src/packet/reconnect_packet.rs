@@ -0,0 +1,68 @@
+use bytes::BufMut;
+use bytes::BytesMut;
+
+/// Sent once a player joins, the token lets the client take its champion back after a disconnect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconnectTokenPacket {
+    pub version: u8,
+    pub code: u8,
+    pub player_id: u8,
+    pub token: u64,
+}
+
+impl ReconnectTokenPacket {
+    pub fn new(player_id: u8, token: u64) -> Self {
+        ReconnectTokenPacket {
+            version: 1,
+            code: 19,
+            player_id,
+            token,
+        }
+    }
+
+    pub fn serialize(&self) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(self.version);
+        buffer.put_u8(self.code);
+        buffer.put_u8(self.player_id);
+        buffer.put_u64(self.token);
+        buffer
+    }
+}
+
+/// Sent instead of the spell selection by a client coming back to a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPacket {
+    pub token: u64,
+}
+
+impl ReconnectPacket {
+    pub fn deserialize(payload: &[u8]) -> Result<Self, &'static str> {
+        let bytes: [u8; 8] = payload
+            .try_into()
+            .map_err(|_| "Reconnect payload must be 8 bytes long")?;
+        Ok(ReconnectPacket {
+            token: u64::from_be_bytes(bytes),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_token_round_trip() {
+        let token = 0x0102_0304_0506_0708;
+        let serialized = ReconnectTokenPacket::new(3, token).serialize();
+        assert_eq!(&serialized[..3], &[1, 19, 3]);
+
+        let packet = ReconnectPacket::deserialize(&serialized[3..]).unwrap();
+        assert_eq!(packet.token, token);
+    }
+
+    #[test]
+    fn test_reconnect_packet_rejects_short_payload() {
+        assert!(ReconnectPacket::deserialize(&[1, 2, 3]).is_err());
+    }
+}
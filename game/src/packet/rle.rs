@@ -0,0 +1,127 @@
+//! Run-length encodings of a board view, a list of encoded cell values row by row.
+//!
+//! Protocol version 1 clients read the text format `"code:count|code:count|..."`. Version 2
+//! keyframes use the binary format, each run is the cell code as a `u8` followed by its
+//! length as an unsigned LEB128 varint.
+
+/// Consecutive cells sharing the same value, as `(value, count)`.
+fn runs(view: &[u8]) -> Vec<(u8, usize)> {
+    let mut runs: Vec<(u8, usize)> = Vec::new();
+    for &value in view {
+        match runs.last_mut() {
+            Some((current, count)) if *current == value => *count += 1,
+            _ => runs.push((value, 1)),
+        }
+    }
+    runs
+}
+
+pub fn encode_text(view: &[u8]) -> Vec<u8> {
+    runs(view)
+        .iter()
+        .map(|(value, count)| format!("{}:{}", value, count))
+        .collect::<Vec<_>>()
+        .join("|")
+        .into_bytes()
+}
+
+// The server only encodes, the decoders check the encodings in tests
+#[cfg(test)]
+pub fn decode_text(encoded: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let mut view = Vec::new();
+    if encoded.is_empty() {
+        return Ok(view);
+    }
+    let text = std::str::from_utf8(encoded).map_err(|_| "RLE is not valid UTF-8")?;
+    for run in text.split('|') {
+        let (value, count) = run.split_once(':').ok_or("RLE run is missing ':'")?;
+        let value: u8 = value.parse().map_err(|_| "Invalid RLE cell value")?;
+        let count: usize = count.parse().map_err(|_| "Invalid RLE run length")?;
+        view.resize(view.len() + count, value);
+    }
+    Ok(view)
+}
+
+pub fn encode_binary(view: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    for (value, mut count) in runs(view) {
+        encoded.push(value);
+        // Seven bits per byte, the high bit flags that more bytes follow
+        while count >= 0x80 {
+            encoded.push((count as u8 & 0x7f) | 0x80);
+            count >>= 7;
+        }
+        encoded.push(count as u8);
+    }
+    encoded
+}
+
+#[cfg(test)]
+pub fn decode_binary(encoded: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let mut view = Vec::new();
+    let mut bytes = encoded.iter();
+    while let Some(&value) = bytes.next() {
+        let mut count = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = *bytes.next().ok_or("RLE run length is truncated")?;
+            if shift >= usize::BITS {
+                return Err("RLE run length is too large");
+            }
+            count |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        view.resize(view.len() + count, value);
+    }
+    Ok(view)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_view() -> Vec<u8> {
+        let mut view = vec![0; 300];
+        view.extend([1, 1, 4, 16, 16, 1]);
+        view.extend(vec![2; 128]);
+        view
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let view = sample_view();
+        let encoded = encode_text(&view);
+        assert_eq!(encoded, b"0:300|1:2|4:1|16:2|1:1|2:128");
+        assert_eq!(decode_text(&encoded).unwrap(), view);
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let view = sample_view();
+        let encoded = encode_binary(&view);
+        assert_eq!(
+            encoded,
+            vec![0, 0xac, 0x02, 1, 2, 4, 1, 16, 2, 1, 1, 2, 0x80, 0x01]
+        );
+        assert_eq!(decode_binary(&encoded).unwrap(), view);
+    }
+
+    #[test]
+    fn test_empty_view() {
+        assert!(encode_text(&[]).is_empty());
+        assert!(encode_binary(&[]).is_empty());
+        assert_eq!(decode_text(&[]).unwrap(), Vec::<u8>::new());
+        assert_eq!(decode_binary(&[]).unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_invalid_input_is_rejected() {
+        assert!(decode_text(b"1:2|3").is_err());
+        assert!(decode_text(b"x:2").is_err());
+        assert!(decode_binary(&[1, 0x80]).is_err());
+        assert!(decode_binary(&[1]).is_err());
+    }
+}
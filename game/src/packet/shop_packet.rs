@@ -20,6 +20,7 @@ impl ShopRequestPacket {
     }
 }
 
+#[derive(Debug)]
 pub struct ShopResponsePacket {
     pub version: u8,
     pub code: u8,
@@ -29,10 +30,12 @@ pub struct ShopResponsePacket {
     armor: u16,
     gold: u16,
     inventory: Vec<u16>,
+    health_regen: u16,
+    mana_regen: u16,
 }
 
 impl ShopResponsePacket {
-    pub fn new(stats: (u16, u16, u16, u16, u16), inventory: Vec<u16>) -> Self {
+    pub fn new(stats: (u16, u16, u16, u16, u16, u16, u16), inventory: Vec<u16>) -> Self {
         ShopResponsePacket {
             version: 1,
             code: 15,
@@ -42,6 +45,8 @@ impl ShopResponsePacket {
             armor: stats.3,
             gold: stats.4,
             inventory,
+            health_regen: stats.5,
+            mana_regen: stats.6,
         }
     }
 
@@ -62,6 +67,9 @@ impl ShopResponsePacket {
                 buffer.put_u16(0); // Empty slot
             }
         }
+        // Added after the inventory, the older fields keep their offsets
+        buffer.put_u16(self.health_regen);
+        buffer.put_u16(self.mana_regen);
         buffer
     }
 }
@@ -0,0 +1,78 @@
+use crate::game::cell::Team;
+use crate::game::spectator::{Camera, SpectatorVision};
+
+/// First packet of a spectator connection, `[vision]` with 0 for full vision, 1 for Blue and
+/// 2 for Red.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpectatePacket {
+    pub vision: SpectatorVision,
+}
+
+impl SpectatePacket {
+    pub fn deserialize(payload: &[u8]) -> Result<Self, &'static str> {
+        let vision = match payload {
+            [0] => SpectatorVision::Full,
+            [1] => SpectatorVision::Team(Team::Blue),
+            [2] => SpectatorVision::Team(Team::Red),
+            [_] => return Err("Unknown spectator vision"),
+            _ => return Err("Spectate payload must be 1 byte long"),
+        };
+        Ok(SpectatePacket { vision })
+    }
+}
+
+/// Camera move from a spectator, `[0][row u16][col u16]` for a free camera or
+/// `[1][player_id u16][unused u16]` to follow a champion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CameraRequest {
+    pub camera: Camera,
+}
+
+impl CameraRequest {
+    pub fn deserialize(payload: &[u8]) -> Result<Self, &'static str> {
+        let [mode, a0, a1, b0, b1] = payload else {
+            return Err("Camera payload must be 5 bytes long");
+        };
+        let first = u16::from_be_bytes([*a0, *a1]);
+        let second = u16::from_be_bytes([*b0, *b1]);
+        let camera = match mode {
+            0 => Camera::Free {
+                row: first,
+                col: second,
+            },
+            1 => Camera::Follow(first as usize),
+            _ => return Err("Unknown camera mode"),
+        };
+        Ok(CameraRequest { camera })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spectate_deserialize() {
+        assert_eq!(
+            SpectatePacket::deserialize(&[2]).unwrap().vision,
+            SpectatorVision::Team(Team::Red)
+        );
+        assert!(SpectatePacket::deserialize(&[3]).is_err());
+        assert!(SpectatePacket::deserialize(&[]).is_err());
+    }
+
+    #[test]
+    fn test_camera_deserialize() {
+        assert_eq!(
+            CameraRequest::deserialize(&[0, 0, 30, 0, 40])
+                .unwrap()
+                .camera,
+            Camera::Free { row: 30, col: 40 }
+        );
+        assert_eq!(
+            CameraRequest::deserialize(&[1, 0, 2, 0, 0]).unwrap().camera,
+            Camera::Follow(2)
+        );
+        assert!(CameraRequest::deserialize(&[2, 0, 0, 0, 0]).is_err());
+    }
+}
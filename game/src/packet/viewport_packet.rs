@@ -0,0 +1,68 @@
+use crate::game::board::Viewport;
+use bytes::BufMut;
+use bytes::BytesMut;
+
+/// Answer to a viewport request, the size the client will actually receive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewportPacket {
+    pub version: u8,
+    pub code: u8,
+    pub viewport: Viewport,
+}
+
+impl ViewportPacket {
+    pub fn new(viewport: Viewport) -> Self {
+        ViewportPacket {
+            version: 1,
+            code: 24,
+            viewport,
+        }
+    }
+
+    pub fn serialize(&self) -> BytesMut {
+        let mut buffer = BytesMut::new();
+        buffer.put_u8(self.version);
+        buffer.put_u8(self.code);
+        buffer.put_u16(self.viewport.rows);
+        buffer.put_u16(self.viewport.cols);
+        buffer
+    }
+}
+
+/// Sent by a client after joining and whenever its terminal is resized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewportRequest {
+    pub rows: u16,
+    pub cols: u16,
+}
+
+impl ViewportRequest {
+    pub fn deserialize(payload: &[u8]) -> Result<Self, &'static str> {
+        match payload {
+            [r0, r1, c0, c1] => Ok(ViewportRequest {
+                rows: u16::from_be_bytes([*r0, *r1]),
+                cols: u16::from_be_bytes([*c0, *c1]),
+            }),
+            _ => Err("Viewport payload must be 4 bytes long"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_viewport_round_trip() {
+        let viewport = Viewport {
+            rows: 31,
+            cols: 101,
+        };
+        let serialized = ViewportPacket::new(viewport).serialize();
+        assert_eq!(&serialized[..], &[1, 24, 0, 31, 0, 101]);
+
+        let request = ViewportRequest::deserialize(&serialized[2..]).unwrap();
+        assert_eq!((request.rows, request.cols), (31, 101));
+        assert!(ViewportRequest::deserialize(&[0, 31]).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fs;
+
+use crate::errors::GameError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Key shared with the auth service to check session tokens without calling it.
+///
+/// A token reads `{username}:{expires_at}:{signature}`, where `expires_at` is a unix
+/// timestamp in seconds and `signature` the hex HMAC-SHA256 of `{username}:{expires_at}`.
+#[derive(Clone)]
+pub struct SessionKey(Vec<u8>);
+
+impl SessionKey {
+    pub fn new(key: &[u8]) -> Self {
+        SessionKey(key.to_vec())
+    }
+
+    pub fn load(path: &str) -> Result<Self, GameError> {
+        let content = fs::read(path)
+            .map_err(|e| GameError::InvalidInput(format!("cannot read {}: {}", path, e)))?;
+        let key = content.trim_ascii();
+        if key.is_empty() {
+            return Err(GameError::InvalidInput(format!("{} is empty", path)));
+        }
+        Ok(SessionKey::new(key))
+    }
+
+    fn mac(&self, message: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.0).expect("HMAC accepts any key length");
+        mac.update(message.as_bytes());
+        mac
+    }
+
+    // Tokens are signed by the auth service, tests sign their own
+    #[cfg(test)]
+    pub fn sign(&self, username: &str, expires_at: u64) -> String {
+        let message = format!("{}:{}", username, expires_at);
+        let signature = hex::encode(self.mac(&message).finalize().into_bytes());
+        format!("{}:{}", message, signature)
+    }
+
+    /// Returns the username the token was issued for.
+    pub fn verify(&self, token: &str, now_unix: u64) -> Result<String, GameError> {
+        // Usernames may contain ':', so the two fixed fields are split from the end
+        let mut fields = token.rsplitn(3, ':');
+        let (Some(signature), Some(expires_at), Some(username)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(GameError::InvalidSession("malformed token"));
+        };
+        let signature =
+            hex::decode(signature).map_err(|_| GameError::InvalidSession("malformed signature"))?;
+        self.mac(&format!("{}:{}", username, expires_at))
+            .verify_slice(&signature)
+            .map_err(|_| GameError::InvalidSession("bad signature"))?;
+
+        let expires_at: u64 = expires_at
+            .parse()
+            .map_err(|_| GameError::InvalidSession("malformed expiry"))?;
+        if expires_at <= now_unix {
+            return Err(GameError::InvalidSession("token expired"));
+        }
+        if username.is_empty() {
+            return Err(GameError::InvalidSession("empty username"));
+        }
+        Ok(username.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signed_token() {
+        let key = SessionKey::new(b"shared secret");
+        let token = key.sign("alice:smith", 2_000);
+        assert_eq!(key.verify(&token, 1_000), Ok("alice:smith".to_string()));
+    }
+
+    #[test]
+    fn test_reject_tampered_or_expired_tokens() {
+        let key = SessionKey::new(b"shared secret");
+        let token = key.sign("alice", 2_000);
+
+        let forged = token.replacen("alice", "bob", 1);
+        assert_eq!(
+            key.verify(&forged, 1_000),
+            Err(GameError::InvalidSession("bad signature"))
+        );
+        let other_key = SessionKey::new(b"another secret");
+        assert!(other_key.verify(&token, 1_000).is_err());
+        assert_eq!(
+            key.verify(&token, 2_000),
+            Err(GameError::InvalidSession("token expired"))
+        );
+        assert!(key.verify("garbage", 1_000).is_err());
+    }
+}
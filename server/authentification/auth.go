@@ -45,7 +45,7 @@ func Authentificate(msg event.Message) event.Message {
 		return event.AuthMessage{Success: 0}
 	}
 	if resp.Success {
-		return event.AuthMessage{Success: 1}
+		return event.AuthMessage{Success: 1, SessionToken: resp.GetSessionToken()}
 	} else {
 		return event.AuthMessage{Success: 0}
 	}
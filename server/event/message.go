@@ -43,7 +43,9 @@ func (sm SignInMessage) Validate() error {
 }
 
 type AuthMessage struct {
-	Success int
+	Success      int
+	// Token to present to the game server, only set on a successful login
+	SessionToken string
 }
 
 func (am AuthMessage) Type() string {
@@ -118,7 +120,7 @@ func (rc RoomCreateMessage) Validate() error {
 }
 
 type RoomSearchMessage struct {
-	Success int
+	Success      int
 	RoomID  string
 	RoomIP  string
 }
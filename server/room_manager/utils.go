@@ -1,6 +1,7 @@
 package manager
 
 import (
+	"errors"
 	"fmt"
 	"os"
 	"os/exec"
@@ -8,9 +9,22 @@ import (
 	"time"
 )
 
+// Key written by the auth service, game servers need it to check session tokens
+func sessionKeyFile() string {
+	if path := os.Getenv("SESSION_KEY_FILE"); path != "" {
+		return path
+	}
+	return "session.key"
+}
+
 func StartGame(ip, map_id, max_players string) error {
 	command := "./game/target/debug/game"
 	args := []string{"--port", ip, "--map", map_id, "--max-players", max_players}
+	if keyFile := sessionKeyFile(); fileExists(keyFile) {
+		args = append(args, "--session-key-file", keyFile)
+	} else {
+		fmt.Printf("No session key at %s, game on port %s accepts players without login\n", keyFile, ip)
+	}
 	cmd := exec.Command(command, args...)
 
   FileId := rand.Intn(9999) + 1
@@ -31,6 +45,35 @@ func StartGame(ip, map_id, max_players string) error {
   }
   fmt.Printf("Rust game server process started with PID: %d on port %s\n", cmd.Process.Pid, ip)
   fmt.Fprintf(logFile, "Rust game server process started with PID: %d on port %s.\n", cmd.Process.Pid, ip)
+  go waitForGame(cmd, ip)
   time.Sleep(1 * time.Second)
 	return nil
 }
+
+func fileExists(path string) bool {
+	_, err := os.Stat(path)
+	return err == nil
+}
+
+// Exit codes of the game server once a base is destroyed
+const (
+	exitBlueWin = 10
+	exitRedWin  = 11
+)
+
+func waitForGame(cmd *exec.Cmd, ip string) {
+	err := cmd.Wait()
+	var exitErr *exec.ExitError
+	if err != nil && !errors.As(err, &exitErr) {
+		fmt.Printf("Failed to wait for game server on port %s: %q\n", ip, err)
+		return
+	}
+	switch cmd.ProcessState.ExitCode() {
+	case exitBlueWin:
+		fmt.Printf("Game on port %s ended: blue team wins\n", ip)
+	case exitRedWin:
+		fmt.Printf("Game on port %s ended: red team wins\n", ip)
+	default:
+		fmt.Printf("Game server on port %s exited with status %d\n", ip, cmd.ProcessState.ExitCode())
+	}
+}
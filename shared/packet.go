@@ -27,8 +27,35 @@ code 13: spell selection
 code 14: shop request
 code 15: shop response
 code 16: purchase item
+code 17: receive phase
+code 18: send pause
+code 19: receive reconnect token
+code 20: send reconnect
+code 21: send join
+code 22: send frame ack
+code 23: send viewport request
+code 24: receive viewport
+code 25: send spectate
+code 26: send camera
+code 27: receive kill
+
+The Rust game server also speaks the framed version 2 layout, see Frame.
 */
 
+// FramedVersion is the protocol version of [version][code][payload length u16 BE][payload]
+const FramedVersion = 2
+
+// Frame serializes a packet in the framed layout of protocol version 2
+func Frame(packet Packet) []byte {
+	legacy := packet.Serialize()
+	payload := legacy[2:]
+	buf := make([]byte, 4, 4+len(payload))
+	buf[0] = FramedVersion
+	buf[1] = legacy[1]
+	binary.BigEndian.PutUint16(buf[2:4], uint16(len(payload)))
+	return append(buf, payload...)
+}
+
 type Packet interface {
 	Version() int
 	Code() int
@@ -71,10 +98,10 @@ func CreatePacketFromMessage(msg event.Message) ([]byte, error) {
 	switch m := msg.(type) {
 	case event.AuthMessage:
 		if err := msg.Validate(); err != nil {
-			packet := NewRespPacket(true)
+			packet := NewRespPacket(true, m.SessionToken)
 			return packet.Serialize(), nil
 		}
-		packet := NewRespPacket(false)
+		packet := NewRespPacket(false, "")
 		return packet.Serialize(), nil
 	case event.RoomSearchMessage:
 		packet := NewLookRoomPacket(m.Success, m.RoomID, m.RoomIP)
@@ -172,16 +199,19 @@ func (cp *SignInPacket) Serialize() []byte {
 	return buf.Bytes()
 }
 
+// RespPacket answers a login or account creation, a login also carries the session token
 type RespPacket struct {
 	version, code int
 	Success       bool
+	SessionToken  string
 }
 
-func NewRespPacket(success bool) *RespPacket {
+func NewRespPacket(success bool, sessionToken string) *RespPacket {
 	return &RespPacket{
-		version: 1,
-		code:    2,
-		Success: success,
+		version:      1,
+		code:         2,
+		Success:      success,
+		SessionToken: sessionToken,
 	}
 }
 
@@ -202,6 +232,8 @@ func (rp *RespPacket) Serialize() []byte {
 	} else {
 		buf.WriteByte(0)
 	}
+	binary.Write(&buf, binary.BigEndian, uint16(len(rp.SessionToken)))
+	buf.WriteString(rp.SessionToken)
 	return buf.Bytes()
 }
 
@@ -422,6 +454,192 @@ func (egp *EndGamePacket) Serialize() []byte {
 	return buf.Bytes()
 }
 
+// Match phases sent in PhasePacket
+const (
+	PhaseLobby = iota
+	PhaseCountdown
+	PhaseInProgress
+	PhasePaused
+	PhaseEnded
+)
+
+type PhasePacket struct {
+	version, code int
+	Phase         int
+	SecondsLeft   int
+	ResumeVotes   int
+	Players       int
+}
+
+func NewPhasePacket(phase, secondsLeft, resumeVotes, players int) *PhasePacket {
+	return &PhasePacket{
+		version:     1,
+		code:        17,
+		Phase:       phase,
+		SecondsLeft: secondsLeft,
+		ResumeVotes: resumeVotes,
+		Players:     players,
+	}
+}
+
+func (pp PhasePacket) Version() int {
+	return pp.version
+}
+
+func (pp PhasePacket) Code() int {
+	return pp.code
+}
+
+func (pp *PhasePacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(pp.version))
+	buf.WriteByte(byte(pp.code))
+	buf.WriteByte(byte(pp.Phase))
+	binary.Write(&buf, binary.BigEndian, uint16(pp.SecondsLeft))
+	buf.WriteByte(byte(pp.ResumeVotes))
+	buf.WriteByte(byte(pp.Players))
+	return buf.Bytes()
+}
+
+// PausePacket asks to pause the match, or votes to resume it
+type PausePacket struct {
+	version, code int
+	Resume        bool
+}
+
+func NewPausePacket(resume bool) *PausePacket {
+	return &PausePacket{
+		version: 1,
+		code:    18,
+		Resume:  resume,
+	}
+}
+
+func (pp PausePacket) Version() int {
+	return pp.version
+}
+
+func (pp PausePacket) Code() int {
+	return pp.code
+}
+
+func (pp *PausePacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(pp.version))
+	buf.WriteByte(byte(pp.code))
+	if pp.Resume {
+		buf.WriteByte(1)
+	} else {
+		buf.WriteByte(0)
+	}
+	return buf.Bytes()
+}
+
+// KillPacket is broadcast by the game server when a champion dies
+type KillPacket struct {
+	version, code int
+	Killer        int // 0 when no champion gets the kill
+	Victim        int
+	Bounty        int
+	Assists       []int
+}
+
+func NewKillPacket(killer, victim, bounty int, assists []int) *KillPacket {
+	return &KillPacket{
+		version: 1,
+		code:    27,
+		Killer:  killer,
+		Victim:  victim,
+		Bounty:  bounty,
+		Assists: assists,
+	}
+}
+
+func (kp KillPacket) Version() int {
+	return kp.version
+}
+
+func (kp KillPacket) Code() int {
+	return kp.code
+}
+
+func (kp *KillPacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(kp.version))
+	buf.WriteByte(byte(kp.code))
+	buf.WriteByte(byte(kp.Killer))
+	buf.WriteByte(byte(kp.Victim))
+	binary.Write(&buf, binary.BigEndian, uint16(kp.Bounty))
+	buf.WriteByte(byte(len(kp.Assists)))
+	for _, assist := range kp.Assists {
+		buf.WriteByte(byte(assist))
+	}
+	return buf.Bytes()
+}
+
+// ReconnectTokenPacket is sent by the game server once a player joined
+type ReconnectTokenPacket struct {
+	version, code int
+	PlayerID      int
+	Token         uint64
+}
+
+func NewReconnectTokenPacket(playerID int, token uint64) *ReconnectTokenPacket {
+	return &ReconnectTokenPacket{
+		version:  1,
+		code:     19,
+		PlayerID: playerID,
+		Token:    token,
+	}
+}
+
+func (rtp ReconnectTokenPacket) Version() int {
+	return rtp.version
+}
+
+func (rtp ReconnectTokenPacket) Code() int {
+	return rtp.code
+}
+
+func (rtp *ReconnectTokenPacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(rtp.version))
+	buf.WriteByte(byte(rtp.code))
+	buf.WriteByte(byte(rtp.PlayerID))
+	binary.Write(&buf, binary.BigEndian, rtp.Token)
+	return buf.Bytes()
+}
+
+// ReconnectPacket replaces the SpellSelectionPacket when coming back to a match
+type ReconnectPacket struct {
+	version, code int
+	Token         uint64
+}
+
+func NewReconnectPacket(token uint64) *ReconnectPacket {
+	return &ReconnectPacket{
+		version: 1,
+		code:    20,
+		Token:   token,
+	}
+}
+
+func (rp ReconnectPacket) Version() int {
+	return rp.version
+}
+
+func (rp ReconnectPacket) Code() int {
+	return rp.code
+}
+
+func (rp *ReconnectPacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(rp.version))
+	buf.WriteByte(byte(rp.code))
+	binary.Write(&buf, binary.BigEndian, rp.Token)
+	return buf.Bytes()
+}
+
 type SpellSelectionPacket struct {
 	version, code  int
 	Spell1, Spell2 int
@@ -453,6 +671,42 @@ func (ssp *SpellSelectionPacket) Serialize() []byte {
 	return buf.Bytes()
 }
 
+// JoinPacket replaces the SpellSelectionPacket when the game server checks sessions
+type JoinPacket struct {
+	version, code  int
+	Spell1, Spell2 int
+	SessionToken   string
+}
+
+func NewJoinPacket(spell1, spell2 int, sessionToken string) *JoinPacket {
+	return &JoinPacket{
+		version:      1,
+		code:         21,
+		Spell1:       spell1,
+		Spell2:       spell2,
+		SessionToken: sessionToken,
+	}
+}
+
+func (jp JoinPacket) Version() int {
+	return jp.version
+}
+
+func (jp JoinPacket) Code() int {
+	return jp.code
+}
+
+func (jp *JoinPacket) Serialize() []byte {
+	var buf bytes.Buffer
+	buf.WriteByte(byte(jp.version))
+	buf.WriteByte(byte(jp.code))
+	buf.WriteByte(byte(jp.Spell1))
+	buf.WriteByte(byte(jp.Spell2))
+	binary.Write(&buf, binary.BigEndian, uint16(len(jp.SessionToken)))
+	buf.WriteString(jp.SessionToken)
+	return buf.Bytes()
+}
+
 /*
 GAME PACKETS
 */
@@ -525,9 +779,11 @@ type ShopResponsePacket struct {
 	Armor         int
 	Gold          int
 	Inventory     []int
+	HealthRegen   int
+	ManaRegen     int
 }
 
-func NewShopResponsePacket(health, mana, attack_damage, armor, gold int, inventory []int) *ShopResponsePacket {
+func NewShopResponsePacket(health, mana, attack_damage, armor, gold int, inventory []int, healthRegen, manaRegen int) *ShopResponsePacket {
 	return &ShopResponsePacket{
 		version:       1,
 		code:          15,
@@ -537,6 +793,8 @@ func NewShopResponsePacket(health, mana, attack_damage, armor, gold int, invento
 		Armor:         armor,
 		Gold:          gold,
 		Inventory:     inventory,
+		HealthRegen:   healthRegen,
+		ManaRegen:     manaRegen,
 	}
 }
 
@@ -565,6 +823,8 @@ func (srp *ShopResponsePacket) Serialize() []byte {
 			binary.Write(&buf, binary.BigEndian, uint16(0)) // Empty slot
 		}
 	}
+	binary.Write(&buf, binary.BigEndian, uint16(srp.HealthRegen))
+	binary.Write(&buf, binary.BigEndian, uint16(srp.ManaRegen))
 	return buf.Bytes()
 }
 
@@ -646,6 +906,7 @@ func (bp *BoardPacket) Serialize() []byte {
 	return buf.Bytes()
 }
 
+// DeltaPacket is only sent by the Go game server, code 10 is parsed as a FramePacket
 type DeltaPacket struct {
 	version, code int
 	TickID        uint32
@@ -694,6 +955,271 @@ func (dp *DeltaPacket) Serialize() []byte {
 	return buf.Bytes()
 }
 
+// FramePacket is the board update of a protocol version 2 connection.
+// A keyframe (BaseTick 0) holds the view in the binary run-length encoding,
+// a delta holds (col, row, value) triples to apply on top of frame BaseTick.
+type FramePacket struct {
+	version, code int
+	Tick          uint32
+	BaseTick      uint32
+	Health        int
+	MaxHealth     int
+	Mana          int
+	MaxMana       int
+	Level         int
+	Xp            int
+	XpNeeded      int
+	RespawnIn     int
+	Data          []byte
+}
+
+func NewFramePacket(tick, baseTick uint32, health, maxHealth, mana, maxMana, level, xp, xpNeeded, respawnIn int, data []byte) *FramePacket {
+	return &FramePacket{
+		version:   1,
+		code:      10,
+		Tick:      tick,
+		BaseTick:  baseTick,
+		Health:    health,
+		MaxHealth: maxHealth,
+		Mana:      mana,
+		MaxMana:   maxMana,
+		Level:     level,
+		Xp:        xp,
+		XpNeeded:  xpNeeded,
+		RespawnIn: respawnIn,
+		Data:      data,
+	}
+}
+
+func (fp FramePacket) Version() int {
+	return fp.version
+}
+
+func (fp FramePacket) Code() int {
+	return fp.code
+}
+
+func (fp *FramePacket) Serialize() []byte {
+	buf := []byte{byte(fp.version), byte(fp.code)}
+	buf = binary.BigEndian.AppendUint32(buf, fp.Tick)
+	buf = binary.BigEndian.AppendUint32(buf, fp.BaseTick)
+	buf = binary.BigEndian.AppendUint16(buf, uint16(fp.Health))
+	buf = binary.BigEndian.AppendUint16(buf, uint16(fp.MaxHealth))
+	buf = binary.BigEndian.AppendUint16(buf, uint16(fp.Mana))
+	buf = binary.BigEndian.AppendUint16(buf, uint16(fp.MaxMana))
+	buf = append(buf, byte(fp.Level))
+	buf = binary.BigEndian.AppendUint32(buf, uint32(fp.Xp))
+	buf = binary.BigEndian.AppendUint32(buf, uint32(fp.XpNeeded))
+	buf = binary.BigEndian.AppendUint16(buf, uint16(fp.RespawnIn))
+	buf = binary.BigEndian.AppendUint16(buf, uint16(len(fp.Data)))
+	return append(buf, fp.Data...)
+}
+
+// FrameAckPacket tells the game server a frame was applied, deltas are computed against it
+type FrameAckPacket struct {
+	version, code int
+	Tick          uint32
+}
+
+func NewFrameAckPacket(tick uint32) *FrameAckPacket {
+	return &FrameAckPacket{
+		version: 1,
+		code:    22,
+		Tick:    tick,
+	}
+}
+
+func (fa FrameAckPacket) Version() int {
+	return fa.version
+}
+
+func (fa FrameAckPacket) Code() int {
+	return fa.code
+}
+
+func (fa *FrameAckPacket) Serialize() []byte {
+	buf := []byte{byte(fa.version), byte(fa.code)}
+	return binary.BigEndian.AppendUint32(buf, fa.Tick)
+}
+
+// ViewportRequestPacket asks the game server for a view size, sent after joining and on resize
+type ViewportRequestPacket struct {
+	version, code int
+	Rows          int
+	Cols          int
+}
+
+func NewViewportRequestPacket(rows, cols int) *ViewportRequestPacket {
+	return &ViewportRequestPacket{
+		version: 1,
+		code:    23,
+		Rows:    rows,
+		Cols:    cols,
+	}
+}
+
+func (vr ViewportRequestPacket) Version() int {
+	return vr.version
+}
+
+func (vr ViewportRequestPacket) Code() int {
+	return vr.code
+}
+
+func (vr *ViewportRequestPacket) Serialize() []byte {
+	buf := []byte{byte(vr.version), byte(vr.code)}
+	buf = binary.BigEndian.AppendUint16(buf, uint16(vr.Rows))
+	return binary.BigEndian.AppendUint16(buf, uint16(vr.Cols))
+}
+
+// ViewportPacket is the view size the game server will actually send
+type ViewportPacket struct {
+	version, code int
+	Rows          int
+	Cols          int
+}
+
+func NewViewportPacket(rows, cols int) *ViewportPacket {
+	return &ViewportPacket{
+		version: 1,
+		code:    24,
+		Rows:    rows,
+		Cols:    cols,
+	}
+}
+
+func (vp ViewportPacket) Version() int {
+	return vp.version
+}
+
+func (vp ViewportPacket) Code() int {
+	return vp.code
+}
+
+func (vp *ViewportPacket) Serialize() []byte {
+	buf := []byte{byte(vp.version), byte(vp.code)}
+	buf = binary.BigEndian.AppendUint16(buf, uint16(vp.Rows))
+	return binary.BigEndian.AppendUint16(buf, uint16(vp.Cols))
+}
+
+// Spectator visions
+const (
+	VisionFull = 0
+	VisionBlue = 1
+	VisionRed  = 2
+)
+
+// SpectatePacket is the first packet of a spectator connection
+type SpectatePacket struct {
+	version, code int
+	Vision        int
+}
+
+func NewSpectatePacket(vision int) *SpectatePacket {
+	return &SpectatePacket{
+		version: 1,
+		code:    25,
+		Vision:  vision,
+	}
+}
+
+func (sp SpectatePacket) Version() int {
+	return sp.version
+}
+
+func (sp SpectatePacket) Code() int {
+	return sp.code
+}
+
+func (sp *SpectatePacket) Serialize() []byte {
+	return []byte{byte(sp.version), byte(sp.code), byte(sp.Vision)}
+}
+
+// CameraPacket moves a spectator camera onto a fixed cell, or makes it follow a champion
+type CameraPacket struct {
+	version, code int
+	Follow        bool
+	PlayerID      int
+	Row           int
+	Col           int
+}
+
+func NewCameraFollowPacket(playerID int) *CameraPacket {
+	return &CameraPacket{
+		version:  1,
+		code:     26,
+		Follow:   true,
+		PlayerID: playerID,
+	}
+}
+
+func NewCameraFreePacket(row, col int) *CameraPacket {
+	return &CameraPacket{
+		version: 1,
+		code:    26,
+		Row:     row,
+		Col:     col,
+	}
+}
+
+func (cp CameraPacket) Version() int {
+	return cp.version
+}
+
+func (cp CameraPacket) Code() int {
+	return cp.code
+}
+
+func (cp *CameraPacket) Serialize() []byte {
+	buf := []byte{byte(cp.version), byte(cp.code)}
+	if cp.Follow {
+		buf = append(buf, 1)
+		buf = binary.BigEndian.AppendUint16(buf, uint16(cp.PlayerID))
+		return binary.BigEndian.AppendUint16(buf, 0)
+	}
+	buf = append(buf, 0)
+	buf = binary.BigEndian.AppendUint16(buf, uint16(cp.Row))
+	return binary.BigEndian.AppendUint16(buf, uint16(cp.Col))
+}
+
+// UnknownPacket is a framed packet with a code this side does not know, its payload is skipped
+type UnknownPacket struct {
+	version, code int
+}
+
+func (up UnknownPacket) Version() int {
+	return up.version
+}
+
+func (up UnknownPacket) Code() int {
+	return up.code
+}
+
+func (up *UnknownPacket) Serialize() []byte {
+	return []byte{byte(up.version), byte(up.code), 0, 0}
+}
+
+// deSerializeFramed reads a version 2 packet by parsing its payload as the
+// matching version 1 packet. The whole frame is consumed even when it is malformed.
+func deSerializeFramed(data []byte) (Packet, int, error) {
+	if len(data) < 4 {
+		return nil, 0, errors.New("incomplete packet")
+	}
+	totalLen := 4 + int(binary.BigEndian.Uint16(data[2:4]))
+	if len(data) < totalLen {
+		return nil, 0, errors.New("incomplete packet")
+	}
+	legacy := append([]byte{1, data[1]}, data[4:totalLen]...)
+	packet, bytesConsumed, err := DeSerialize(legacy)
+	if err != nil && err.Error() == "unknown message type" {
+		return &UnknownPacket{version: FramedVersion, code: int(data[1])}, totalLen, nil
+	}
+	if err != nil || bytesConsumed != len(legacy) {
+		return nil, totalLen, errors.New("malformed packet")
+	}
+	return packet, totalLen, nil
+}
+
 // DeSerialize deserializes a byte slice into a specific Packet type.
 // It determines the packet type based on the message code and returns the
 // parsed packet, the number of bytes consumed, and an error if the packet
@@ -714,6 +1240,9 @@ func DeSerialize(data []byte) (Packet, int, error) {
 	}
 
 	version := int(data[0])
+	if version == FramedVersion {
+		return deSerializeFramed(data)
+	}
 	if version != 1 {
 		return nil, 0, errors.New("invalid version")
 	}
@@ -771,15 +1300,21 @@ func DeSerialize(data []byte) (Packet, int, error) {
 		return packet, totalLen, nil
 
 	case 2: // RespPacket
-		if len(data) < 3 {
+		if len(data) < 5 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		tokenLen := int(binary.BigEndian.Uint16(data[3:5]))
+		totalLen := 5 + tokenLen
+		if len(data) < totalLen {
 			return nil, 0, errors.New("incomplete packet")
 		}
 		packet := &RespPacket{
-			version: version,
-			code:    code,
-			Success: data[2] == 1,
+			version:      version,
+			code:         code,
+			Success:      data[2] == 1,
+			SessionToken: string(data[5:totalLen]),
 		}
-		return packet, 3, nil
+		return packet, totalLen, nil
 
 	case 3: // RoomRequestPacket
 		if len(data) < 3 {
@@ -885,28 +1420,28 @@ func DeSerialize(data []byte) (Packet, int, error) {
 		}
 		return packet, totalLen, nil
 
-	case 10: // DeltaPacket
-		if len(data) < 10 {
+	case 10: // FramePacket
+		if len(data) < 31 {
 			return nil, 0, errors.New("incomplete packet")
 		}
-		deltaCount := int(binary.BigEndian.Uint16(data[8:10]))
-		totalLen := 10 + deltaCount*3
+		totalLen := 31 + int(binary.BigEndian.Uint16(data[29:31]))
 		if len(data) < totalLen {
 			return nil, 0, errors.New("incomplete packet")
 		}
-		tickID := binary.BigEndian.Uint32(data[2:6])
-		points := [2]int{int(data[6]), int(data[7])}
-		deltas := make([][3]byte, deltaCount)
-		for i := range deltaCount {
-			start := 10 + i*3
-			copy(deltas[i][:], data[start:start+3])
-		}
-		packet := &DeltaPacket{
-			version: version,
-			code:    code,
-			TickID:  tickID,
-			Points:  points,
-			Deltas:  deltas,
+		packet := &FramePacket{
+			version:   version,
+			code:      code,
+			Tick:      binary.BigEndian.Uint32(data[2:6]),
+			BaseTick:  binary.BigEndian.Uint32(data[6:10]),
+			Health:    int(binary.BigEndian.Uint16(data[10:12])),
+			MaxHealth: int(binary.BigEndian.Uint16(data[12:14])),
+			Mana:      int(binary.BigEndian.Uint16(data[14:16])),
+			MaxMana:   int(binary.BigEndian.Uint16(data[16:18])),
+			Level:     int(data[18]),
+			Xp:        int(binary.BigEndian.Uint32(data[19:23])),
+			XpNeeded:  int(binary.BigEndian.Uint32(data[23:27])),
+			RespawnIn: int(binary.BigEndian.Uint16(data[27:29])),
+			Data:      data[31:totalLen],
 		}
 		return packet, totalLen, nil
 
@@ -945,7 +1480,7 @@ func DeSerialize(data []byte) (Packet, int, error) {
 		return packet, 4, nil
 
 	case 15: // ShopResponsePacket
-		if len(data) < 24 {
+		if len(data) < 28 {
 			return nil, 0, errors.New("incomplete packet")
 		}
 		health := int(binary.BigEndian.Uint16(data[2:4]))
@@ -958,6 +1493,8 @@ func DeSerialize(data []byte) (Packet, int, error) {
 			start := 12 + i*2
 			inventory = append(inventory, int(binary.BigEndian.Uint16(data[start:start+2])))
 		}
+		healthRegen := int(binary.BigEndian.Uint16(data[24:26]))
+		manaRegen := int(binary.BigEndian.Uint16(data[26:28]))
 		packet := &ShopResponsePacket{
 			version:       version,
 			code:          code,
@@ -967,8 +1504,10 @@ func DeSerialize(data []byte) (Packet, int, error) {
 			Armor:         armor,
 			Gold:          gold,
 			Inventory:     inventory,
+			HealthRegen:   healthRegen,
+			ManaRegen:     manaRegen,
 		}
-		return packet, 24, nil
+		return packet, 28, nil
 
 	case 16: // PurchaseItemPacket
 		if len(data) < 4 {
@@ -981,6 +1520,140 @@ func DeSerialize(data []byte) (Packet, int, error) {
 		}
 		return packet, 4, nil
 
+	case 17: // PhasePacket
+		if len(data) < 7 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		packet := &PhasePacket{
+			version:     version,
+			code:        code,
+			Phase:       int(data[2]),
+			SecondsLeft: int(binary.BigEndian.Uint16(data[3:5])),
+			ResumeVotes: int(data[5]),
+			Players:     int(data[6]),
+		}
+		return packet, 7, nil
+
+	case 18: // PausePacket
+		if len(data) < 3 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		packet := &PausePacket{
+			version: version,
+			code:    code,
+			Resume:  data[2] == 1,
+		}
+		return packet, 3, nil
+
+	case 19: // ReconnectTokenPacket
+		if len(data) < 11 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		packet := &ReconnectTokenPacket{
+			version:  version,
+			code:     code,
+			PlayerID: int(data[2]),
+			Token:    binary.BigEndian.Uint64(data[3:11]),
+		}
+		return packet, 11, nil
+
+	case 20: // ReconnectPacket
+		if len(data) < 10 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		packet := &ReconnectPacket{
+			version: version,
+			code:    code,
+			Token:   binary.BigEndian.Uint64(data[2:10]),
+		}
+		return packet, 10, nil
+
+	case 22: // FrameAckPacket
+		if len(data) < 6 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		packet := &FrameAckPacket{
+			version: version,
+			code:    code,
+			Tick:    binary.BigEndian.Uint32(data[2:6]),
+		}
+		return packet, 6, nil
+
+	case 23: // ViewportRequestPacket
+		if len(data) < 6 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		packet := &ViewportRequestPacket{
+			version: version,
+			code:    code,
+			Rows:    int(binary.BigEndian.Uint16(data[2:4])),
+			Cols:    int(binary.BigEndian.Uint16(data[4:6])),
+		}
+		return packet, 6, nil
+
+	case 24: // ViewportPacket
+		if len(data) < 6 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		packet := &ViewportPacket{
+			version: version,
+			code:    code,
+			Rows:    int(binary.BigEndian.Uint16(data[2:4])),
+			Cols:    int(binary.BigEndian.Uint16(data[4:6])),
+		}
+		return packet, 6, nil
+
+	case 25: // SpectatePacket
+		if len(data) < 3 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		packet := &SpectatePacket{
+			version: version,
+			code:    code,
+			Vision:  int(data[2]),
+		}
+		return packet, 3, nil
+
+	case 26: // CameraPacket
+		if len(data) < 7 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		first := int(binary.BigEndian.Uint16(data[3:5]))
+		second := int(binary.BigEndian.Uint16(data[5:7]))
+		packet := &CameraPacket{
+			version: version,
+			code:    code,
+			Follow:  data[2] == 1,
+		}
+		if packet.Follow {
+			packet.PlayerID = first
+		} else {
+			packet.Row, packet.Col = first, second
+		}
+		return packet, 7, nil
+
+	case 27: // KillPacket
+		if len(data) < 7 {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		totalLen := 7 + int(data[6])
+		if len(data) < totalLen {
+			return nil, 0, errors.New("incomplete packet")
+		}
+		var assists []int
+		for _, assist := range data[7:totalLen] {
+			assists = append(assists, int(assist))
+		}
+		packet := &KillPacket{
+			version: version,
+			code:    code,
+			Killer:  int(data[2]),
+			Victim:  int(data[3]),
+			Bounty:  int(binary.BigEndian.Uint16(data[4:6])),
+			Assists: assists,
+		}
+		return packet, totalLen, nil
+
 	default:
 		return nil, 0, errors.New("unknown message type")
 	}
@@ -90,8 +90,10 @@ func runClient(wg *sync.WaitGroup, clientID int, serverPort string) {
 		return
 	}
 
+	var sessionToken string
 	if respPacket, ok := packet.(*shared.RespPacket); ok && respPacket.Success {
 		log.Printf("Client %d: Login successful", clientID)
+		sessionToken = respPacket.SessionToken
 	} else {
 		log.Printf("Client %d: Login failed", clientID)
 		return
@@ -136,11 +138,11 @@ func runClient(wg *sync.WaitGroup, clientID int, serverPort string) {
 
 	log.Printf("Client %d: Connected to game server", clientID)
 
-	// 6. Send Spell Selection Packet
-	spellSelectionPacket := shared.NewSpellSelectionPacket(0, 1)
-	_, err = gameConn.Write(spellSelectionPacket.Serialize())
+	// 6. Send Join Packet with the spells and the session token
+	joinPacket := shared.NewJoinPacket(0, 1, sessionToken)
+	_, err = gameConn.Write(joinPacket.Serialize())
 	if err != nil {
-		log.Printf("Client %d: Failed to send spell selection packet: %v", clientID, err)
+		log.Printf("Client %d: Failed to send join packet: %v", clientID, err)
 		return
 	}
 